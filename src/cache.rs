@@ -0,0 +1,117 @@
+use advent_of_code_rust::{AdventOfCode, Solution};
+use puzzle_results_table::solver::{Result, Solver};
+use std::env;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+const CACHE_DIRECTORY: &str = "target/solution_cache";
+
+// Wraps a solver so that solutions computed by a previous run are reused instead of being computed
+// again. A cached solution is only reused if both the puzzle input and the binary are unchanged
+// since it was computed, so editing either automatically invalidates the cache. Cached solutions
+// are marked in the Part column of the table and reported with a duration of zero.
+pub struct CachedSolver {
+    solver: &'static AdventOfCode,
+    // Identifies the input and build the solutions were computed for. This is None if the puzzle
+    // input couldn't be read, in which case nothing is cached and the solver reports the error
+    // itself when it's executed.
+    key: Option<String>,
+    cached_solutions: Vec<Option<String>>,
+}
+
+impl CachedSolver {
+    fn new(solver: &'static AdventOfCode, build_hash: u64) -> CachedSolver {
+        let key = fs::read(solver.input_path())
+            .ok()
+            .map(|input| format!("{:016x} {build_hash:016x}", hash(&input)));
+
+        let cached_solutions = (1..=solver.part_solvers.len())
+            .map(|part| {
+                let key = key.as_ref()?;
+                let cache_file = fs::read_to_string(cache_path(solver, part)).ok()?;
+                let (cached_key, solution) = cache_file.split_once('\n')?;
+                (cached_key == key).then(|| solution.to_string())
+            })
+            .collect();
+
+        CachedSolver {
+            solver,
+            key,
+            cached_solutions,
+        }
+    }
+}
+
+impl Solver for CachedSolver {
+    fn get_row_count(&self) -> usize {
+        self.solver.get_row_count()
+    }
+
+    fn get_labels(&self, row: usize) -> Vec<String> {
+        let mut labels = self.solver.get_labels(row);
+        if self.cached_solutions[row].is_some()
+            && let Some(part_label) = labels.last_mut()
+        {
+            part_label.push_str(" (cached)");
+        }
+        labels
+    }
+
+    fn execute(&self, row: usize) -> Result {
+        if let Some(solution) = &self.cached_solutions[row] {
+            return Result {
+                solution: Solution::String(solution.clone()),
+                duration: Duration::ZERO,
+            };
+        }
+
+        let result = self.solver.execute(row);
+        // The cache is only an optimization, so if it can't be written, the solution is still
+        // valid and the error can be ignored.
+        if let Some(key) = &self.key {
+            let _ = fs::create_dir_all(CACHE_DIRECTORY).and_then(|()| {
+                fs::write(
+                    cache_path(self.solver, row + 1),
+                    format!("{key}\n{}", result.solution),
+                )
+            });
+        }
+        result
+    }
+}
+
+// Wraps every solver that matches the predicate so its solutions are cached.
+pub fn get_cached_solvers(
+    solvers: &'static [AdventOfCode],
+    solver_predicate: impl Fn(&AdventOfCode) -> bool,
+) -> Vec<CachedSolver> {
+    let build_hash = get_build_hash();
+    solvers
+        .iter()
+        .filter(|solver| solver_predicate(solver))
+        .map(|solver| CachedSolver::new(solver, build_hash))
+        .collect()
+}
+
+// Hashes the running executable, so that rebuilding with any code change invalidates the cache.
+fn get_build_hash() -> u64 {
+    let executable = env::current_exe()
+        .and_then(fs::read)
+        .expect("Should be able to read the running executable");
+    hash(&executable)
+}
+
+fn hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn cache_path(solver: &AdventOfCode, part: usize) -> PathBuf {
+    PathBuf::from(format!(
+        "{CACHE_DIRECTORY}/{}_{:02}_{part}.txt",
+        solver.year, solver.day
+    ))
+}
@@ -0,0 +1,101 @@
+use advent_of_code_rust::{AdventOfCode, DisabledReason, solvers};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::path::Path;
+
+// What is known about a single day of the calendar.
+struct CalendarDay {
+    parts: usize,
+    disabled_reason: Option<&'static DisabledReason>,
+    has_input: bool,
+}
+
+// Prints a calendar grid for every year with at least one solver matching the predicate. Each day
+// is marked with a star for every part that has a solver, followed by a flag if the solver is
+// disabled or its puzzle input is missing. This doesn't run any solvers, so it's always quick.
+pub fn print_calendar(solver_predicate: impl Fn(&AdventOfCode) -> bool) {
+    // Map from each year to the days in that year which have a solver. Days are 1-indexed, so the
+    // day is decremented by 1 to get the index into the array.
+    let mut years: BTreeMap<u16, [Option<CalendarDay>; 25]> = BTreeMap::new();
+
+    for registered_solver in solvers() {
+        let solver = registered_solver.solver;
+        if !solver_predicate(solver) {
+            continue;
+        }
+
+        let days = years
+            .entry(solver.year)
+            .or_insert_with(|| [const { None }; 25]);
+        days[usize::from(solver.day) - 1] = Some(CalendarDay {
+            parts: solver.part_solvers.len(),
+            disabled_reason: registered_solver.disabled_reason,
+            has_input: Path::new(&solver.input_path()).exists(),
+        });
+    }
+
+    let mut stdout = io::stdout().lock();
+    let mut total_stars = 0;
+
+    for (year, days) in &years {
+        let mut header = format!("{year}  ");
+        let mut marks = " ".repeat(header.len());
+        let mut stars = 0;
+        let mut solved_days = 0;
+        let mut disabled_days = 0;
+        let mut missing_inputs = 0;
+
+        for (index, day) in days.iter().take(days_in_year(*year)).enumerate() {
+            write!(header, "{:>3} ", index + 1).expect("Writing to String can't fail");
+
+            let Some(day) = day else {
+                marks.push_str("    ");
+                continue;
+            };
+
+            solved_days += 1;
+            stars += day.parts;
+            let star_marks = match day.parts {
+                1 => "* ",
+                _ => "**",
+            };
+            let flag = match day.disabled_reason {
+                Some(DisabledReason::Slow) => 'S',
+                Some(DisabledReason::StackOverflow) => 'X',
+                None if !day.has_input => '?',
+                None => ' ',
+            };
+            if day.disabled_reason.is_some() {
+                disabled_days += 1;
+            }
+            if !day.has_input {
+                missing_inputs += 1;
+            }
+            write!(marks, " {star_marks}{flag}").expect("Writing to String can't fail");
+        }
+        total_stars += stars;
+
+        writeln!(stdout, "{}", header.trim_end()).expect("Writing to stdout should succeed");
+        writeln!(stdout, "{}", marks.trim_end()).expect("Writing to stdout should succeed");
+        writeln!(
+            stdout,
+            "      {stars} stars over {solved_days} days, {disabled_days} disabled, {missing_inputs} missing inputs\n"
+        )
+        .expect("Writing to stdout should succeed");
+    }
+
+    writeln!(
+        stdout,
+        "Total: {total_stars} stars over {} years\n\
+        ** both parts solved, * only part 1 solved, S disabled for being slow, X disabled for \
+        overflowing the stack, ? puzzle input missing",
+        years.len()
+    )
+    .expect("Writing to stdout should succeed");
+}
+
+// Advent of Code ran for 25 days each year until 2025, when it was shortened to 12 days.
+fn days_in_year(year: u16) -> usize {
+    if year >= 2025 { 12 } else { 25 }
+}
@@ -1,17 +1,172 @@
-use crate::solver::AdventOfCode;
+use advent_of_code_rust::AdventOfCode;
+use advent_of_code_rust::common::vm::trace::TraceOptions;
 use std::env::Args;
+use std::str::FromStr;
 
 pub struct PuzzleDate {
     pub year: u16,
     pub day: Option<u8>,
 }
 
+pub enum Command {
+    // Run the selected solvers and display their results in a table. If use_cache is true and the
+    // "cache" feature is enabled, solutions from previous runs are reused where possible.
+    Run {
+        puzzle_dates: Vec<PuzzleDate>,
+        use_cache: bool,
+    },
+    // Display a calendar of which of the selected puzzles have solvers, without running them.
+    Calendar(Vec<PuzzleDate>),
+    // Start an HTTP server on localhost with the given port that solves puzzle inputs on request.
+    Serve(u16),
+    // Run the program in the puzzle input for a virtual machine puzzle, reporting what it does. If
+    // trace_path is given, every executed instruction is written to that file, and if interactive
+    // is true, breakpoints wait for commands from stdin. If disassemble is true, the program is
+    // written as pseudo-code instead of being run.
+    Trace {
+        year: u16,
+        day: u8,
+        part: u8,
+        options: TraceOptions,
+        trace_path: Option<String>,
+        interactive: bool,
+        disassemble: bool,
+    },
+    // Write the graph described by the puzzle input for a graph puzzle to a Graphviz DOT file at
+    // output_path, or to a file named after the puzzle's date if no path is given.
+    Graph {
+        year: u16,
+        day: u8,
+        output_path: Option<String>,
+    },
+}
+
 pub const LABEL_HEADERS: [&str; 4] = ["Year", "Day", "Puzzle", "Part"];
 
-pub fn parse_arguments(mut args: Args) -> Vec<PuzzleDate> {
+const DEFAULT_PORT: u16 = 8080;
+
+const DEFAULT_HOT_INSTRUCTIONS: usize = 10;
+
+pub fn parse_arguments(args: Args) -> Command {
+    let mut args = args.peekable();
     // Discard the first argument, which is just the executable path.
     args.next();
 
+    // The first argument may optionally be a command name. If it isn't, default to running the
+    // solvers.
+    match args.peek().map(String::as_str) {
+        Some("list" | "calendar") => {
+            args.next();
+            Command::Calendar(parse_puzzle_dates(args))
+        }
+        Some("serve") => {
+            args.next();
+            let port = args.next().map_or(DEFAULT_PORT, |port| {
+                port.parse().expect("Port should be a number")
+            });
+            Command::Serve(port)
+        }
+        Some("trace") => {
+            args.next();
+            parse_trace_arguments(args)
+        }
+        Some("graph") => {
+            args.next();
+            // The year and day are optionally followed by the output path.
+            Command::Graph {
+                year: parse_number(args.next(), "Graph", "year"),
+                day: parse_number(args.next(), "Graph", "day"),
+                output_path: args.next(),
+            }
+        }
+        _ => {
+            // The --no-cache flag may appear anywhere among the puzzle dates.
+            let mut use_cache = true;
+            let puzzle_dates = parse_puzzle_dates(args.filter(|arg| {
+                if arg == "--no-cache" {
+                    use_cache = false;
+                    false
+                } else {
+                    true
+                }
+            }));
+            Command::Run {
+                puzzle_dates,
+                use_cache,
+            }
+        }
+    }
+}
+
+// Parses the arguments after "trace", which are the year and day, optionally followed by the part
+// number (which defaults to 1), and then any number of flags in any order.
+fn parse_trace_arguments(mut args: impl Iterator<Item = String>) -> Command {
+    let year = parse_number(args.next(), "Trace", "year");
+    let day = parse_number(args.next(), "Trace", "day");
+    let mut args = args.peekable();
+    let part = if args.peek().is_some_and(|arg| !arg.starts_with("--")) {
+        parse_number(args.next(), "Trace", "part")
+    } else {
+        1
+    };
+
+    let mut options = TraceOptions {
+        hot_instructions: DEFAULT_HOT_INSTRUCTIONS,
+        ..TraceOptions::default()
+    };
+    let mut trace_path = None;
+    let mut interactive = false;
+    let mut disassemble = false;
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--break" => options.breakpoints.push(parse_number(
+                args.next(),
+                "Trace",
+                "breakpoint program counter",
+            )),
+            "--watch" => options.watches.push(
+                args.next()
+                    .expect("--watch should be followed by a register name"),
+            ),
+            "--limit" => {
+                options.step_limit = Some(parse_number(args.next(), "Trace", "step limit"));
+            }
+            "--hot" => {
+                options.hot_instructions =
+                    parse_number(args.next(), "Trace", "number of hot instructions");
+            }
+            "--trace-file" => {
+                trace_path = Some(
+                    args.next()
+                        .expect("--trace-file should be followed by a path"),
+                );
+            }
+            "--interactive" => interactive = true,
+            "--disassemble" => disassemble = true,
+            _ => panic!("Unknown trace flag \"{flag}\""),
+        }
+    }
+
+    Command::Trace {
+        year,
+        day,
+        part,
+        options,
+        trace_path,
+        interactive,
+        disassemble,
+    }
+}
+
+// Parses a numeric argument of a command, panicking with a message naming the argument if it's
+// missing or isn't a number.
+fn parse_number<T: FromStr>(arg: Option<String>, command: &str, name: &str) -> T {
+    arg.unwrap_or_else(|| panic!("{command} command should have a {name}"))
+        .parse()
+        .unwrap_or_else(|_| panic!("The {name} should be a number"))
+}
+
+fn parse_puzzle_dates(args: impl Iterator<Item = String>) -> Vec<PuzzleDate> {
     let mut puzzle_dates = Vec::new();
     // Note that arg may refer to a day or a year, but previous_arg can only refer to a year simply
     // because no branch of the following logic stores a day in it.
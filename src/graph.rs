@@ -0,0 +1,31 @@
+use advent_of_code_rust::{GRAPHABLE_SOLVERS, GraphableSolver};
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+
+// Writes the graph described by the puzzle input for the given day to a Graphviz DOT file, which
+// is named after the puzzle's date if no path is given.
+pub fn write_graph(year: u16, day: u8, output_path: Option<String>) {
+    let graphable_solver = find_graphable_solver(year, day);
+    let input =
+        fs::read_to_string(graphable_solver.solver.input_path()).expect("Error reading file");
+    let graph = (graphable_solver.export)(&input);
+
+    let output_path = output_path.unwrap_or_else(|| format!("{year}_{day:02}.dot"));
+    let mut output = BufWriter::new(File::create(&output_path).expect("Error creating graph file"));
+    graph
+        .write(
+            &format!("{year} day {day}: {}", graphable_solver.solver.title),
+            &mut output,
+        )
+        .and_then(|()| output.flush())
+        .expect("Error writing graph file");
+}
+
+fn find_graphable_solver(year: u16, day: u8) -> &'static GraphableSolver {
+    GRAPHABLE_SOLVERS
+        .iter()
+        .find(|graphable_solver| {
+            graphable_solver.solver.year == year && graphable_solver.solver.day == day
+        })
+        .unwrap_or_else(|| panic!("{year} day {day} doesn't have a graph that can be exported"))
+}
@@ -0,0 +1,157 @@
+//! Solvers for Advent of Code puzzles, along with a registry describing every solver so they can be
+//! looked up and called programmatically.
+
+pub use puzzle_results_table::solver::Solution;
+pub use solver::common;
+pub use solver::{
+    AdventOfCode, DISABLED_SOLVERS, DisabledReason, DisabledSolver, GRAPHABLE_SOLVERS,
+    GraphableSolver, SOLVERS, TRACEABLE_SOLVERS, TraceableSolver,
+};
+
+use std::any::Any;
+use std::fmt::{self, Display, Formatter};
+use std::panic;
+
+mod solver;
+
+/// A solver from the registry, along with the reason it's disabled if it's excluded from
+/// [`SOLVERS`].
+pub struct RegisteredSolver {
+    pub solver: &'static AdventOfCode,
+    pub disabled_reason: Option<&'static DisabledReason>,
+}
+
+/// Iterates over every solver in the registry, including disabled solvers. Enabled solvers come
+/// first, in the same order as [`SOLVERS`].
+pub fn solvers() -> impl Iterator<Item = RegisteredSolver> {
+    let enabled_solvers = SOLVERS.iter().map(|solver| RegisteredSolver {
+        solver,
+        disabled_reason: None,
+    });
+    let disabled_solvers = DISABLED_SOLVERS
+        .iter()
+        .map(|disabled_solver| RegisteredSolver {
+            solver: &disabled_solver.solver,
+            disabled_reason: Some(&disabled_solver.reason),
+        });
+
+    enabled_solvers.chain(disabled_solvers)
+}
+
+/// Finds the solver for the puzzle on the given year and day, including disabled solvers.
+#[must_use]
+pub fn find_solver(year: u16, day: u8) -> Option<&'static AdventOfCode> {
+    solvers()
+        .map(|registered_solver| registered_solver.solver)
+        .find(|solver| solver.year == year && solver.day == day)
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    /// There is no solver for the puzzle on this year and day.
+    UnknownPuzzle { year: u16, day: u8 },
+    /// There is a solver for the puzzle, but not for this part of it.
+    UnknownPart { year: u16, day: u8, part: u8 },
+    /// The solver panicked, most likely because the input wasn't a valid puzzle input. Contains
+    /// the panic message.
+    Panicked(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            SolveError::UnknownPuzzle { year, day } => {
+                write!(f, "no solver exists for {year} day {day}")
+            }
+            SolveError::UnknownPart { year, day, part } => {
+                write!(f, "no solver exists for {year} day {day} part {part}")
+            }
+            SolveError::Panicked(message) => write!(f, "solver panicked: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+/// Solves one part of the puzzle on the given year and day using the provided puzzle input. Parts
+/// are numbered from 1.
+///
+/// # Errors
+///
+/// Returns an error if there is no solver for the requested part, or if the solver panics, which
+/// usually means the input isn't a valid puzzle input. The panic is caught so it doesn't unwind
+/// into the caller, although the panic message is still printed by the panic hook.
+pub fn solve(year: u16, day: u8, part: u8, input: &str) -> Result<Solution, SolveError> {
+    let solver = find_solver(year, day).ok_or(SolveError::UnknownPuzzle { year, day })?;
+    let part_solver = usize::from(part)
+        .checked_sub(1)
+        .and_then(|index| solver.part_solvers.get(index))
+        .ok_or(SolveError::UnknownPart { year, day, part })?;
+
+    panic::catch_unwind(|| part_solver(input))
+        .map_err(|payload| SolveError::Panicked(panic_message(payload.as_ref())))
+}
+
+// Panic payloads are almost always a &str or a String, depending on whether the panic message was
+// formatted.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+// These tests use the Year 2015 solvers, so they can only run when those solvers are compiled.
+#[cfg(test)]
+#[cfg(feature = "year-2015")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn solve_existing_part() {
+        assert_eq!(solve(2015, 1, 1, "(()(()("), Ok(Solution::U8(3)));
+        assert_eq!(solve(2015, 1, 2, "()())"), Ok(Solution::U8(5)));
+    }
+
+    #[test]
+    fn solve_unknown_puzzle() {
+        assert_eq!(
+            solve(2015, 26, 1, ""),
+            Err(SolveError::UnknownPuzzle {
+                year: 2015,
+                day: 26
+            })
+        );
+    }
+
+    #[test]
+    fn solve_unknown_part() {
+        assert_eq!(
+            solve(2015, 1, 3, ""),
+            Err(SolveError::UnknownPart {
+                year: 2015,
+                day: 1,
+                part: 3
+            })
+        );
+        assert_eq!(
+            solve(2015, 1, 0, ""),
+            Err(SolveError::UnknownPart {
+                year: 2015,
+                day: 1,
+                part: 0
+            })
+        );
+    }
+
+    #[test]
+    fn solve_invalid_input() {
+        assert!(matches!(
+            solve(2015, 2, 1, "not a present"),
+            Err(SolveError::Panicked(_))
+        ));
+    }
+}
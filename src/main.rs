@@ -1,14 +1,59 @@
-use config::{get_solver_predicate, parse_arguments, LABEL_HEADERS};
+use advent_of_code_rust::SOLVERS;
+use cache::get_cached_solvers;
+use calendar::print_calendar;
+use config::{Command, LABEL_HEADERS, get_solver_predicate, parse_arguments};
+use graph::write_graph;
 use puzzle_results_table::create_results_table;
-use solver::SOLVERS;
+use server::serve;
 use std::env::args;
+use trace::{print_disassembly, trace};
 
+mod cache;
+mod calendar;
 mod config;
-mod solver;
+mod graph;
+mod server;
+mod trace;
 
 fn main() {
-    let puzzle_dates = parse_arguments(args());
-    let solver_predicate = get_solver_predicate(puzzle_dates);
-
-    create_results_table(&LABEL_HEADERS, &SOLVERS, solver_predicate);
+    match parse_arguments(args()) {
+        Command::Run {
+            puzzle_dates,
+            use_cache,
+        } => {
+            let solver_predicate = get_solver_predicate(puzzle_dates);
+            if cfg!(feature = "cache") && use_cache {
+                // The cached solvers have already been filtered by the predicate.
+                let cached_solvers = get_cached_solvers(SOLVERS, solver_predicate);
+                create_results_table(&LABEL_HEADERS, &cached_solvers, |_| true);
+            } else {
+                create_results_table(&LABEL_HEADERS, SOLVERS, solver_predicate);
+            }
+        }
+        Command::Calendar(puzzle_dates) => {
+            let solver_predicate = get_solver_predicate(puzzle_dates);
+            print_calendar(solver_predicate);
+        }
+        Command::Serve(port) => serve(port),
+        Command::Trace {
+            year,
+            day,
+            part,
+            options,
+            trace_path,
+            interactive,
+            disassemble,
+        } => {
+            if disassemble {
+                print_disassembly(year, day);
+            } else {
+                trace(year, day, part, options, trace_path, interactive);
+            }
+        }
+        Command::Graph {
+            year,
+            day,
+            output_path,
+        } => write_graph(year, day, output_path),
+    }
 }
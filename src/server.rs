@@ -0,0 +1,293 @@
+use advent_of_code_rust::{SolveError, solve, solvers};
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The largest request body that will be read. Puzzle inputs are far smaller than this.
+const MAX_BODY_SIZE: usize = 1 << 20;
+// The most bytes read for the request line and headers, so a client can't send an endless line.
+const MAX_HEAD_SIZE: u64 = 1 << 16;
+// How long a read or write on a connection can take before the connection is dropped.
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// A response to send back to the client. The body is always JSON.
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Response {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+}
+
+// Starts a small HTTP server on localhost which exposes the solver registry:
+// - `GET /solvers` lists every solver.
+// - `POST /solve/{year}/{day}/{part}` solves a puzzle part, taking the puzzle input as the body.
+// Each connection is handled on its own thread. Solvers that panic are caught by `solve`, request
+// bodies over `MAX_BODY_SIZE` are rejected before anything is allocated for them, and a connection
+// is dropped once a read or write on it waits longer than `TIMEOUT`.
+pub fn serve(port: u16) {
+    let listener = TcpListener::bind(("127.0.0.1", port)).expect("Should be able to bind to port");
+    writeln!(io::stdout(), "Listening on http://127.0.0.1:{port}")
+        .expect("Writing to stdout should succeed");
+
+    // Connections that fail before they're established aren't worth stopping the server over, so
+    // they're ignored.
+    for stream in listener.incoming().flatten() {
+        thread::spawn(move || handle_connection(stream));
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) {
+    // If the timeouts can't be set, the connection is unusable anyway, so drop it.
+    if stream.set_read_timeout(Some(TIMEOUT)).is_err()
+        || stream.set_write_timeout(Some(TIMEOUT)).is_err()
+    {
+        return;
+    }
+
+    let response = match read_request(&stream) {
+        Ok((method, path, body)) => route(&method, &path, &body),
+        Err(response) => response,
+    };
+
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        413 => "Content Too Large",
+        _ => "Unprocessable Content",
+    };
+    // If the client has already gone away, there's nobody to report the error to, so ignore it.
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\n\
+        Content-Type: application/json\r\n\
+        Content-Length: {}\r\n\
+        Access-Control-Allow-Origin: *\r\n\
+        Connection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    );
+}
+
+// Reads the request line, headers and body of an HTTP request, returning the method, path and
+// body. Only the Content-Length header is needed, so all other headers are skipped. If the request
+// can't be read, the error response to send is returned instead.
+fn read_request(stream: impl Read) -> Result<(String, String, String), Response> {
+    let mut reader = BufReader::new(stream.take(MAX_HEAD_SIZE));
+    let bad_request = |message: &str| Response::error(400, message);
+
+    let mut request_line = String::new();
+    reader
+        .read_line(&mut request_line)
+        .map_err(|error| read_error(&error))?;
+    let mut request_line_iter = request_line.split_whitespace();
+    let method = request_line_iter
+        .next()
+        .ok_or_else(|| bad_request("Request should have a method"))?
+        .to_string();
+    let path = request_line_iter
+        .next()
+        .ok_or_else(|| bad_request("Request should have a path"))?
+        .to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .map_err(|error| read_error(&error))?;
+        if !header.ends_with('\n') {
+            return Err(bad_request("Headers should end with an empty line"));
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| bad_request("Content-Length should be a number"))?;
+        }
+    }
+
+    // Check the length before allocating, so a huge Content-Length can't exhaust memory.
+    if content_length > MAX_BODY_SIZE {
+        return Err(Response::error(
+            413,
+            &format!("Body should be at most {MAX_BODY_SIZE} bytes"),
+        ));
+    }
+    // The head limit no longer applies, but the body can't be longer than its declared length.
+    reader.get_mut().set_limit(content_length as u64);
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|error| read_error(&error))?;
+    let body = String::from_utf8(body).map_err(|_| bad_request("Body should be valid UTF-8"))?;
+
+    Ok((method, path, body))
+}
+
+// Converts an error from reading the request into the response to send.
+fn read_error(error: &io::Error) -> Response {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            Response::error(408, "Timed out waiting for the request")
+        }
+        _ => Response::error(400, &error.to_string()),
+    }
+}
+
+fn route(method: &str, path: &str, body: &str) -> Response {
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+
+    match (method, segments.as_slice()) {
+        ("GET", ["solvers"]) => list_solvers(),
+        ("POST", ["solve", year, day, part]) => match (year.parse(), day.parse(), part.parse()) {
+            (Ok(year), Ok(day), Ok(part)) => solve_part(year, day, part, body),
+            _ => Response::error(400, "Year, day and part should be numbers"),
+        },
+        (_, ["solvers"] | ["solve", _, _, _]) => Response::error(405, "Method not allowed"),
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+fn list_solvers() -> Response {
+    let mut body = String::from("[");
+    for (index, registered_solver) in solvers().enumerate() {
+        if index != 0 {
+            body.push(',');
+        }
+        let solver = registered_solver.solver;
+        write!(
+            body,
+            "{{\"year\":{},\"day\":{},\"title\":{},\"parts\":{},\"disabled\":{}}}",
+            solver.year,
+            solver.day,
+            json_string(solver.title),
+            solver.part_solvers.len(),
+            registered_solver.disabled_reason.is_some()
+        )
+        .expect("Writing to String can't fail");
+    }
+    body.push(']');
+
+    Response { status: 200, body }
+}
+
+fn solve_part(year: u16, day: u8, part: u8, input: &str) -> Response {
+    let start = Instant::now();
+    let result = solve(year, day, part, input);
+    let duration = start.elapsed();
+
+    match result {
+        Ok(solution) => Response {
+            status: 200,
+            body: format!(
+                "{{\"year\":{year},\"day\":{day},\"part\":{part},\"answer\":{},\"duration_ms\":{}}}",
+                json_string(&solution.to_string()),
+                duration.as_secs_f64() * 1000.0
+            ),
+        },
+        Err(error @ (SolveError::UnknownPuzzle { .. } | SolveError::UnknownPart { .. })) => {
+            Response::error(404, &error.to_string())
+        }
+        Err(error @ SolveError::Panicked(_)) => Response::error(422, &error.to_string()),
+    }
+}
+
+// Encodes a string as a JSON string literal, including the surrounding quotes.
+fn json_string(string: &str) -> String {
+    let mut json = String::with_capacity(string.len() + 2);
+    json.push('"');
+    for character in string.chars() {
+        match character {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            character if character.is_control() => {
+                write!(json, "\\u{:04x}", u32::from(character))
+                    .expect("Writing to String can't fail");
+            }
+            character => json.push(character),
+        }
+    }
+    json.push('"');
+    json
+}
+
+// These tests use the Year 2015 solvers, so they can only run when those solvers are compiled.
+#[cfg(test)]
+#[cfg(feature = "year-2015")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn route_solve() {
+        let response = route("POST", "/solve/2015/1/1", "(()(()(");
+        assert_eq!(response.status, 200);
+        assert!(
+            response.body.starts_with(
+                "{\"year\":2015,\"day\":1,\"part\":1,\"answer\":\"3\",\"duration_ms\":"
+            )
+        );
+    }
+
+    #[test]
+    fn route_errors() {
+        assert_eq!(route("POST", "/solve/2015/26/1", "").status, 404);
+        assert_eq!(route("POST", "/solve/2015/1/3", "").status, 404);
+        assert_eq!(route("POST", "/solve/2015/one/1", "").status, 400);
+        assert_eq!(
+            route("POST", "/solve/2015/2/1", "not a present").status,
+            422
+        );
+        assert_eq!(route("GET", "/solve/2015/1/1", "").status, 405);
+        assert_eq!(route("GET", "/unknown", "").status, 404);
+    }
+
+    #[test]
+    fn read_requests() {
+        let request = "POST /solve/2015/1/1 HTTP/1.1\r\nContent-Length: 3\r\n\r\n(()";
+        let (method, path, body) =
+            read_request(request.as_bytes()).unwrap_or_else(|response| panic!("{}", response.body));
+        assert_eq!(
+            (method.as_str(), path.as_str(), body.as_str()),
+            ("POST", "/solve/2015/1/1", "(()")
+        );
+
+        let too_large = "POST /solve/2015/1/1 HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n";
+        let response = read_request(too_large.as_bytes())
+            .map(|_| ())
+            .expect_err("Body is too large");
+        assert_eq!(response.status, 413);
+
+        let endless_header = format!("GET /solvers HTTP/1.1\r\nX: {}", "a".repeat(1 << 17));
+        let response = read_request(endless_header.as_bytes())
+            .map(|_| ())
+            .expect_err("Header never ends");
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn escape_json_string() {
+        assert_eq!(json_string("a \"b\"\n\\c"), "\"a \\\"b\\\"\\n\\\\c\"");
+    }
+}
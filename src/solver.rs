@@ -1,5 +1,7 @@
+use common::dot::DotGraph;
+use common::vm::trace::Tracer;
 use puzzle_results_table::solver::{Result, Solution, Solver};
-use std::{fs, time::Instant};
+use std::{fs, io, time::Instant};
 
 pub struct AdventOfCode {
     pub year: u16,
@@ -8,6 +10,14 @@ pub struct AdventOfCode {
     pub part_solvers: &'static [fn(input: &str) -> Solution],
 }
 
+impl AdventOfCode {
+    // The path to the puzzle input file for this solver, relative to the repository root.
+    #[must_use]
+    pub fn input_path(&self) -> String {
+        format!("puzzle_inputs/{}/{:02}.txt", self.year, self.day)
+    }
+}
+
 impl Solver for AdventOfCode {
     fn get_row_count(&self) -> usize {
         self.part_solvers.len()
@@ -27,8 +37,7 @@ impl Solver for AdventOfCode {
     }
 
     fn execute(&self, row: usize) -> Result {
-        let file_path = format!("puzzle_inputs/{}/{:02}.txt", self.year, self.day);
-        let input = fs::read_to_string(&file_path).expect("Error reading file");
+        let input = fs::read_to_string(self.input_path()).expect("Error reading file");
         let part_solver = self.part_solvers[row];
 
         let start = Instant::now();
@@ -39,117 +48,313 @@ impl Solver for AdventOfCode {
     }
 }
 
+pub enum DisabledReason {
+    Slow,
+    StackOverflow,
+}
+
+// A solver that exists but is left out of SOLVERS so it never gets run. These are still tracked so
+// that they can be reported, e.g. in the calendar.
+pub struct DisabledSolver {
+    pub solver: AdventOfCode,
+    pub reason: DisabledReason,
+}
+
+// A solver whose puzzle input is a program for a virtual machine, which can be run by the trace
+// command to help with reverse-engineering the program. The trace function runs the program as the
+// given part of the puzzle would, reporting to the tracer as it goes. If there is a disassemble
+// function, it writes the program as pseudo-code instead.
+pub struct TraceableSolver {
+    pub solver: AdventOfCode,
+    pub trace: fn(input: &str, part: u8, tracer: &mut Tracer) -> io::Result<()>,
+    pub disassemble: Option<Disassembler>,
+}
+
+pub type Disassembler = fn(input: &str, output: &mut dyn io::Write) -> io::Result<()>;
+
+// A solver whose puzzle input describes a graph, which can be written to a Graphviz DOT file by the
+// graph command to help see its structure. The export function builds the graph from the puzzle
+// input, highlighting any nodes that the solver flags as important to its answer.
+pub struct GraphableSolver {
+    pub solver: AdventOfCode,
+    pub export: fn(input: &str) -> DotGraph,
+}
+
+pub mod common;
+#[cfg(feature = "year-2015")]
 pub mod year_2015;
+#[cfg(feature = "year-2016")]
 pub mod year_2016;
+#[cfg(feature = "year-2017")]
 pub mod year_2017;
+#[cfg(feature = "year-2023")]
 pub mod year_2023;
+#[cfg(feature = "year-2024")]
 pub mod year_2024;
+#[cfg(feature = "year-2025")]
 pub mod year_2025;
 
-pub const SOLVERS: [AdventOfCode; 100] = [
+pub const SOLVERS: &[AdventOfCode] = &[
+    #[cfg(feature = "year-2025")]
     year_2025::day_09::SOLVER,
+    #[cfg(feature = "year-2025")]
     year_2025::day_08::SOLVER,
+    #[cfg(feature = "year-2025")]
     year_2025::day_07::SOLVER,
+    #[cfg(feature = "year-2025")]
     year_2025::day_06::SOLVER,
+    #[cfg(feature = "year-2025")]
     year_2025::day_05::SOLVER,
+    #[cfg(feature = "year-2025")]
     year_2025::day_04::SOLVER,
+    #[cfg(feature = "year-2025")]
     year_2025::day_03::SOLVER,
+    #[cfg(feature = "year-2025")]
     year_2025::day_02::SOLVER,
+    #[cfg(feature = "year-2025")]
     year_2025::day_01::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_25::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_24::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_23::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_22::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_21::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_20::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_19::SOLVER,
-    // Disable this solver for now, as it takes several minutes to run.
-    // year_2024::day_18::SOLVER,
+    #[cfg(feature = "year-2024")]
+    year_2024::day_18::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_17::SOLVER,
-    // Disable this solver for now, as on the puzzle input, it does so much recursion that it
-    // sometimes overflows the stack.
-    // year_2024::day_16::SOLVER,
+    #[cfg(feature = "year-2024")]
+    year_2024::day_16::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_15::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_14::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_13::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_12::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_11::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_10::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_09::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_08::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_07::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_06::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_05::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_04::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_03::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_02::SOLVER,
+    #[cfg(feature = "year-2024")]
     year_2024::day_01::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_10::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_09::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_08::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_07::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_06::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_05::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_04::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_03::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_02::SOLVER,
+    #[cfg(feature = "year-2023")]
     year_2023::day_01::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_25::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_24::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_23::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_22::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_21::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_20::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_19::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_18::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_17::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_16::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_15::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_14::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_13::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_12::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_11::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_10::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_09::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_08::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_07::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_06::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_05::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_04::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_03::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_02::SOLVER,
+    #[cfg(feature = "year-2017")]
     year_2017::day_01::SOLVER,
+    #[cfg(feature = "year-2016")]
     year_2016::day_08::SOLVER,
+    #[cfg(feature = "year-2016")]
     year_2016::day_07::SOLVER,
+    #[cfg(feature = "year-2016")]
     year_2016::day_06::SOLVER,
+    #[cfg(feature = "year-2016")]
     year_2016::day_05::SOLVER,
+    #[cfg(feature = "year-2016")]
     year_2016::day_04::SOLVER,
+    #[cfg(feature = "year-2016")]
     year_2016::day_03::SOLVER,
+    #[cfg(feature = "year-2016")]
     year_2016::day_02::SOLVER,
+    #[cfg(feature = "year-2016")]
     year_2016::day_01::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_25::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_24::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_23::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_22::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_21::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_20::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_19::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_18::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_17::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_16::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_15::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_14::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_13::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_12::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_11::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_10::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_09::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_08::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_07::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_06::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_05::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_04::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_03::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_02::SOLVER,
+    #[cfg(feature = "year-2015")]
     year_2015::day_01::SOLVER,
 ];
+
+pub const DISABLED_SOLVERS: &[DisabledSolver] = &[];
+
+pub const TRACEABLE_SOLVERS: &[TraceableSolver] = &[
+    #[cfg(feature = "year-2024")]
+    TraceableSolver {
+        solver: year_2024::day_17::SOLVER,
+        trace: year_2024::day_17::trace,
+        disassemble: None,
+    },
+    #[cfg(feature = "year-2017")]
+    TraceableSolver {
+        solver: year_2017::day_23::SOLVER,
+        trace: year_2017::day_23::trace,
+        disassemble: Some(year_2017::day_23::disassemble),
+    },
+    #[cfg(feature = "year-2017")]
+    TraceableSolver {
+        solver: year_2017::day_18::SOLVER,
+        trace: year_2017::day_18::trace,
+        disassemble: None,
+    },
+    #[cfg(feature = "year-2017")]
+    TraceableSolver {
+        solver: year_2017::day_08::SOLVER,
+        trace: year_2017::day_08::trace,
+        disassemble: None,
+    },
+    #[cfg(feature = "year-2015")]
+    TraceableSolver {
+        solver: year_2015::day_23::SOLVER,
+        trace: year_2015::day_23::trace,
+        disassemble: None,
+    },
+];
+
+pub const GRAPHABLE_SOLVERS: &[GraphableSolver] = &[
+    #[cfg(feature = "year-2024")]
+    GraphableSolver {
+        solver: year_2024::day_24::SOLVER,
+        export: year_2024::day_24::export_graph,
+    },
+    #[cfg(feature = "year-2024")]
+    GraphableSolver {
+        solver: year_2024::day_23::SOLVER,
+        export: year_2024::day_23::export_graph,
+    },
+    #[cfg(feature = "year-2017")]
+    GraphableSolver {
+        solver: year_2017::day_12::SOLVER,
+        export: year_2017::day_12::export_graph,
+    },
+    #[cfg(feature = "year-2017")]
+    GraphableSolver {
+        solver: year_2017::day_07::SOLVER,
+        export: year_2017::day_07::export_graph,
+    },
+];
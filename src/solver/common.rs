@@ -0,0 +1,16 @@
+// Data structures and algorithms shared between solvers for puzzles from different days.
+pub mod circuit;
+pub mod combinator;
+pub mod cycle;
+pub mod dot;
+pub mod geometry;
+pub mod grid;
+pub mod hash;
+pub mod infinite_grid;
+pub mod math;
+pub mod ocr;
+pub mod parse;
+pub mod range_set;
+pub mod search;
+pub mod union_find;
+pub mod vm;
@@ -0,0 +1,395 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// The index of a wire in a [`Circuit`].
+pub type WireId = usize;
+/// The index of a gate in a [`Circuit`].
+pub type GateId = usize;
+
+/// The operations that the gates of a circuit can perform.
+pub trait Operation: Sized {
+    /// The type of signal carried by each wire.
+    type Value: Copy + FromStr;
+
+    /// Returns the operation named by a keyword in a netlist, or the operation of a gate written
+    /// without a keyword if the keyword is `None`. Returns `None` if there's no such operation.
+    fn from_keyword(keyword: Option<&str>) -> Option<Self>;
+
+    /// Returns the signal that the gate outputs for the signals on its inputs.
+    fn evaluate(&self, inputs: &[Self::Value]) -> Self::Value;
+}
+
+/// An input to a gate, which is either a wire or a fixed signal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Input<V> {
+    Wire(WireId),
+    Value(V),
+}
+
+/// A gate that performs an operation on its inputs and drives a single output wire.
+#[derive(Clone, Debug)]
+pub struct Gate<O: Operation> {
+    pub operation: O,
+    pub inputs: Vec<Input<O::Value>>,
+    pub output: WireId,
+}
+
+/// The error returned when a circuit can't be evaluated because some wires depend on themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CycleError<'a> {
+    /// One of the wires in the cycle.
+    pub wire: &'a str,
+}
+
+impl Display for CycleError<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Wire {} depends on its own signal", self.wire)
+    }
+}
+
+/// A netlist of named wires connected by gates, where each wire is driven by at most one gate.
+/// Wires that aren't driven by any gate are the circuit's inputs.
+#[derive(Clone)]
+pub struct Circuit<'a, O: Operation> {
+    wire_names: Vec<&'a str>,
+    wire_ids: FxHashMap<&'a str, WireId>,
+    gates: Vec<Gate<O>>,
+    // The gate that drives each wire, if any.
+    drivers: Vec<Option<GateId>>,
+    // The gates that read each wire.
+    readers: Vec<Vec<GateId>>,
+}
+
+impl<'a, O: Operation> Circuit<'a, O> {
+    /// Creates a circuit with no wires or gates.
+    #[must_use]
+    pub fn new() -> Self {
+        Circuit {
+            wire_names: Vec::new(),
+            wire_ids: FxHashMap::default(),
+            gates: Vec::new(),
+            drivers: Vec::new(),
+            readers: Vec::new(),
+        }
+    }
+
+    /// Parses a netlist with one gate per line, such as `x AND y -> z`. Each word before the `->`
+    /// is an operation keyword, a fixed signal, or otherwise the name of an input wire, and the
+    /// word after the `->` is the name of the output wire.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a line doesn't have a `->`, has more than one operation keyword, doesn't have a
+    /// keyword when the operation requires one, or drives a wire that is already driven.
+    #[must_use]
+    pub fn parse(netlist: &'a str) -> Self {
+        let mut circuit = Circuit::new();
+        for line in netlist.lines() {
+            let (gate, output) = line
+                .split_once(" -> ")
+                .expect("Gate should have an output after \" -> \"");
+
+            let mut operation = None;
+            let mut inputs = Vec::new();
+            for word in gate.split(' ') {
+                if let Some(keyword_operation) = O::from_keyword(Some(word)) {
+                    assert!(
+                        operation.is_none(),
+                        "Gate should have at most one operation"
+                    );
+                    operation = Some(keyword_operation);
+                } else if let Ok(value) = word.parse() {
+                    inputs.push(Input::Value(value));
+                } else {
+                    inputs.push(Input::Wire(circuit.wire(word)));
+                }
+            }
+            let operation = operation
+                .or_else(|| O::from_keyword(None))
+                .expect("Gate should have an operation");
+            let output = circuit.wire(output);
+            circuit.add_gate(operation, inputs, output);
+        }
+        circuit
+    }
+
+    /// Returns the wire with a name, adding it to the circuit if it doesn't exist yet.
+    pub fn wire(&mut self, name: &'a str) -> WireId {
+        *self.wire_ids.entry(name).or_insert_with(|| {
+            self.wire_names.push(name);
+            self.drivers.push(None);
+            self.readers.push(Vec::new());
+            self.wire_names.len() - 1
+        })
+    }
+
+    /// Adds a gate to the circuit and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the output wire is already driven by another gate.
+    pub fn add_gate(
+        &mut self,
+        operation: O,
+        inputs: Vec<Input<O::Value>>,
+        output: WireId,
+    ) -> GateId {
+        let gate = self.gates.len();
+        assert!(
+            self.drivers[output].replace(gate).is_none(),
+            "Wire {} should only be driven by one gate",
+            self.wire_names[output]
+        );
+        for input in &inputs {
+            if let Input::Wire(wire) = input
+                && !self.readers[*wire].contains(&gate)
+            {
+                self.readers[*wire].push(gate);
+            }
+        }
+        self.gates.push(Gate {
+            operation,
+            inputs,
+            output,
+        });
+        gate
+    }
+
+    /// Returns the wire with a name, if it's in the circuit.
+    #[must_use]
+    pub fn wire_id(&self, name: &str) -> Option<WireId> {
+        self.wire_ids.get(name).copied()
+    }
+
+    /// Returns the name of a wire.
+    #[must_use]
+    pub fn wire_name(&self, wire: WireId) -> &'a str {
+        self.wire_names[wire]
+    }
+
+    /// Returns the number of wires in the circuit.
+    #[must_use]
+    pub fn wire_count(&self) -> usize {
+        self.wire_names.len()
+    }
+
+    /// Returns every wire along with its name.
+    pub fn wires(&self) -> impl Iterator<Item = (WireId, &'a str)> + '_ {
+        self.wire_names.iter().copied().enumerate()
+    }
+
+    /// Returns every gate, indexed by their [`GateId`].
+    #[must_use]
+    pub fn gates(&self) -> &[Gate<O>] {
+        &self.gates
+    }
+
+    /// Returns the gate that drives a wire, or `None` if the wire is an input of the circuit.
+    #[must_use]
+    pub fn driver(&self, wire: WireId) -> Option<GateId> {
+        self.drivers[wire]
+    }
+
+    /// Returns the gates that read a wire.
+    #[must_use]
+    pub fn readers(&self, wire: WireId) -> &[GateId] {
+        &self.readers[wire]
+    }
+
+    /// Returns every gate that the signal on a wire depends on, directly or indirectly, in
+    /// increasing order.
+    #[must_use]
+    pub fn fan_in(&self, wire: WireId) -> Vec<GateId> {
+        let mut found_gates = FxHashSet::default();
+        let mut wires = vec![wire];
+        while let Some(wire) = wires.pop() {
+            if let Some(gate) = self.drivers[wire]
+                && found_gates.insert(gate)
+            {
+                wires.extend(
+                    self.gates[gate]
+                        .inputs
+                        .iter()
+                        .filter_map(|input| match input {
+                            Input::Wire(wire) => Some(*wire),
+                            Input::Value(_) => None,
+                        }),
+                );
+            }
+        }
+        let mut fan_in = found_gates.into_iter().collect::<Vec<_>>();
+        fan_in.sort_unstable();
+        fan_in
+    }
+
+    /// Swaps the output wires of two gates.
+    pub fn swap_outputs(&mut self, first_gate: GateId, second_gate: GateId) {
+        let first_output = self.gates[first_gate].output;
+        let second_output = self.gates[second_gate].output;
+        self.gates[first_gate].output = second_output;
+        self.gates[second_gate].output = first_output;
+        self.drivers[first_output] = Some(second_gate);
+        self.drivers[second_output] = Some(first_gate);
+    }
+
+    /// Returns every gate in an order where each gate comes after the gates driving its inputs.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CycleError`] if a gate depends on its own output.
+    pub fn topological_order(&self) -> Result<Vec<GateId>, CycleError<'a>> {
+        // Count how many of each gate's input wires are driven by gates that haven't been ordered
+        // yet. A gate can be ordered once this count reaches 0.
+        let mut pending_inputs = self
+            .gates
+            .iter()
+            .map(|gate| {
+                gate.inputs
+                    .iter()
+                    .filter_map(|input| match input {
+                        Input::Wire(wire) if self.drivers[*wire].is_some() => Some(*wire),
+                        _ => None,
+                    })
+                    .collect::<FxHashSet<_>>()
+                    .len()
+            })
+            .collect::<Vec<_>>();
+        let mut ready_gates = (0..self.gates.len())
+            .filter(|gate| pending_inputs[*gate] == 0)
+            .collect::<Vec<_>>();
+
+        let mut order = Vec::with_capacity(self.gates.len());
+        while let Some(gate) = ready_gates.pop() {
+            order.push(gate);
+            for reader in &self.readers[self.gates[gate].output] {
+                pending_inputs[*reader] -= 1;
+                if pending_inputs[*reader] == 0 {
+                    ready_gates.push(*reader);
+                }
+            }
+        }
+
+        // Any gate that never became ready is waiting on a cycle.
+        if let Some(gate) = pending_inputs.iter().position(|pending| *pending > 0) {
+            return Err(CycleError {
+                wire: self.wire_names[self.gates[gate].output],
+            });
+        }
+        Ok(order)
+    }
+
+    /// Returns the signal on every wire, given the signals on some wires. A gate that drives a
+    /// wire with a given signal is ignored, so any wire can be overridden. Wires whose signals
+    /// depend on a wire that isn't given a signal have no signal.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CycleError`] if a gate depends on its own output.
+    pub fn evaluate(
+        &self,
+        signals: impl IntoIterator<Item = (WireId, O::Value)>,
+    ) -> Result<Vec<Option<O::Value>>, CycleError<'a>> {
+        let mut values = vec![None; self.wire_count()];
+        for (wire, value) in signals {
+            values[wire] = Some(value);
+        }
+
+        for gate in self.topological_order()? {
+            let gate = &self.gates[gate];
+            if values[gate.output].is_some() {
+                continue;
+            }
+            let inputs = gate
+                .inputs
+                .iter()
+                .map(|input| match input {
+                    Input::Wire(wire) => values[*wire],
+                    Input::Value(value) => Some(*value),
+                })
+                .collect::<Option<Vec<_>>>();
+            values[gate.output] = inputs.map(|inputs| gate.operation.evaluate(&inputs));
+        }
+        Ok(values)
+    }
+}
+
+impl<O: Operation> Default for Circuit<'_, O> {
+    fn default() -> Self {
+        Circuit::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    enum Logic {
+        And,
+        Xor,
+        Buffer,
+    }
+
+    impl Operation for Logic {
+        type Value = bool;
+
+        fn from_keyword(keyword: Option<&str>) -> Option<Self> {
+            match keyword {
+                Some("AND") => Some(Logic::And),
+                Some("XOR") => Some(Logic::Xor),
+                None => Some(Logic::Buffer),
+                Some(_) => None,
+            }
+        }
+
+        fn evaluate(&self, inputs: &[bool]) -> bool {
+            match self {
+                Logic::And => inputs[0] && inputs[1],
+                Logic::Xor => inputs[0] ^ inputs[1],
+                Logic::Buffer => inputs[0],
+            }
+        }
+    }
+
+    // A half adder, listed with its gates out of order.
+    const HALF_ADDER: &str = "\
+sum -> s
+a XOR b -> sum
+a AND b -> c
+true AND c -> carry";
+
+    #[test]
+    fn half_adder() {
+        let mut circuit = Circuit::<Logic>::parse(HALF_ADDER);
+        let wire = |name| circuit.wire_id(name).expect("Wire should exist");
+        let (a, b, s, c, carry) = (wire("a"), wire("b"), wire("s"), wire("c"), wire("carry"));
+
+        for (a_value, b_value) in [(false, false), (false, true), (true, false), (true, true)] {
+            let values = circuit
+                .evaluate([(a, a_value), (b, b_value)])
+                .expect("Circuit should have no cycles");
+            assert_eq!(values[s], Some(a_value ^ b_value));
+            assert_eq!(values[carry], Some(a_value && b_value));
+        }
+
+        // Overriding a driven wire replaces its gate, and wires that depend on missing signals
+        // have no signal.
+        let values = circuit
+            .evaluate([(a, true), (c, false)])
+            .expect("Circuit should have no cycles");
+        assert_eq!(values[carry], Some(false));
+        assert_eq!(values[s], None);
+
+        assert_eq!(circuit.driver(a), None);
+        assert_eq!(circuit.readers(a).len(), 2);
+        assert_eq!(circuit.fan_in(carry).len(), 2);
+        assert_eq!(circuit.fan_in(s).len(), 2);
+
+        // Swapping the outputs of the XOR and buffer gates creates a cycle.
+        let sum_gate = circuit.driver(wire("sum")).expect("Sum should be driven");
+        let s_gate = circuit.driver(s).expect("S should be driven");
+        circuit.swap_outputs(sum_gate, s_gate);
+        assert_eq!(circuit.driver(s), Some(sum_gate));
+        assert!(circuit.evaluate([(a, true), (b, true)]).is_err());
+    }
+}
@@ -0,0 +1,244 @@
+use std::str::FromStr;
+
+/// Something that can parse a value from the start of some text. On success, it returns the parsed
+/// value and the rest of the text after it. On failure, it returns None, and the text can be tried
+/// with a different parser instead.
+///
+/// Every function that takes a string slice and returns an optional value and remaining text is a
+/// parser, so recursive grammars can be written as functions that use the combinators in this
+/// module to refer to themselves.
+pub trait Parser<'a, T> {
+    /// Parses a value from the start of the text.
+    fn parse(&self, input: &'a str) -> Option<(T, &'a str)>;
+
+    /// Converts the parsed value with a function.
+    fn map<U>(self, map_value: impl Fn(T) -> U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str| {
+            self.parse(input)
+                .map(|(value, rest)| (map_value(value), rest))
+        }
+    }
+
+    /// Tries this parser, and then the other parser if this one fails.
+    fn or(self, other: impl Parser<'a, T>) -> impl Parser<'a, T>
+    where
+        Self: Sized,
+    {
+        move |input: &'a str| self.parse(input).or_else(|| other.parse(input))
+    }
+}
+
+impl<'a, T, F: Fn(&'a str) -> Option<(T, &'a str)>> Parser<'a, T> for F {
+    fn parse(&self, input: &'a str) -> Option<(T, &'a str)> {
+        self(input)
+    }
+}
+
+/// Parses some exact text.
+#[must_use]
+pub fn literal<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        input
+            .strip_prefix(expected)
+            .map(|rest| (&input[..expected.len()], rest))
+    }
+}
+
+/// Parses a single character, whatever it is.
+#[must_use]
+pub fn any_char<'a>() -> impl Parser<'a, char> {
+    char_where(|_| true)
+}
+
+/// Parses a single character that satisfies a predicate.
+#[must_use]
+pub fn char_where<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, char> {
+    move |input: &'a str| {
+        let mut chars = input.chars();
+        chars
+            .next()
+            .filter(|character| predicate(*character))
+            .map(|character| (character, chars.as_str()))
+    }
+}
+
+/// Parses as many characters as possible that satisfy a predicate, which may be none at all.
+#[must_use]
+pub fn take_while<'a>(predicate: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| {
+        let end = input
+            .find(|character| !predicate(character))
+            .unwrap_or(input.len());
+        Some(input.split_at(end))
+    }
+}
+
+/// Parses an unsigned integer made of one or more digits. Fails if the number doesn't fit in the
+/// integer type.
+#[must_use]
+pub fn unsigned<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let (digits, rest) = take_while(|character| character.is_ascii_digit()).parse(input)?;
+        Some((digits.parse().ok()?, rest))
+    }
+}
+
+/// Parses a signed integer made of one or more digits, optionally preceded by a `-`. Fails if the
+/// number doesn't fit in the integer type.
+#[must_use]
+pub fn signed<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: &'a str| {
+        let (_, after_sign) = opt(literal("-")).parse(input)?;
+        let (digits, rest) =
+            take_while(|character| character.is_ascii_digit()).parse(after_sign)?;
+        if digits.is_empty() {
+            return None;
+        }
+        Some((input[..input.len() - rest.len()].parse().ok()?, rest))
+    }
+}
+
+/// Parses a value if possible, without failing if it isn't there.
+#[must_use]
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Option<T>> {
+    move |input: &'a str| {
+        Some(match parser.parse(input) {
+            Some((value, rest)) => (Some(value), rest),
+            None => (None, input),
+        })
+    }
+}
+
+/// Parses two values, one after the other.
+#[must_use]
+pub fn pair<'a, T, U>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, U>,
+) -> impl Parser<'a, (T, U)> {
+    move |input: &'a str| {
+        let (first_value, rest) = first.parse(input)?;
+        let (second_value, rest) = second.parse(rest)?;
+        Some(((first_value, second_value), rest))
+    }
+}
+
+/// Parses two values, one after the other, keeping only the second.
+#[must_use]
+pub fn preceded<'a, T, U>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, U>,
+) -> impl Parser<'a, U> {
+    pair(first, second).map(|(_, second_value)| second_value)
+}
+
+/// Parses two values, one after the other, keeping only the first.
+#[must_use]
+pub fn terminated<'a, T, U>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, U>,
+) -> impl Parser<'a, T> {
+    pair(first, second).map(|(first_value, _)| first_value)
+}
+
+/// Parses a value between an opening and closing delimiter, keeping only the value.
+#[must_use]
+pub fn delimited<'a, T, U, V>(
+    open: impl Parser<'a, T>,
+    parser: impl Parser<'a, U>,
+    close: impl Parser<'a, V>,
+) -> impl Parser<'a, U> {
+    preceded(open, terminated(parser, close))
+}
+
+/// Parses as many values as possible, one after the other, which may be none at all.
+#[must_use]
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |mut input: &'a str| {
+        let mut values = Vec::new();
+        while let Some((value, rest)) = parser.parse(input) {
+            values.push(value);
+            // Stop if nothing was consumed, as the parser would keep succeeding forever otherwise.
+            if rest.len() == input.len() {
+                break;
+            }
+            input = rest;
+        }
+        Some((values, input))
+    }
+}
+
+/// Parses as many values as possible, with a separator between each pair of values, which may be
+/// none at all.
+#[must_use]
+pub fn separated<'a, T, U>(
+    parser: impl Parser<'a, T>,
+    separator: impl Parser<'a, U>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let Some((first_value, mut input)) = parser.parse(input) else {
+            return Some((Vec::new(), input));
+        };
+        let mut values = vec![first_value];
+        while let Some((_, after_separator)) = separator.parse(input)
+            && let Some((value, rest)) = parser.parse(after_separator)
+        {
+            values.push(value);
+            input = rest;
+        }
+        Some((values, input))
+    }
+}
+
+/// Iterates over every value that can be parsed from anywhere in some text, in order. Wherever the
+/// parser fails, the scan recovers by skipping a single character and trying again, so any
+/// corrupted text between values is ignored.
+pub fn scan<'a, T>(parser: impl Parser<'a, T>, mut input: &'a str) -> impl Iterator<Item = T> {
+    std::iter::from_fn(move || {
+        loop {
+            if let Some((value, rest)) = parser.parse(input) {
+                input = rest;
+                return Some(value);
+            }
+            let mut chars = input.chars();
+            chars.next()?;
+            input = chars.as_str();
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn combinators() {
+        let point = delimited(
+            literal("("),
+            pair(terminated(signed::<i32>(), literal(",")), signed::<i32>()),
+            literal(")"),
+        );
+        assert_eq!(point.parse("(3,-4)!"), Some(((3, -4), "!")));
+        assert_eq!(point.parse("(3;-4)"), None);
+
+        let list = separated(unsigned::<u8>(), literal(", "));
+        assert_eq!(list.parse("1, 2, 3."), Some((vec![1, 2, 3], ".")));
+        assert_eq!(unsigned::<u8>().parse("300"), None);
+        assert_eq!(list.parse("x"), Some((vec![], "x")));
+
+        let word = many(char_where(char::is_alphabetic)).map(|chars| chars.len());
+        let mark_or_word = literal("?").map(|_| 0).or(word);
+        assert_eq!(mark_or_word.parse("?x"), Some((0, "x")));
+        assert_eq!(mark_or_word.parse("ab?"), Some((2, "?")));
+        assert_eq!(take_while(|c| c == 'a').parse("aab"), Some(("aa", "b")));
+        assert_eq!(opt(any_char()).parse(""), Some((None, "")));
+    }
+
+    #[test]
+    fn scanning() {
+        let values = scan(preceded(literal("x="), unsigned::<u32>()), "ax=1,x=,x=23");
+        assert_eq!(values.collect::<Vec<_>>(), vec![1, 23]);
+    }
+}
@@ -0,0 +1,155 @@
+use rustc_hash::FxHashMap;
+use std::hash::Hash;
+
+/// The shape of the sequence of states produced by repeatedly applying a function to a starting
+/// state, which must eventually repeat if there are finitely many states. The first `tail` states
+/// are never seen again, and every state after them repeats every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps from the starting state to the first state that is part of the cycle.
+    pub tail: usize,
+    /// The number of steps it takes to return to a state in the cycle.
+    pub length: usize,
+}
+
+/// Finds the cycle in the sequence of states starting from `start`, where each state is produced
+/// from the previous one by `next`. Every state is stored in a hash map until one repeats, so this
+/// takes the fewest calls to `next` but uses memory proportional to the number of distinct states.
+pub fn find_cycle<S: Eq + Hash>(mut next: impl FnMut(&S) -> S, start: S) -> Cycle {
+    let mut seen = FxHashMap::default();
+    let mut state = start;
+    let mut step = 0;
+    loop {
+        let following = next(&state);
+        if let Some(tail) = seen.insert(state, step) {
+            return Cycle {
+                tail,
+                length: step - tail,
+            };
+        }
+        state = following;
+        step += 1;
+    }
+}
+
+/// Finds the cycle in the sequence of states starting from `start` using Brent's algorithm, which
+/// only ever stores two states at once.
+pub fn brent<S: Clone + Eq>(mut next: impl FnMut(&S) -> S, start: S) -> Cycle {
+    // Move the hare ahead in stages of doubling length, moving the tortoise up to the hare at the
+    // start of each stage, until the hare lands on the tortoise. The number of steps taken in that
+    // stage is then the cycle length.
+    let mut stage_length = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = next(&start);
+    while tortoise != hare {
+        if stage_length == length {
+            tortoise = hare.clone();
+            stage_length *= 2;
+            length = 0;
+        }
+        hare = next(&hare);
+        length += 1;
+    }
+
+    // With the hare a whole cycle ahead of the tortoise, moving them together means they first
+    // meet at the start of the cycle.
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = next(&hare);
+    }
+    let mut tail = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        tail += 1;
+    }
+
+    Cycle { tail, length }
+}
+
+/// Finds the cycle in the sequence of states starting from `start` using Floyd's algorithm, which
+/// only ever stores two states at once.
+pub fn floyd<S: Clone + Eq>(mut next: impl FnMut(&S) -> S, start: S) -> Cycle {
+    // Move the hare twice as fast as the tortoise until they meet somewhere in the cycle. The
+    // tortoise has then taken a multiple of the cycle length in steps.
+    let mut tortoise = next(&start);
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        let halfway = next(&hare);
+        hare = next(&halfway);
+    }
+
+    // The tortoise is a multiple of the cycle length ahead of the start, so moving one from the
+    // start and the other from the tortoise together means they first meet at the start of the
+    // cycle.
+    let mut tail = 0;
+    let mut tortoise = start;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        tail += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        hare = next(&hare);
+        length += 1;
+    }
+
+    Cycle { tail, length }
+}
+
+/// Returns the state after applying `next` to `start` n times. Once a state repeats, the rest of
+/// the steps are skipped by jumping to the equivalent state within the cycle, so this is fast even
+/// for huge values of n. The cycle doesn't have to pass through the starting state.
+pub fn nth_state<S: Clone + Eq + Hash>(mut next: impl FnMut(&S) -> S, start: S, n: usize) -> S {
+    let mut seen = FxHashMap::default();
+    let mut states = Vec::new();
+    let mut state = start;
+    for step in 0..n {
+        if let Some(tail) = seen.insert(state.clone(), step) {
+            let length = step - tail;
+            return states.swap_remove(tail + (n - tail) % length);
+        }
+        let following = next(&state);
+        states.push(state);
+        state = following;
+    }
+    state
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A sequence with a tail of 2 (2, 5) followed by a cycle of length 6 (26, 677, 330, 901, 802,
+    // 205) that doesn't include the starting state.
+    // The cycle detectors pass states by reference, so this has to take a reference even though
+    // u32 is small enough to copy.
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn next(state: &u32) -> u32 {
+        (state * state + 1) % 1000
+    }
+
+    #[test]
+    fn cycle_detection() {
+        let expected = Cycle { tail: 2, length: 6 };
+        assert_eq!(find_cycle(next, 2), expected);
+        assert_eq!(brent(next, 2), expected);
+        assert_eq!(floyd(next, 2), expected);
+    }
+
+    #[test]
+    fn fast_forward() {
+        let mut state = 2;
+        for n in 0..50 {
+            assert_eq!(nth_state(next, 2, n), state);
+            state = next(&state);
+        }
+        assert_eq!(nth_state(next, 2, 1_000_000_000), nth_state(next, 2, 4));
+    }
+}
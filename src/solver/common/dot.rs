@@ -0,0 +1,193 @@
+use rustc_hash::FxHashMap;
+use std::io::{self, Write};
+
+// The fill color of nodes that a solver flagged.
+const HIGHLIGHT_COLOR: &str = "#ff9090";
+
+/// A graph that can be written in the Graphviz DOT language, so it can be rendered to see its
+/// structure. Nodes are identified by name, and are written in the order they were first added.
+#[derive(Clone, Debug)]
+pub struct DotGraph {
+    directed: bool,
+    nodes: Vec<DotNode>,
+    node_indexes: FxHashMap<String, usize>,
+    edges: Vec<DotEdge>,
+}
+
+#[derive(Clone, Debug)]
+struct DotNode {
+    name: String,
+    label: Option<String>,
+    highlighted: bool,
+}
+
+#[derive(Clone, Debug)]
+struct DotEdge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+}
+
+impl DotGraph {
+    /// Creates a graph with no nodes or edges, where edges have a direction if `directed` is true.
+    #[must_use]
+    pub fn new(directed: bool) -> DotGraph {
+        DotGraph {
+            directed,
+            nodes: Vec::new(),
+            node_indexes: FxHashMap::default(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds a node if it doesn't exist yet, and replaces its label if one is given. Nodes without
+    /// a label are shown with their name.
+    pub fn add_node(&mut self, name: &str, label: Option<String>) {
+        let index = self.node_index(name);
+        if label.is_some() {
+            self.nodes[index].label = label;
+        }
+    }
+
+    /// Adds an edge between two nodes, adding the nodes if they don't exist yet.
+    pub fn add_edge(&mut self, from: &str, to: &str, label: Option<String>) {
+        let from = self.node_index(from);
+        let to = self.node_index(to);
+        self.edges.push(DotEdge { from, to, label });
+    }
+
+    /// Marks a node as flagged by the solver so it stands out, adding it if it doesn't exist yet.
+    pub fn highlight(&mut self, name: &str) {
+        let index = self.node_index(name);
+        self.nodes[index].highlighted = true;
+    }
+
+    /// Writes the graph in the DOT language.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing to the output fails.
+    pub fn write(&self, name: &str, output: &mut dyn Write) -> io::Result<()> {
+        let (graph_type, edge_operator) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(output, "{graph_type} {} {{", quote(name))?;
+
+        for node in &self.nodes {
+            write!(output, "    {}", quote(&node.name))?;
+            let mut attributes = Vec::new();
+            if let Some(label) = &node.label {
+                attributes.push(format!("label={}", quote(label)));
+            }
+            if node.highlighted {
+                attributes.push(format!(
+                    "style=filled, fillcolor={}",
+                    quote(HIGHLIGHT_COLOR)
+                ));
+            }
+            write_attributes(output, &attributes)?;
+        }
+
+        for edge in &self.edges {
+            write!(
+                output,
+                "    {} {edge_operator} {}",
+                quote(&self.nodes[edge.from].name),
+                quote(&self.nodes[edge.to].name)
+            )?;
+            let attributes = edge
+                .label
+                .iter()
+                .map(|label| format!("label={}", quote(label)))
+                .collect::<Vec<_>>();
+            write_attributes(output, &attributes)?;
+        }
+
+        writeln!(output, "}}")
+    }
+
+    fn node_index(&mut self, name: &str) -> usize {
+        if let Some(index) = self.node_indexes.get(name) {
+            return *index;
+        }
+        self.nodes.push(DotNode {
+            name: name.to_string(),
+            label: None,
+            highlighted: false,
+        });
+        self.node_indexes
+            .insert(name.to_string(), self.nodes.len() - 1);
+        self.nodes.len() - 1
+    }
+}
+
+// Ends a node or edge statement, with its attributes in square brackets if it has any.
+fn write_attributes(output: &mut dyn Write, attributes: &[String]) -> io::Result<()> {
+    if attributes.is_empty() {
+        writeln!(output, ";")
+    } else {
+        writeln!(output, " [{}];", attributes.join(", "))
+    }
+}
+
+// Writes a name or label as a DOT string, which can contain any character once quotes and
+// backslashes are escaped.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for character in text.chars() {
+        match character {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            _ => quoted.push(character),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_graph() {
+        let mut graph = DotGraph::new(true);
+        graph.add_node("a", Some("a \"first\"".to_string()));
+        graph.add_edge("a", "b", Some("XOR".to_string()));
+        graph.add_edge("b", "c", None);
+        graph.highlight("c");
+
+        let mut output = Vec::new();
+        graph
+            .write("test", &mut output)
+            .expect("Writing to a Vec can't fail");
+        assert_eq!(
+            String::from_utf8(output).expect("DOT output should be valid UTF-8"),
+            "\
+digraph \"test\" {
+    \"a\" [label=\"a \\\"first\\\"\"];
+    \"b\";
+    \"c\" [style=filled, fillcolor=\"#ff9090\"];
+    \"a\" -> \"b\" [label=\"XOR\"];
+    \"b\" -> \"c\";
+}
+"
+        );
+
+        let mut graph = DotGraph::new(false);
+        graph.add_edge("x", "y", None);
+        let mut output = Vec::new();
+        graph
+            .write("pipes", &mut output)
+            .expect("Writing to a Vec can't fail");
+        assert!(
+            String::from_utf8(output)
+                .expect("DOT output should be valid UTF-8")
+                .contains("\"x\" -- \"y\";")
+        );
+    }
+}
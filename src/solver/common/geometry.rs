@@ -0,0 +1,404 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// One of the four cardinal directions. North points up the page, towards decreasing y, which
+/// matches the orientation of [`Grid`](super::grid::Grid) and of puzzle inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Every cardinal direction, in clockwise order starting from north.
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Returns the direction 90 degrees anticlockwise from this one.
+    #[must_use]
+    pub fn left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Returns the direction 90 degrees clockwise from this one.
+    #[must_use]
+    pub fn right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    /// Returns the direction 180 degrees from this one.
+    #[must_use]
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Rotates this direction 90 degrees anticlockwise in place.
+    pub fn turn_left(&mut self) {
+        *self = self.left();
+    }
+
+    /// Rotates this direction 90 degrees clockwise in place.
+    pub fn turn_right(&mut self) {
+        *self = self.right();
+    }
+
+    /// Rotates this direction 180 degrees in place.
+    pub fn turn_around(&mut self) {
+        *self = self.opposite();
+    }
+
+    /// Returns true for east and west.
+    #[must_use]
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::East | Direction::West)
+    }
+
+    /// Returns the `(dx, dy)` offset of one step in this direction, in the form taken by
+    /// [`Grid::offset`](super::grid::Grid::offset).
+    #[must_use]
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+
+    /// Returns the vector of one step in this direction.
+    #[must_use]
+    pub fn unit_vector<T: From<i8>>(self) -> Point2<T> {
+        Direction8::from(self).unit_vector()
+    }
+}
+
+/// One of the four cardinal directions or four diagonal directions. North points up the page,
+/// towards decreasing y.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Every direction, in clockwise order starting from north.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// Returns the direction 45 degrees anticlockwise from this one.
+    #[must_use]
+    pub fn left(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Returns the direction 45 degrees clockwise from this one.
+    #[must_use]
+    pub fn right(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Returns the direction 180 degrees from this one.
+    #[must_use]
+    pub fn opposite(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Returns true for the four diagonal directions.
+    #[must_use]
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Returns the `(dx, dy)` offset of one step in this direction, in the form taken by
+    /// [`Grid::offset`](super::grid::Grid::offset).
+    #[must_use]
+    pub fn offset(self) -> (isize, isize) {
+        let Point2 { x, y } = self.unit_vector();
+        (x, y)
+    }
+
+    /// Returns the vector of one step in this direction. Diagonal steps move one unit along both
+    /// axes.
+    #[must_use]
+    pub fn unit_vector<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        };
+        Point2::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Direction8 {
+        match direction {
+            Direction::North => Direction8::North,
+            Direction::East => Direction8::East,
+            Direction::South => Direction8::South,
+            Direction::West => Direction8::West,
+        }
+    }
+}
+
+/// A point or vector in 2D space. Points can be added and subtracted, and multiplied by a scalar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    /// Returns the Manhattan (taxicab) distance between two points, which is the sum of the
+    /// distances along each axis.
+    #[must_use]
+    pub fn manhattan_distance(self, other: Point2<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Returns the Chebyshev distance between two points, which is the largest of the distances
+    /// along each axis. This is the number of steps between the points when diagonal steps are
+    /// allowed.
+    #[must_use]
+    pub fn chebyshev_distance(self, other: Point2<T>) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> (T, T) {
+        (point.x, point.y)
+    }
+}
+
+/// A point or vector in 3D space. Points can be added and subtracted, and multiplied by a scalar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    /// Returns the Manhattan (taxicab) distance between two points, which is the sum of the
+    /// distances along each axis.
+    #[must_use]
+    pub fn manhattan_distance(self, other: Point3<T>) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    /// Returns the Chebyshev distance between two points, which is the largest of the distances
+    /// along each axis.
+    #[must_use]
+    pub fn chebyshev_distance(self, other: Point3<T>) -> T {
+        abs_diff(self.x, other.x)
+            .max(abs_diff(self.y, other.y))
+            .max(abs_diff(self.z, other.z))
+    }
+}
+
+// Implements the component-wise arithmetic operators for a point type with the given fields.
+macro_rules! impl_point_ops {
+    ($point:ident, $($field:ident),+) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = $point<T>;
+
+            fn add(self, other: $point<T>) -> $point<T> {
+                $point { $($field: self.$field + other.$field),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = $point<T>;
+
+            fn sub(self, other: $point<T>) -> $point<T> {
+                $point { $($field: self.$field - other.$field),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: $point<T>) {
+                $(self.$field += other.$field;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: $point<T>) {
+                $(self.$field -= other.$field;)+
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = $point<T>;
+
+            fn mul(self, scalar: T) -> $point<T> {
+                $point { $($field: self.$field * scalar),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = $point<T>;
+
+            fn neg(self) -> $point<T> {
+                $point { $($field: -self.$field),+ }
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2, x, y);
+impl_point_ops!(Point3, x, y, z);
+
+/// A horizontal or vertical line segment, including both of its end points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Line<T> {
+    pub start: Point2<T>,
+    pub end: Point2<T>,
+}
+
+impl<T: Copy + Ord> Line<T> {
+    /// Creates a line between two points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the points aren't on the same row or column.
+    #[must_use]
+    pub fn new(start: Point2<T>, end: Point2<T>) -> Line<T> {
+        assert!(
+            start.x == end.x || start.y == end.y,
+            "Line should be horizontal or vertical"
+        );
+        Line { start, end }
+    }
+
+    /// Returns the point where this line meets another line, or None if they don't meet. If the
+    /// lines overlap along more than one point, the overlapping point closest to the start of this
+    /// line is returned.
+    #[must_use]
+    pub fn intersection(&self, other: &Line<T>) -> Option<Point2<T>> {
+        // Both lines are horizontal or vertical, so each line is the same as its own bounding box,
+        // and the lines meet exactly where their bounding boxes overlap.
+        let (self_min, self_max) = self.bounds();
+        let (other_min, other_max) = other.bounds();
+        let overlap_min = Point2::new(self_min.x.max(other_min.x), self_min.y.max(other_min.y));
+        let overlap_max = Point2::new(self_max.x.min(other_max.x), self_max.y.min(other_max.y));
+        if overlap_min.x > overlap_max.x || overlap_min.y > overlap_max.y {
+            return None;
+        }
+
+        Some(Point2::new(
+            self.start.x.clamp(overlap_min.x, overlap_max.x),
+            self.start.y.clamp(overlap_min.y, overlap_max.y),
+        ))
+    }
+
+    // Returns the smallest and largest corners of the line's bounding box.
+    fn bounds(&self) -> (Point2<T>, Point2<T>) {
+        (
+            Point2::new(self.start.x.min(self.end.x), self.start.y.min(self.end.y)),
+            Point2::new(self.start.x.max(self.end.x), self.start.y.max(self.end.y)),
+        )
+    }
+}
+
+// Returns the absolute difference between two values. Comparing first avoids overflow for unsigned
+// types.
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rotate_directions() {
+        assert_eq!(Direction::North.left(), Direction::West);
+        assert_eq!(Direction::West.right(), Direction::North);
+        assert_eq!(Direction::East.opposite(), Direction::West);
+        let mut direction = Direction::South;
+        direction.turn_right();
+        assert_eq!(direction, Direction::West);
+        assert_eq!(Direction8::North.left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthEast.opposite(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.offset(), (-1, 1));
+    }
+
+    #[test]
+    fn point_arithmetic_and_distances() {
+        let a = Point2::new(1, -2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a + b, Point2::new(-2, 3));
+        assert_eq!(a - b, Point2::new(4, -7));
+        assert_eq!(b * 2, Point2::new(-6, 10));
+        assert_eq!(a + Direction::North.unit_vector() * 3, Point2::new(1, -5));
+        assert_eq!(a.manhattan_distance(b), 11);
+        assert_eq!(a.chebyshev_distance(b), 7);
+        assert_eq!(
+            Point2::new(2_usize, 7).manhattan_distance(Point2::new(5, 3)),
+            7
+        );
+        assert_eq!(
+            Point3::new(1, 2, 3).manhattan_distance(Point3::new(-1, 2, 6)),
+            5
+        );
+    }
+
+    #[test]
+    fn line_intersection() {
+        let horizontal = Line::new(Point2::new(0, 2), Point2::new(5, 2));
+        let vertical = Line::new(Point2::new(3, 5), Point2::new(3, -1));
+        assert_eq!(horizontal.intersection(&vertical), Some(Point2::new(3, 2)));
+        let parallel = Line::new(Point2::new(0, 3), Point2::new(5, 3));
+        assert_eq!(horizontal.intersection(&parallel), None);
+        let overlapping = Line::new(Point2::new(8, 2), Point2::new(4, 2));
+        assert_eq!(
+            overlapping.intersection(&horizontal),
+            Some(Point2::new(5, 2))
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use super::union_find::UnionFind;
+use rustc_hash::FxHashMap;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+// Offsets to the four orthogonally-adjacent cells, in the order up, right, down, left.
+const ORTHOGONAL_OFFSETS: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+// Offsets to all eight adjacent cells, in clockwise order starting from up.
+const ALL_OFFSETS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular 2D grid, stored as a single flat vector in row-major order. Cells are addressed by
+/// `(x, y)` positions, where x increases to the right and y increases downwards, so `(0, 0)` is the
+/// top-left cell. This matches the layout of puzzle inputs, where the first line is the top row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid where every cell is the given value.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of cells isn't a multiple of the width.
+    #[must_use]
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            width != 0 && cells.len().is_multiple_of(width),
+            "Number of cells should be a multiple of the width"
+        );
+        Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Creates a grid from text where each line is a row and each character is a cell, converting
+    /// each character with the provided function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lines don't all have the same number of characters.
+    pub fn parse(input: &str, mut parse_cell: impl FnMut(char) -> T) -> Grid<T> {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+        for line in input.lines() {
+            let row_start = cells.len();
+            cells.extend(line.chars().map(&mut parse_cell));
+            let row_width = cells.len() - row_start;
+            assert_eq!(
+                *width.get_or_insert(row_width),
+                row_width,
+                "Every line of the grid should have the same length"
+            );
+            height += 1;
+        }
+
+        Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// All cells in row-major order.
+    #[must_use]
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    #[must_use]
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Gets the cell at the position, or None if it's outside the grid.
+    #[must_use]
+    pub fn get(&self, position: (usize, usize)) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    /// Gets the cell at the position, or None if it's outside the grid.
+    pub fn get_mut(&mut self, position: (usize, usize)) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    /// Gets the cell at a position that may be negative, or None if it's outside the grid.
+    #[must_use]
+    pub fn get_signed(&self, (x, y): (isize, isize)) -> Option<&T> {
+        let x = usize::try_from(x).ok()?;
+        let y = usize::try_from(y).ok()?;
+        self.get((x, y))
+    }
+
+    /// Gets the cell at a position that may be outside the grid in any direction, treating the
+    /// grid as if it repeats infinitely in every direction.
+    #[must_use]
+    pub fn get_wrapping(&self, (x, y): (isize, isize)) -> &T {
+        // A grid can never have more than isize::MAX cells, so its width and height always fit in
+        // an isize, and rem_euclid always returns a value in 0..width or 0..height.
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        let position = (
+            x.rem_euclid(self.width as isize) as usize,
+            y.rem_euclid(self.height as isize) as usize,
+        );
+        &self[position]
+    }
+
+    /// Moves from a position by an offset, returning the new position if it's inside the grid.
+    #[must_use]
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let position = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(position).then_some(position)
+    }
+
+    /// Iterates over the positions of the up to four cells orthogonally adjacent to a position, in
+    /// the order up, right, down, left. Cells outside the grid are skipped.
+    pub fn neighbours_4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        ORTHOGONAL_OFFSETS
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Iterates over the positions of the up to eight cells adjacent to a position, including
+    /// diagonally adjacent cells, in clockwise order starting from up. Cells outside the grid are
+    /// skipped.
+    pub fn neighbours_8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        ALL_OFFSETS
+            .into_iter()
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over each row from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks_exact panics on a chunk size of 0, so use a chunk size of 1 for a grid with no
+        // columns. The grid then has no cells, so there are no rows to iterate over anyway.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// Iterates over the cells in a column from top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.rows().map(move |row| &row[x])
+    }
+
+    /// Iterates over every position in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Iterates over every position and its cell in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Finds the position of the first cell in row-major order that satisfies the predicate.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| (index % self.width, index / self.width))
+    }
+
+    /// Creates a new grid of the same size by converting every cell with the provided function.
+    pub fn map<U>(&self, map_cell: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(map_cell).collect(),
+        }
+    }
+
+    /// Creates a new grid of the same size by computing every cell from its position with the
+    /// provided function. This is useful when each new cell depends on neighbouring cells.
+    pub fn map_positions<U>(&self, map_position: impl FnMut((usize, usize)) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.positions().map(map_position).collect(),
+        }
+    }
+
+    /// Labels the connected components of the grid, where two orthogonally adjacent cells are in
+    /// the same component if the provided function returns true for them. Returns a grid of labels
+    /// and the number of components. Labels are numbered from 0 in the row-major order in which
+    /// each component first appears.
+    pub fn label_components(
+        &self,
+        mut connected: impl FnMut(&T, &T) -> bool,
+    ) -> (Grid<usize>, usize) {
+        // Connecting every cell to the cells to its right and below it covers every adjacent pair.
+        let mut union_find = UnionFind::new(self.cells.len());
+        for (index, cell) in self.cells.iter().enumerate() {
+            if (index + 1) % self.width != 0 && connected(cell, &self.cells[index + 1]) {
+                union_find.union(index, index + 1);
+            }
+            if index + self.width < self.cells.len()
+                && connected(cell, &self.cells[index + self.width])
+            {
+                union_find.union(index, index + self.width);
+            }
+        }
+
+        let mut root_labels = FxHashMap::default();
+        let cells = (0..self.cells.len())
+            .map(|index| {
+                let next_label = root_labels.len();
+                *root_labels
+                    .entry(union_find.find(index))
+                    .or_insert(next_label)
+            })
+            .collect();
+        (
+            Grid {
+                width: self.width,
+                height: self.height,
+                cells,
+            },
+            root_labels.len(),
+        )
+    }
+
+    /// Flips the grid over its main diagonal, so rows become columns and columns become rows.
+    #[must_use]
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Rotates the grid 90 degrees clockwise.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        // The left column read from bottom to top becomes the top row.
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| self[(x, y)].clone()))
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Rotates the grid 90 degrees counterclockwise.
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        // The right column read from top to bottom becomes the top row.
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Renders the grid as text with one line per row, converting each cell to a character with
+    /// the provided function. This is the inverse of [`Grid::parse`].
+    pub fn render(&self, mut render_cell: impl FnMut(&T) -> char) -> String {
+        let mut rendered = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            rendered.extend(row.iter().map(&mut render_cell));
+            rendered.push('\n');
+        }
+        rendered.pop();
+        rendered
+    }
+
+    fn index_of(&self, (x, y): (usize, usize)) -> Option<usize> {
+        self.contains((x, y)).then_some(y * self.width + x)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        let index = self
+            .index_of(position)
+            .expect("Position should be inside the grid");
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        let index = self
+            .index_of(position)
+            .expect("Position should be inside the grid");
+        &mut self.cells[index]
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y != 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example_grid() -> Grid<char> {
+        Grid::parse(
+            "\
+abc
+def",
+            |character| character,
+        )
+    }
+
+    #[test]
+    fn parse_and_index() {
+        let grid = example_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(0, 0)], 'a');
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get_signed((-1, 0)), None);
+        assert_eq!(grid.get_signed((1, 1)), Some(&'e'));
+        assert_eq!(*grid.get_wrapping((-1, -1)), 'f');
+        assert_eq!(*grid.get_wrapping((4, 2)), 'b');
+    }
+
+    #[test]
+    #[should_panic(expected = "Every line of the grid should have the same length")]
+    fn parse_ragged() {
+        let _ = Grid::parse("ab\nc", |character| character);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = example_grid();
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours_8((1, 0)).collect::<Vec<_>>(),
+            vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = example_grid();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.find(|cell| *cell == 'e'), Some((1, 1)));
+    }
+
+    #[test]
+    fn components() {
+        let grid = Grid::parse("aab\nbab\nbbc", |character| character);
+        let (labels, count) = grid.label_components(|a, b| a == b);
+        assert_eq!(count, 4);
+        assert_eq!(labels.to_string(), "001\n201\n223");
+    }
+
+    #[test]
+    fn transform() {
+        let grid = example_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(
+            grid.map(|cell| *cell == 'e')
+                .render(|cell| if *cell { '#' } else { '.' }),
+            "...\n.#."
+        );
+    }
+}
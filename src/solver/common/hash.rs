@@ -0,0 +1,3 @@
+// Hash functions used by puzzles, along with ways of computing many hashes quickly.
+pub mod knot;
+pub mod md5;
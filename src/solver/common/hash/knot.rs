@@ -0,0 +1,137 @@
+use std::fmt::Write;
+
+// The lengths appended to the input bytes before a full knot hash is computed.
+const LENGTH_SUFFIX: [u8; 5] = [17, 31, 73, 47, 23];
+
+/// A circular list of numbers being twisted by knot hash rounds, along with the position and skip
+/// size that carry over from one round to the next.
+#[derive(Clone, Debug)]
+pub struct KnotHashResult {
+    pub list: Vec<u8>,
+    position: usize,
+    skip_size: usize,
+}
+
+impl KnotHashResult {
+    /// Creates a list of the numbers from 0 up to but not including the size, with the position and
+    /// skip size both starting at 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size is 0 or more than 256.
+    #[must_use]
+    pub fn new(size: usize) -> KnotHashResult {
+        assert!(
+            (1..=256).contains(&size),
+            "Knot hash list should have between 1 and 256 numbers"
+        );
+        KnotHashResult {
+            list: (0..=255).take(size).collect(),
+            position: 0,
+            skip_size: 0,
+        }
+    }
+}
+
+/// Performs one round of the knot hash, reversing a section of the list for each length.
+pub fn knot_hash_round(knot_hash: &mut KnotHashResult, lengths: &[u8]) {
+    for length in lengths {
+        let length = usize::from(*length);
+
+        // A length of 0 needs to be explicitly skipped as it will set up invalid initial
+        // conditions. Fortunately, a length of 0 is a no-op anyway so no additional handling is
+        // needed.
+        if length != 0 {
+            let mut start_position = knot_hash.position;
+            let mut end_position = (start_position + length - 1) % knot_hash.list.len();
+
+            while start_position != end_position {
+                knot_hash.list.swap(start_position, end_position);
+
+                start_position = (start_position + 1) % knot_hash.list.len();
+                // If length is even, the positions will be equal at this intermediate step instead
+                // of when both start_position and end_position are updated, so add an extra check
+                // here to end the loop if they're equal.
+                if start_position == end_position {
+                    break;
+                }
+                // In mod n arithmetic, adding n - 1 is equivalent to subtracting 1. This trick is
+                // used here to prevent the possibility of subtracting 1 from a usize with value 0
+                // which would cause an overflow.
+                end_position = (end_position + knot_hash.list.len() - 1) % knot_hash.list.len();
+            }
+        }
+
+        knot_hash.position =
+            (knot_hash.position + length + knot_hash.skip_size) % knot_hash.list.len();
+        knot_hash.skip_size += 1;
+    }
+}
+
+/// Returns the sparse hash of some bytes, which is the list of 256 numbers after 64 rounds using
+/// the bytes followed by the standard length suffix as the lengths.
+#[must_use]
+pub fn sparse_hash(bytes: &[u8]) -> Vec<u8> {
+    let lengths = [bytes, &LENGTH_SUFFIX].concat();
+    let mut result = KnotHashResult::new(256);
+    for _ in 0..64 {
+        knot_hash_round(&mut result, &lengths);
+    }
+    result.list
+}
+
+/// Condenses a sparse hash into a dense hash by combining each block of 16 numbers with XOR.
+///
+/// # Panics
+///
+/// Panics if the sparse hash doesn't have exactly 256 numbers.
+#[must_use]
+pub fn dense_hash(sparse_hash: &[u8]) -> [u8; 16] {
+    assert_eq!(
+        sparse_hash.len(),
+        256,
+        "Sparse hash should have 256 numbers"
+    );
+    let mut dense_hash = [0; 16];
+    for (dense_number, block) in dense_hash.iter_mut().zip(sparse_hash.chunks_exact(16)) {
+        *dense_number = block.iter().fold(0, |acc, number| acc ^ number);
+    }
+    dense_hash
+}
+
+/// Returns the full knot hash of some bytes.
+#[must_use]
+pub fn knot_hash(bytes: &[u8]) -> [u8; 16] {
+    dense_hash(&sparse_hash(bytes))
+}
+
+/// Writes a hash out as lowercase hexadecimal digits, two per byte.
+#[must_use]
+pub fn to_hex(hash: &[u8]) -> String {
+    hash.iter()
+        .fold(String::with_capacity(hash.len() * 2), |mut acc, byte| {
+            write!(acc, "{byte:0>2x}").expect("Writing to String can't fail");
+            acc
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round() {
+        let mut knot_hash = KnotHashResult::new(5);
+        knot_hash_round(&mut knot_hash, &[3, 4, 1, 5]);
+        assert_eq!(knot_hash.list, [3, 4, 2, 1, 0]);
+    }
+
+    #[test]
+    fn full_hash() {
+        assert_eq!(to_hex(&knot_hash(b"")), "a2582a3a0e66e6e86e3812dcb672a272");
+        assert_eq!(
+            to_hex(&dense_hash(&sparse_hash(b"1,2,4"))),
+            "63960835bcdc130f0b66d7ff4f6a5a8e"
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use rayon::prelude::*;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+// The amount each word is rotated by in each of the 64 operations.
+const S: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+// The constants added in each of the 64 operations, from the sines of the integers.
+const K: [u32; 64] = [
+    0xd76a_a478,
+    0xe8c7_b756,
+    0x2420_70db,
+    0xc1bd_ceee,
+    0xf57c_0faf,
+    0x4787_c62a,
+    0xa830_4613,
+    0xfd46_9501,
+    0x6980_98d8,
+    0x8b44_f7af,
+    0xffff_5bb1,
+    0x895c_d7be,
+    0x6b90_1122,
+    0xfd98_7193,
+    0xa679_438e,
+    0x49b4_0821,
+    0xf61e_2562,
+    0xc040_b340,
+    0x265e_5a51,
+    0xe9b6_c7aa,
+    0xd62f_105d,
+    0x0244_1453,
+    0xd8a1_e681,
+    0xe7d3_fbc8,
+    0x21e1_cde6,
+    0xc337_07d6,
+    0xf4d5_0d87,
+    0x455a_14ed,
+    0xa9e3_e905,
+    0xfcef_a3f8,
+    0x676f_02d9,
+    0x8d2a_4c8a,
+    0xfffa_3942,
+    0x8771_f681,
+    0x6d9d_6122,
+    0xfde5_380c,
+    0xa4be_ea44,
+    0x4bde_cfa9,
+    0xf6bb_4b60,
+    0xbebf_bc70,
+    0x289b_7ec6,
+    0xeaa1_27fa,
+    0xd4ef_3085,
+    0x0488_1d05,
+    0xd9d4_d039,
+    0xe6db_99e5,
+    0x1fa2_7cf8,
+    0xc4ac_5665,
+    0xf429_2244,
+    0x432a_ff97,
+    0xab94_23a7,
+    0xfc93_a039,
+    0x655b_59c3,
+    0x8f0c_cc92,
+    0xffef_f47d,
+    0x8584_5dd1,
+    0x6fa8_7e4f,
+    0xfe2c_e6e0,
+    0xa301_4314,
+    0x4e08_11a1,
+    0xf753_7e82,
+    0xbd3a_f235,
+    0x2ad7_d2bb,
+    0xeb86_d391,
+];
+
+// The state before any bytes have been hashed.
+const INITIAL_STATE: [u32; 4] = [0x6745_2301, 0xefcd_ab89, 0x98ba_dcfe, 0x1032_5476];
+
+/// The 128-bit result of hashing some bytes with MD5.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Digest(pub [u8; 16]);
+
+impl Digest {
+    /// Returns the digest as a number, where the first byte is the most significant.
+    #[must_use]
+    pub fn to_u128(self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
+    /// Returns one of the 32 hexadecimal digits of the digest, as it would be written out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is 32 or more.
+    #[must_use]
+    pub fn nibble(self, index: usize) -> u8 {
+        let byte = self.0[index / 2];
+        if index.is_multiple_of(2) {
+            byte >> 4
+        } else {
+            byte & 0xf
+        }
+    }
+
+    /// Returns the number of hexadecimal zeroes the digest starts with.
+    #[must_use]
+    pub fn leading_zero_nibbles(self) -> u32 {
+        self.to_u128().leading_zeros() / 4
+    }
+
+    /// Returns the digest written out as lowercase hexadecimal digits. This doesn't allocate, so
+    /// it's suitable for hashing a digest again.
+    #[must_use]
+    pub fn to_hex_bytes(self) -> [u8; 32] {
+        const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut hex = [0; 32];
+        for (index, digit) in hex.iter_mut().enumerate() {
+            *digit = HEX_DIGITS[usize::from(self.nibble(index))];
+        }
+        hex
+    }
+}
+
+impl Display for Digest {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let hex = self.to_hex_bytes();
+        f.write_str(str::from_utf8(&hex).expect("Hexadecimal digits should be valid UTF-8"))
+    }
+}
+
+/// Hashes some bytes with MD5.
+#[must_use]
+pub fn md5(bytes: &[u8]) -> Digest {
+    let mut hasher = Md5::new();
+    hasher.update(bytes);
+    hasher.finalize()
+}
+
+/// An MD5 hasher that bytes can be fed into a piece at a time. Cloning a hasher copies its state,
+/// so hashing many messages that start the same way only needs the common start hashed once.
+#[derive(Clone, Debug)]
+pub struct Md5 {
+    state: [u32; 4],
+    // Bytes that don't yet make up a whole 64-byte block.
+    buffer: [u8; 64],
+    buffer_len: usize,
+    // The total number of bytes hashed.
+    length: u64,
+}
+
+impl Md5 {
+    /// Creates a hasher that hasn't hashed anything yet.
+    #[must_use]
+    pub fn new() -> Md5 {
+        Md5 {
+            state: INITIAL_STATE,
+            buffer: [0; 64],
+            buffer_len: 0,
+            length: 0,
+        }
+    }
+
+    /// Adds bytes to the end of the message being hashed.
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len() as u64);
+
+        // Top up a partly filled block first.
+        if self.buffer_len > 0 {
+            let taken = bytes.len().min(64 - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + taken].copy_from_slice(&bytes[..taken]);
+            self.buffer_len += taken;
+            bytes = &bytes[taken..];
+            if self.buffer_len < 64 {
+                return;
+            }
+            compress(&mut self.state, &self.buffer);
+            self.buffer_len = 0;
+        }
+
+        let (blocks, remainder) = bytes.as_chunks::<64>();
+        for block in blocks {
+            compress(&mut self.state, block);
+        }
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+        self.buffer_len = remainder.len();
+    }
+
+    /// Finishes hashing the message and returns its digest.
+    #[must_use]
+    pub fn finalize(mut self) -> Digest {
+        // The message is padded with a 1 bit and then 0 bits until there's exactly room for the
+        // message length in bits at the end of the last block, which may need an extra block.
+        let bit_length = self.length.wrapping_mul(8);
+        self.buffer[self.buffer_len] = 0x80;
+        self.buffer[self.buffer_len + 1..].fill(0);
+        if self.buffer_len >= 56 {
+            compress(&mut self.state, &self.buffer);
+            self.buffer.fill(0);
+        }
+        self.buffer[56..].copy_from_slice(&bit_length.to_le_bytes());
+        compress(&mut self.state, &self.buffer);
+
+        let mut digest = [0; 16];
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        Digest(digest)
+    }
+}
+
+impl Default for Md5 {
+    fn default() -> Md5 {
+        Md5::new()
+    }
+}
+
+// Mixes a 64-byte block into the state. Single-character names are used to follow the names of
+// each variable in the definition of the MD5 algorithm.
+#[allow(clippy::many_single_char_names)]
+fn compress(state: &mut [u32; 4], block: &[u8; 64]) {
+    let mut words = [0; 16];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().expect("Each chunk should have 4 bytes"));
+    }
+
+    let [mut a, mut b, mut c, mut d] = *state;
+    for i in 0..64 {
+        let (f, g) = match i {
+            0..16 => ((b & c) | (!b & d), i),
+            16..32 => ((b & d) | (c & !d), (5 * i + 1) % 16),
+            32..48 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let f = f.wrapping_add(a).wrapping_add(K[i]).wrapping_add(words[g]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(f.rotate_left(S[i]));
+    }
+
+    for (word, mixed) in state.iter_mut().zip([a, b, c, d]) {
+        *word = word.wrapping_add(mixed);
+    }
+}
+
+/// Hashes messages made of a common prefix followed by a decimal counter, such as `abc0`, `abc1`,
+/// `abc2` and so on. The prefix is only hashed once, and the counters are written out without
+/// allocating.
+#[derive(Clone, Debug)]
+pub struct PrefixHasher {
+    prefix: Md5,
+}
+
+impl PrefixHasher {
+    /// Creates a hasher for messages starting with the prefix.
+    #[must_use]
+    pub fn new(prefix: &[u8]) -> PrefixHasher {
+        let mut hasher = Md5::new();
+        hasher.update(prefix);
+        PrefixHasher { prefix: hasher }
+    }
+
+    /// Returns the digest of the prefix followed by the counter.
+    #[must_use]
+    pub fn hash(&self, counter: u64) -> Digest {
+        // u64::MAX has 20 digits, which are written from the end of the buffer backwards.
+        let mut digits = [0; 20];
+        let mut start = digits.len();
+        let mut remaining = counter;
+        loop {
+            start -= 1;
+            // The remainder is a single digit, so it always fits in a u8.
+            #[allow(clippy::cast_possible_truncation)]
+            let digit = (remaining % 10) as u8;
+            digits[start] = b'0' + digit;
+            remaining /= 10;
+            if remaining == 0 {
+                break;
+            }
+        }
+
+        let mut hasher = self.prefix.clone();
+        hasher.update(&digits[start..]);
+        hasher.finalize()
+    }
+
+    /// Hashes every counter in a range in parallel, and returns the counters and digests that
+    /// satisfy a predicate, in increasing order of counter.
+    pub fn filter_range(
+        &self,
+        counters: Range<u64>,
+        predicate: impl Fn(Digest) -> bool + Sync,
+    ) -> Vec<(u64, Digest)> {
+        counters
+            .into_par_iter()
+            .filter_map(|counter| {
+                let digest = self.hash(counter);
+                predicate(digest).then_some((counter, digest))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vectors() {
+        // The test suite from RFC 1321.
+        for (message, expected) in [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "c3fcd3d76192e4007dfb496cca67e13b",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "d174ab98d277d9f5a5611c2c9f419d9f",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ] {
+            assert_eq!(md5(message.as_bytes()).to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn streaming() {
+        let message = (0..200)
+            .map(|byte: u8| byte.wrapping_mul(7))
+            .collect::<Vec<_>>();
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 200] {
+            let mut hasher = Md5::new();
+            hasher.update(&message[..split]);
+            hasher.update(&message[split..]);
+            assert_eq!(hasher.finalize(), md5(&message));
+        }
+
+        let digest = md5(b"abc");
+        assert_eq!(digest.nibble(0), 9);
+        assert_eq!(digest.nibble(3), 1);
+        assert_eq!(md5(b"abcdef609043").leading_zero_nibbles(), 5);
+    }
+
+    #[test]
+    fn prefix_hashing() {
+        let hasher = PrefixHasher::new(b"abc");
+        assert_eq!(hasher.hash(0), md5(b"abc0"));
+        assert_eq!(hasher.hash(3_231_929), md5(b"abc3231929"));
+        assert_eq!(hasher.hash(u64::MAX), md5(b"abc18446744073709551615"));
+        assert_eq!(
+            hasher
+                .filter_range(0..1000, |digest| digest.0[0] == 0)
+                .into_iter()
+                .map(|(counter, _)| counter)
+                .collect::<Vec<_>>(),
+            (0..1000)
+                .filter(|counter| md5(format!("abc{counter}").as_bytes()).0[0] == 0)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use super::geometry::{Direction, Direction8};
+use std::ops::{Index, IndexMut};
+
+// The storage grows to a multiple of this many cells along each axis, so that walking off the edge
+// one cell at a time doesn't reallocate on every step.
+const CHUNK_SIZE: usize = 16;
+
+/// A 2D grid that extends infinitely in every direction. Cells are addressed by `(x, y)` positions,
+/// which may be negative, with the same orientation as [`Grid`](super::grid::Grid). Every cell
+/// starts with a default value, and only the area around cells that have been written to is
+/// actually stored, growing as needed when cells further away are written to.
+#[derive(Clone, Debug)]
+pub struct InfiniteGrid<T> {
+    default: T,
+    // The position of the top-left stored cell.
+    origin: (isize, isize),
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    // The smallest and largest corners of the rectangle containing every cell written to so far.
+    bounds: Option<((isize, isize), (isize, isize))>,
+}
+
+impl<T: Clone> InfiniteGrid<T> {
+    /// Creates a grid where every cell is the given default value.
+    #[must_use]
+    pub fn new(default: T) -> InfiniteGrid<T> {
+        InfiniteGrid {
+            default,
+            origin: (0, 0),
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+            bounds: None,
+        }
+    }
+
+    /// Creates a grid from text where each line is a row and each character is a cell, converting
+    /// each character with the provided function. The first character of the first line is placed
+    /// at the given position, and every cell outside the text is the default value.
+    pub fn parse(
+        input: &str,
+        default: T,
+        (x_start, y_start): (isize, isize),
+        mut parse_cell: impl FnMut(char) -> T,
+    ) -> InfiniteGrid<T> {
+        let mut grid = InfiniteGrid::new(default);
+        for (y, line) in (y_start..).zip(input.lines()) {
+            for (x, character) in (x_start..).zip(line.chars()) {
+                grid[(x, y)] = parse_cell(character);
+            }
+        }
+        grid
+    }
+
+    /// Returns the value of the cell at a position.
+    #[must_use]
+    pub fn get(&self, position: (isize, isize)) -> &T {
+        self.index_of(position)
+            .map_or(&self.default, |index| &self.cells[index])
+    }
+
+    /// Returns a mutable reference to the cell at a position, growing the grid if the position
+    /// isn't stored yet.
+    pub fn get_mut(&mut self, (x, y): (isize, isize)) -> &mut T {
+        self.bounds = Some(match self.bounds {
+            Some(((x_min, y_min), (x_max, y_max))) => {
+                ((x_min.min(x), y_min.min(y)), (x_max.max(x), y_max.max(y)))
+            }
+            None => ((x, y), (x, y)),
+        });
+
+        if self.index_of((x, y)).is_none() {
+            self.grow_to_include((x, y));
+        }
+        // The position is now stored, so its offset from the origin is nonnegative.
+        let index =
+            (y - self.origin.1).cast_unsigned() * self.width + (x - self.origin.0).cast_unsigned();
+        &mut self.cells[index]
+    }
+
+    /// Returns the smallest and largest corners of the rectangle containing every cell written to
+    /// so far, or None if nothing has been written to.
+    #[must_use]
+    pub fn bounding_box(&self) -> Option<((isize, isize), (isize, isize))> {
+        self.bounds
+    }
+
+    /// Iterates over every position inside the bounding box and the value of its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((isize, isize), &T)> {
+        let ((x_min, y_min), (x_max, y_max)) = self.bounds.unwrap_or(((0, 0), (-1, -1)));
+        (y_min..=y_max)
+            .flat_map(move |y| (x_min..=x_max).map(move |x| (x, y)))
+            .map(|position| (position, self.get(position)))
+    }
+
+    /// Moves from a position by an offset. Unlike [`Grid::offset`](super::grid::Grid::offset),
+    /// this always succeeds, since every position is inside an infinite grid.
+    #[must_use]
+    pub fn offset(&self, (x, y): (isize, isize), (dx, dy): (isize, isize)) -> (isize, isize) {
+        (x + dx, y + dy)
+    }
+
+    /// Iterates over the positions of the four cells orthogonally adjacent to a position, in the
+    /// order up, right, down, left.
+    pub fn neighbours_4(&self, position: (isize, isize)) -> impl Iterator<Item = (isize, isize)> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| self.offset(position, direction.offset()))
+    }
+
+    /// Iterates over the positions of the eight cells adjacent to a position, including diagonally
+    /// adjacent cells, in clockwise order starting from up.
+    pub fn neighbours_8(&self, position: (isize, isize)) -> impl Iterator<Item = (isize, isize)> {
+        Direction8::ALL
+            .into_iter()
+            .map(move |direction| self.offset(position, direction.offset()))
+    }
+
+    /// Renders the cells inside the bounding box as text with one line per row, converting each
+    /// cell to a character with the provided function.
+    pub fn render(&self, mut render_cell: impl FnMut(&T) -> char) -> String {
+        let Some(((x_min, y_min), (x_max, y_max))) = self.bounds else {
+            return String::new();
+        };
+        let mut rendered = String::new();
+        for y in y_min..=y_max {
+            rendered.extend((x_min..=x_max).map(|x| render_cell(self.get((x, y)))));
+            rendered.push('\n');
+        }
+        rendered.pop();
+        rendered
+    }
+
+    // Reallocates the stored cells so they include the position, keeping every existing cell.
+    fn grow_to_include(&mut self, (x, y): (isize, isize)) {
+        let (x_start, width) = grow_axis(self.origin.0, self.width, x);
+        let (y_start, height) = grow_axis(self.origin.1, self.height, y);
+
+        let mut cells = vec![self.default.clone(); width * height];
+        let x_shift = (self.origin.0 - x_start).cast_unsigned();
+        let y_shift = (self.origin.1 - y_start).cast_unsigned();
+        for (old_y, row) in self.cells.chunks(self.width.max(1)).enumerate() {
+            let new_row_start = (old_y + y_shift) * width + x_shift;
+            cells[new_row_start..new_row_start + row.len()].clone_from_slice(row);
+        }
+
+        self.origin = (x_start, y_start);
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+    }
+
+    fn index_of(&self, (x, y): (isize, isize)) -> Option<usize> {
+        let x = x.checked_sub(self.origin.0)?;
+        let y = y.checked_sub(self.origin.1)?;
+        let (x, y) = (usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl<T: Clone> Index<(isize, isize)> for InfiniteGrid<T> {
+    type Output = T;
+
+    fn index(&self, position: (isize, isize)) -> &T {
+        self.get(position)
+    }
+}
+
+impl<T: Clone> IndexMut<(isize, isize)> for InfiniteGrid<T> {
+    fn index_mut(&mut self, position: (isize, isize)) -> &mut T {
+        self.get_mut(position)
+    }
+}
+
+// Returns the new start and length of one axis of the stored cells, so that it includes the target
+// coordinate. The axis grows by at least its current length, so that repeatedly growing in the same
+// direction takes amortized constant time, and both ends are aligned to a multiple of the chunk
+// size.
+fn grow_axis(start: isize, length: usize, target: isize) -> (isize, usize) {
+    let chunk_size = CHUNK_SIZE.cast_signed();
+    let growth = length.max(CHUNK_SIZE).cast_signed();
+    let end = start + length.cast_signed();
+
+    let (new_start, new_end) = if length == 0 {
+        (target, target + 1)
+    } else if target < start {
+        (target.min(start - growth), end)
+    } else if target >= end {
+        (start, (target + 1).max(end + growth))
+    } else {
+        (start, end)
+    };
+
+    let new_start = new_start.div_euclid(chunk_size) * chunk_size;
+    let new_end = (new_end + chunk_size - 1).div_euclid(chunk_size) * chunk_size;
+    (new_start, (new_end - new_start).cast_unsigned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_and_growth() {
+        let mut grid = InfiniteGrid::new(0);
+        assert_eq!(grid[(-100, 50)], 0);
+        assert_eq!(grid.bounding_box(), None);
+
+        grid[(3, 4)] = 1;
+        grid[(-40, -2)] = 2;
+        grid[(100, 70)] = 3;
+        assert_eq!(grid[(3, 4)], 1);
+        assert_eq!(grid[(-40, -2)], 2);
+        assert_eq!(grid[(100, 70)], 3);
+        assert_eq!(grid[(0, 0)], 0);
+        assert_eq!(grid.bounding_box(), Some(((-40, -2), (100, 70))));
+        assert_eq!(grid.iter().filter(|(_, value)| **value != 0).count(), 3);
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = InfiniteGrid::parse("#.\n.#", false, (-1, -1), |character| character == '#');
+        assert!(grid[(-1, -1)]);
+        assert!(grid[(0, 0)]);
+        assert!(!grid[(1, 1)]);
+        assert_eq!(grid.render(|cell| if *cell { '#' } else { '.' }), "#.\n.#");
+        assert_eq!(
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, -1), (1, 0), (0, 1), (-1, 0)]
+        );
+    }
+}
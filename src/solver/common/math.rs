@@ -0,0 +1,268 @@
+/// Returns the greatest common divisor of two numbers. The greatest common divisor of 0 and 0 is 0.
+#[must_use]
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the lowest common multiple of two numbers. The lowest common multiple of 0 and any
+/// number is 0.
+#[must_use]
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    // Dividing before multiplying avoids overflowing when the result itself fits.
+    a / gcd(a, b) * b
+}
+
+/// Returns `(g, x, y)`, where `g` is the greatest common divisor of `a` and `b`, and `x` and `y`
+/// satisfy `a * x + b * y = g`. The greatest common divisor is never negative.
+#[must_use]
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_remainder, mut remainder) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while remainder != 0 {
+        let quotient = old_remainder / remainder;
+        (old_remainder, remainder) = (remainder, old_remainder - quotient * remainder);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_remainder < 0 {
+        (-old_remainder, -old_x, -old_y)
+    } else {
+        (old_remainder, old_x, old_y)
+    }
+}
+
+/// Returns `(a * b) % modulus`, without overflowing for any inputs.
+///
+/// # Panics
+///
+/// Panics if the modulus is 0.
+#[must_use]
+pub fn mod_mul(a: u64, b: u64, modulus: u64) -> u64 {
+    // The product is reduced below the modulus, so truncating it back to a u64 loses nothing.
+    #[allow(clippy::cast_possible_truncation)]
+    let product = (u128::from(a) * u128::from(b) % u128::from(modulus)) as u64;
+    product
+}
+
+/// Returns `base.pow(exponent) % modulus`, calculated by repeated squaring so that it takes time
+/// proportional to the number of bits in the exponent.
+///
+/// # Panics
+///
+/// Panics if the modulus is 0.
+#[must_use]
+pub fn mod_pow(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    base %= modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mod_mul(result, base, modulus);
+        }
+        base = mod_mul(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Returns the number `x` in `0..modulus` such that `(a * x) % modulus == 1`, or None if there is
+/// no such number, which happens when `a` and the modulus share a factor.
+#[must_use]
+pub fn mod_inverse(a: u64, modulus: u64) -> Option<u64> {
+    let (g, x, _) = extended_gcd(i128::from(a), i128::from(modulus));
+    if g != 1 {
+        return None;
+    }
+    u64::try_from(x.rem_euclid(i128::from(modulus))).ok()
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` using the Chinese Remainder
+/// Theorem. The moduli don't have to be pairwise coprime. Returns `(x, m)`, where `x` is the
+/// smallest nonnegative solution and every solution is `x` plus a multiple of `m`, the lowest
+/// common multiple of the moduli. Returns None if the congruences contradict each other, or if the
+/// lowest common multiple of the moduli doesn't fit in a u64.
+///
+/// # Panics
+///
+/// Panics if any modulus is 0.
+#[must_use]
+pub fn chinese_remainder(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut solution = 0;
+    let mut combined_modulus = 1;
+
+    for (residue, modulus) in congruences {
+        let residue = residue % modulus;
+        let solution_residue = solution % modulus;
+        let difference = if residue >= solution_residue {
+            residue - solution_residue
+        } else {
+            modulus - solution_residue + residue
+        };
+
+        // Find k such that solution + combined_modulus * k ≡ residue (mod modulus). Dividing
+        // through by the greatest common divisor g of the moduli gives
+        // (combined_modulus / g) * k ≡ difference / g (mod modulus / g), which can be solved with a
+        // modular inverse as the moduli are now coprime. There is no solution if g doesn't divide
+        // the difference.
+        let g = gcd(combined_modulus, *modulus);
+        if !difference.is_multiple_of(g) {
+            return None;
+        }
+        let reduced_modulus = modulus / g;
+        let inverse = mod_inverse((combined_modulus / g) % reduced_modulus, reduced_modulus)?;
+        let k = mod_mul(difference / g, inverse, reduced_modulus);
+
+        // The solution is below the combined modulus and k is below the reduced modulus, so the new
+        // solution is below their product and can't overflow if the product doesn't.
+        let next_combined_modulus = combined_modulus.checked_mul(reduced_modulus)?;
+        solution += combined_modulus * k;
+        combined_modulus = next_combined_modulus;
+    }
+
+    Some((solution, combined_modulus))
+}
+
+/// Returns true if a number is prime, checking for factors by trial division.
+#[must_use]
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    // Any factor larger than the square root must be paired with one smaller than it, so only
+    // factors up to the square root need to be checked.
+    (2..=n.isqrt()).all(|factor| !n.is_multiple_of(factor))
+}
+
+/// Uses the Sieve of Eratosthenes to find which numbers below a limit are prime. The returned
+/// vector has one entry for each number from 0 to one less than the limit, which is true if that
+/// number is prime.
+#[must_use]
+pub fn prime_sieve(limit: usize) -> Vec<bool> {
+    let mut is_prime = vec![true; limit];
+    for n in is_prime.iter_mut().take(2) {
+        *n = false;
+    }
+
+    let mut factor = 2;
+    while factor * factor < limit {
+        if is_prime[factor] {
+            // Smaller multiples of this factor have already been crossed off by smaller factors.
+            for multiple in (factor * factor..limit).step_by(factor) {
+                is_prime[multiple] = false;
+            }
+        }
+        factor += 1;
+    }
+    is_prime
+}
+
+/// Returns every prime number below a limit, in increasing order.
+#[must_use]
+pub fn primes(limit: usize) -> Vec<usize> {
+    prime_sieve(limit)
+        .into_iter()
+        .enumerate()
+        .filter_map(|(n, is_prime)| is_prime.then_some(n))
+        .collect()
+}
+
+/// Returns the sum of all divisors of a number, including 1 and the number itself, by factorising
+/// it. The sum can be larger than the number, so it's returned as a `u128`. The sum of the divisors
+/// of 0 is 0.
+#[must_use]
+pub fn divisor_sum(mut n: u64) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+
+    // The divisor sum is multiplicative, and the divisors of p^k sum to 1 + p + ... + p^k. Dividing
+    // instead of squaring the factor keeps the loop condition from overflowing.
+    let mut sum = 1;
+    let mut factor = 2;
+    while factor <= n / factor {
+        let mut prime_power_sum = 1;
+        let mut prime_power = 1;
+        while n.is_multiple_of(factor) {
+            n /= factor;
+            prime_power *= u128::from(factor);
+            prime_power_sum += prime_power;
+        }
+        sum *= prime_power_sum;
+        factor += 1;
+    }
+    // Anything left over is a single prime factor larger than the square root.
+    if n > 1 {
+        sum *= u128::from(n) + 1;
+    }
+    sum
+}
+
+/// Returns the sum of the divisors of every number below a limit, as a vector indexed by number.
+/// This sieves over the multiples of every divisor, which is much faster than factorising each
+/// number separately.
+#[must_use]
+pub fn divisor_sums(limit: usize) -> Vec<usize> {
+    divisor_sums_bounded(limit, usize::MAX)
+}
+
+/// Like [`divisor_sums`], but each divisor is only counted for its first `max_multiples` multiples.
+/// That is, the divisor `d` is counted towards `n` only if `n / d <= max_multiples`.
+#[must_use]
+pub fn divisor_sums_bounded(limit: usize, max_multiples: usize) -> Vec<usize> {
+    let mut sums = vec![0; limit];
+    for divisor in 1..limit {
+        for multiple in (divisor..limit).step_by(divisor).take(max_multiples) {
+            sums[multiple] += divisor;
+        }
+    }
+    sums
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn chinese_remainder_theorem() {
+        assert_eq!(
+            chinese_remainder(&[(2, 3), (3, 5), (2, 7)]),
+            Some((23, 105))
+        );
+        // Moduli that share a factor.
+        assert_eq!(chinese_remainder(&[(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(chinese_remainder(&[(1, 4), (2, 6)]), None);
+        assert_eq!(chinese_remainder(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn primes_and_divisors() {
+        assert_eq!(primes(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(is_prime(7919));
+        assert!(!is_prime(7917));
+        assert_eq!(divisor_sum(12), 28);
+        assert_eq!(divisor_sum(97), 98);
+        // u64::MAX is 3 * 5 * 17 * 257 * 641 * 65537 * 6700417, and its divisor sum doesn't fit in
+        // a u64.
+        assert_eq!(divisor_sum(u64::MAX), 31_421_980_989_189_888_768);
+        assert_eq!(divisor_sums(10), vec![0, 1, 3, 4, 7, 6, 12, 8, 15, 13]);
+        assert_eq!(divisor_sums_bounded(10, 2)[8], 12);
+    }
+}
@@ -0,0 +1,311 @@
+use super::grid::Grid;
+use std::fmt::{self, Display, Formatter};
+
+// A block-letter font, where every letter sits at the left of a fixed-width cell. Glyph rows are
+// only as wide as the letter, and any columns to their right are assumed to be unlit.
+struct Font {
+    height: usize,
+    cell_width: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+// The 4x6 font used by most puzzles that draw letters, with one blank column between letters. Y is
+// the only letter that uses the blank column.
+const SMALL_FONT: Font = Font {
+    height: 6,
+    cell_width: 5,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+// The 6x10 font used by puzzles with larger messages, with two blank columns between letters.
+const LARGE_FONT: Font = Font {
+    height: 10,
+    cell_width: 8,
+    glyphs: &[
+        (
+            'A',
+            &[
+                "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'B',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+                "#....#", "#####.",
+            ],
+        ),
+        (
+            'C',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#....#", ".####.",
+            ],
+        ),
+        (
+            'E',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'F',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'G',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+                "#...##", ".###.#",
+            ],
+        ),
+        (
+            'H',
+            &[
+                "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'J',
+            &[
+                "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+                "#...#.", ".###..",
+            ],
+        ),
+        (
+            'K',
+            &[
+                "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+                "#...#.", "#....#",
+            ],
+        ),
+        (
+            'L',
+            &[
+                "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'N',
+            &[
+                "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+                "#...##", "#....#",
+            ],
+        ),
+        (
+            'P',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'R',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'X',
+            &[
+                "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'Z',
+            &[
+                "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+    ],
+};
+
+/// The error returned when a grid can't be read as text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// The grid isn't as tall as any known font.
+    UnsupportedHeight(usize),
+    /// A cell doesn't match any letter in the font. The glyph is drawn with `#` for lit pixels and
+    /// `.` for unlit pixels, one line per row.
+    UnknownGlyph { position: usize, glyph: String },
+}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            OcrError::UnsupportedHeight(height) => write!(
+                f,
+                "Can't read letters {height} pixels tall, only 6 or 10 pixels tall"
+            ),
+            OcrError::UnknownGlyph { position, glyph } => {
+                write!(f, "Unknown glyph at position {position}:\n{glyph}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+/// Reads the block letters drawn in a grid, where `true` cells are lit. The font is chosen from
+/// the height of the grid: 6 rows for 4x6 letters, or 10 rows for 6x10 letters. The first letter
+/// must start in the leftmost column. Blank cells are read as spaces, and trailing spaces are
+/// removed.
+///
+/// # Errors
+///
+/// Returns an [`OcrError`] if the grid's height doesn't match a font, or if any cell doesn't
+/// match a letter in the font.
+pub fn recognise(grid: &Grid<bool>) -> Result<String, OcrError> {
+    let font = [SMALL_FONT, LARGE_FONT]
+        .into_iter()
+        .find(|font| font.height == grid.height())
+        .ok_or(OcrError::UnsupportedHeight(grid.height()))?;
+
+    let mut text = String::new();
+    for (position, left) in (0..grid.width()).step_by(font.cell_width).enumerate() {
+        let right = (left + font.cell_width).min(grid.width());
+        let rows = grid
+            .rows()
+            .map(|row| {
+                row[left..right]
+                    .iter()
+                    .map(|&lit| if lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        if rows.iter().all(|row| !row.contains('#')) {
+            text.push(' ');
+            continue;
+        }
+
+        let letter = font
+            .glyphs
+            .iter()
+            .find(|(_, glyph)| {
+                glyph.iter().zip(&rows).all(|(glyph_row, row)| {
+                    glyph_row.trim_end_matches('.') == row.trim_end_matches('.')
+                })
+            })
+            .map(|(letter, _)| *letter)
+            .ok_or_else(|| OcrError::UnknownGlyph {
+                position,
+                glyph: rows.join("\n"),
+            })?;
+        text.push(letter);
+    }
+
+    text.truncate(text.trim_end().len());
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_grid(drawing: &str) -> Grid<bool> {
+        Grid::parse(drawing, |cell| cell == '#')
+    }
+
+    // Draws every letter in a font side by side.
+    fn draw_font(font: &Font) -> Grid<bool> {
+        let mut cells = Vec::new();
+        for y in 0..font.height {
+            for (_, glyph) in font.glyphs {
+                let row = glyph[y].chars().map(|cell| cell == '#');
+                cells.extend(row.chain(std::iter::repeat(false)).take(font.cell_width));
+            }
+        }
+        Grid::from_cells(font.cell_width * font.glyphs.len(), cells)
+    }
+
+    fn font_letters(font: &Font) -> String {
+        font.glyphs.iter().map(|(letter, _)| *letter).collect()
+    }
+
+    #[test]
+    fn small_letters() {
+        let grid = parse_grid(
+            "\
+#..#.####.#....#.....##..#...#
+#..#.#....#....#....#..#.#...#
+####.###..#....#....#..#..#.#.
+#..#.#....#....#....#..#...#..
+#..#.#....#....#....#..#...#..
+#..#.####.####.####..##....#..",
+        );
+        assert_eq!(recognise(&grid), Ok("HELLOY".to_string()));
+
+        assert_eq!(
+            recognise(&draw_font(&SMALL_FONT)),
+            Ok(font_letters(&SMALL_FONT))
+        );
+    }
+
+    #[test]
+    fn large_letters() {
+        assert_eq!(
+            recognise(&draw_font(&LARGE_FONT)),
+            Ok(font_letters(&LARGE_FONT))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let grid = parse_grid(
+            "\
+.##..#..#.
+#..#.#..#.
+#..#.#..#.
+####.#..#.
+#..#.#.##.
+#..#..#.#.",
+        );
+        let error = recognise(&grid).expect_err("Second glyph isn't a letter");
+        assert_eq!(
+            error,
+            OcrError::UnknownGlyph {
+                position: 1,
+                glyph: "#..#.\n#..#.\n#..#.\n#..#.\n#.##.\n.#.#.".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Unknown glyph at position 1:\n#..#.\n#..#.\n#..#.\n#..#.\n#.##.\n.#.#."
+        );
+
+        assert_eq!(
+            recognise(&parse_grid("#\n#\n#")),
+            Err(OcrError::UnsupportedHeight(3))
+        );
+    }
+}
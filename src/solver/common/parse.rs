@@ -0,0 +1,315 @@
+use atoi::{FromRadix10Checked, FromRadix10SignedChecked};
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+use std::str::FromStr;
+
+/// An error from parsing puzzle input, pointing at the position in the input where it occurred.
+/// Lines and columns are both numbered from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Iterates over every unsigned integer in some text, in order, skipping any characters that
+/// aren't digits. A `-` before a number is ignored, so `"3-5"` gives 3 and 5.
+///
+/// # Panics
+///
+/// The iterator panics if a number is too large to fit in the integer type, giving the line and
+/// column of the number.
+pub fn unsigned_integers<T: FromRadix10Checked>(text: &str) -> impl Iterator<Item = T> {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+    iter::from_fn(move || {
+        index += bytes[index..]
+            .iter()
+            .position(u8::is_ascii_digit)
+            .unwrap_or(bytes.len() - index);
+        if index == bytes.len() {
+            return None;
+        }
+
+        let (number, length) = T::from_radix_10_checked(&bytes[index..]);
+        let number = number.unwrap_or_else(|| panic!("{}", too_large_error(text, index)));
+        index += length;
+        Some(number)
+    })
+}
+
+/// Iterates over every signed integer in some text, in order, skipping any characters that aren't
+/// digits. A `-` immediately before a number makes it negative, so `"3-5"` gives 3 and -5.
+///
+/// # Panics
+///
+/// The iterator panics if a number is too large to fit in the integer type, giving the line and
+/// column of the number.
+pub fn signed_integers<T: FromRadix10SignedChecked>(text: &str) -> impl Iterator<Item = T> {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+    iter::from_fn(move || {
+        index += bytes[index..]
+            .iter()
+            .position(u8::is_ascii_digit)
+            .unwrap_or(bytes.len() - index);
+        if index == bytes.len() {
+            return None;
+        }
+
+        // Start the number from its sign if it has one.
+        let start = if index > 0 && bytes[index - 1] == b'-' {
+            index - 1
+        } else {
+            index
+        };
+        let (number, length) = T::from_radix_10_signed_checked(&bytes[start..]);
+        let number = number.unwrap_or_else(|| panic!("{}", too_large_error(text, start)));
+        index = start + length;
+        Some(number)
+    })
+}
+
+// Creates the error for a number that doesn't fit in its type, converting the byte index where the
+// number starts into a line and a column counted in characters.
+fn too_large_error(text: &str, index: usize) -> ParseError {
+    let before = &text[..index];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    ParseError {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+        message: "the number is too large for its type".to_string(),
+    }
+}
+
+/// Iterates over the blocks of lines in some text that are separated by blank lines. Blocks made up
+/// entirely of newlines, such as from extra blank lines at the end of the text, are skipped.
+pub fn blocks(text: &str) -> impl Iterator<Item = &str> {
+    text.split("\n\n")
+        .map(|block| block.trim_matches('\n'))
+        .filter(|block| !block.is_empty())
+}
+
+/// A fixed layout for a line of text, made of literal text with `{}` placeholders for fields. Each
+/// field matches everything up to the next occurrence of the literal text that follows it, or the
+/// rest of the line if it's at the end. For example, the pattern `{} -> {}` splits
+/// `"a AND b -> c"` into the fields `"a AND b"` and `"c"`, and key-value lines such as
+/// `"Hit Points: 51"` can be split with the pattern `{}: {}`.
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    // The literal text before the first placeholder, between each pair of placeholders, and after
+    // the last placeholder.
+    literals: Vec<String>,
+}
+
+impl Pattern {
+    /// Creates a pattern from its text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if two placeholders are next to each other, as there would be no way to tell where
+    /// one field ends and the next begins.
+    #[must_use]
+    pub fn new(pattern: &str) -> Pattern {
+        let literals = pattern.split("{}").map(str::to_string).collect::<Vec<_>>();
+        assert!(
+            literals.len() < 3
+                || literals[1..literals.len() - 1]
+                    .iter()
+                    .all(|literal| !literal.is_empty()),
+            "Pattern placeholders should be separated by literal text"
+        );
+        Pattern { literals }
+    }
+
+    /// Matches a single line against the pattern, returning its fields.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the line doesn't match the pattern.
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Captures<'a>, ParseError> {
+        self.captures_line(line, 1)
+    }
+
+    /// Matches every line of some text against the pattern, returning the fields of each line.
+    /// Errors point at the line within the text where the match failed.
+    pub fn captures_lines<'a>(
+        &self,
+        text: &'a str,
+    ) -> impl Iterator<Item = Result<Captures<'a>, ParseError>> {
+        text.lines()
+            .zip(1..)
+            .map(|(line, line_number)| self.captures_line(line, line_number))
+    }
+
+    fn captures_line<'a>(
+        &self,
+        line: &'a str,
+        line_number: usize,
+    ) -> Result<Captures<'a>, ParseError> {
+        let error = |rest: &str, message: String| ParseError {
+            line: line_number,
+            column: column_of(line, rest),
+            message,
+        };
+
+        let Some(mut rest) = line.strip_prefix(self.literals[0].as_str()) else {
+            return Err(error(line, format!("expected \"{}\"", self.literals[0])));
+        };
+        let mut fields = Vec::with_capacity(self.literals.len() - 1);
+        for literal in &self.literals[1..] {
+            let field_end = if literal.is_empty() {
+                rest.len()
+            } else {
+                rest.find(literal.as_str()).ok_or_else(|| {
+                    error(rest, format!("expected a field followed by \"{literal}\""))
+                })?
+            };
+            fields.push((column_of(line, rest), &rest[..field_end]));
+            rest = &rest[field_end + literal.len()..];
+        }
+
+        if !rest.is_empty() {
+            return Err(error(rest, format!("unexpected text \"{rest}\"")));
+        }
+        Ok(Captures {
+            line: line_number,
+            fields,
+        })
+    }
+}
+
+/// The fields from a line that matched a [`Pattern`].
+#[derive(Clone, Debug)]
+pub struct Captures<'a> {
+    line: usize,
+    // The column each field starts at and its text.
+    fields: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Captures<'a> {
+    /// Returns the number of fields.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns true if there are no fields.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns the text of a field, where fields are numbered from 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern doesn't have that many fields.
+    #[must_use]
+    pub fn get(&self, index: usize) -> &'a str {
+        self.fields[index].1
+    }
+
+    /// Parses a field into another type, where fields are numbered from 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error pointing at the field if it couldn't be parsed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern doesn't have that many fields.
+    pub fn parse<T: FromStr>(&self, index: usize) -> Result<T, ParseError>
+    where
+        T::Err: Display,
+    {
+        let (column, field) = self.fields[index];
+        field.parse().map_err(|error| ParseError {
+            line: self.line,
+            column,
+            message: format!("couldn't parse \"{field}\": {error}"),
+        })
+    }
+}
+
+// Returns the column that a suffix of a line starts at.
+fn column_of(line: &str, suffix: &str) -> usize {
+    line[..line.len() - suffix.len()].chars().count() + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(
+            unsigned_integers::<u32>("x=12, y=-3..45").collect::<Vec<_>>(),
+            vec![12, 3, 45]
+        );
+        assert_eq!(
+            signed_integers::<i32>("x=12, y=-3..45").collect::<Vec<_>>(),
+            vec![12, -3, 45]
+        );
+        assert_eq!(signed_integers::<i64>("no numbers").count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2, column 4: the number is too large for its type")]
+    fn unsigned_integer_too_large() {
+        unsigned_integers::<u8>("12\nλ, 300").for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 3, column 3: the number is too large for its type")]
+    fn signed_integer_too_large() {
+        signed_integers::<i8>("1\n-2\nx=-200").for_each(drop);
+    }
+
+    #[test]
+    fn text_blocks() {
+        assert_eq!(
+            blocks("a\nb\n\nc\n\n\nd\n").collect::<Vec<_>>(),
+            vec!["a\nb", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn pattern() {
+        let pattern = Pattern::new("{} bags contain {} {}.");
+        let captures = pattern
+            .captures("light red bags contain 2 muted yellow bags.")
+            .expect("Line should match");
+        assert_eq!(captures.len(), 3);
+        assert_eq!(captures.get(0), "light red");
+        assert_eq!(captures.parse::<u32>(1), Ok(2));
+        assert_eq!(captures.get(2), "muted yellow bags");
+
+        let errors = pattern
+            .captures_lines("a bags contain 1 b.\nc bags hold 2 d.\ne bags contain x f.")
+            .filter_map(Result::err)
+            .collect::<Vec<_>>();
+        assert_eq!((errors[0].line, errors[0].column), (2, 1));
+        let captures = pattern
+            .captures_lines("e bags contain x f.")
+            .next()
+            .and_then(Result::ok)
+            .expect("Line should match");
+        let error = captures
+            .parse::<u32>(1)
+            .expect_err("Field shouldn't be a number");
+        assert_eq!((error.line, error.column), (1, 16));
+    }
+}
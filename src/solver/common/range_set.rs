@@ -0,0 +1,350 @@
+use std::ops::{Add, Range, Sub};
+
+/// A set of values stored as a sorted list of disjoint half-open ranges. Overlapping or touching
+/// ranges are merged as they're inserted, so each range in the set is separated from the next by
+/// at least one value that isn't in the set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    /// Creates an empty set.
+    #[must_use]
+    pub fn new() -> RangeSet<T> {
+        RangeSet { ranges: Vec::new() }
+    }
+
+    /// Returns true if the set contains no values.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the smallest value in the set, or None if the set is empty.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// Iterates over the disjoint ranges making up the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> {
+        self.ranges.iter().cloned()
+    }
+
+    /// Returns true if the set contains a value.
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= value)
+    }
+
+    /// Adds every value in a range to the set, merging it with any ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|other| other.end < range.start);
+        let last = self
+            .ranges
+            .partition_point(|other| other.start <= range.end);
+        let merged = if first < last {
+            range.start.min(self.ranges[first].start)..range.end.max(self.ranges[last - 1].end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Removes every value in a range from the set, splitting any range that it falls inside.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self
+            .ranges
+            .partition_point(|other| other.end <= range.start);
+        let last = self.ranges.partition_point(|other| other.start < range.end);
+        if first == last {
+            return;
+        }
+        // Only the first and last overlapping ranges can stick out past the removed range.
+        let before = self.ranges[first].start..range.start;
+        let after = range.end..self.ranges[last - 1].end;
+        self.ranges.splice(
+            first..last,
+            [before, after]
+                .into_iter()
+                .filter(|piece| !piece.is_empty()),
+        );
+    }
+
+    /// Returns a set containing every value that is in either set.
+    #[must_use]
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    /// Returns a set containing every value that is in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        // Both lists of ranges are sorted, so walk through them together, always stepping past
+        // whichever range ends first since it can't overlap anything further along the other list.
+        let mut ranges = Vec::new();
+        let (mut self_index, mut other_index) = (0, 0);
+        while let (Some(self_range), Some(other_range)) =
+            (self.ranges.get(self_index), other.ranges.get(other_index))
+        {
+            let overlap =
+                self_range.start.max(other_range.start)..self_range.end.min(other_range.end);
+            if !overlap.is_empty() {
+                ranges.push(overlap);
+            }
+            if self_range.end <= other_range.end {
+                self_index += 1;
+            } else {
+                other_index += 1;
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    /// Returns a set containing every value that is in this set but not the other set.
+    #[must_use]
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Default> RangeSet<T> {
+    /// Returns the number of values in the set.
+    #[must_use]
+    pub fn total_length(&self) -> T {
+        self.ranges.iter().fold(T::default(), |total, range| {
+            total + (range.end - range.start)
+        })
+    }
+}
+
+impl<T: Copy + Ord> Default for RangeSet<T> {
+    fn default() -> RangeSet<T> {
+        RangeSet::new()
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> RangeSet<T> {
+        let mut set = RangeSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(ranges: I) -> RangeSet<T> {
+        let mut set = RangeSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// A piecewise mapping of values, made of disjoint source ranges that are each shifted to start at
+/// a destination value. Values outside every source range map to themselves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeMap<T> {
+    // Each source range and the value its start maps to, sorted by the start of the source range.
+    entries: Vec<(Range<T>, T)>,
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> RangeMap<T> {
+    /// Creates a map where every value maps to itself.
+    #[must_use]
+    pub fn new() -> RangeMap<T> {
+        RangeMap {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Maps every value in a source range to the value the same distance from the destination
+    /// start.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the source range overlaps a source range that was already inserted.
+    pub fn insert(&mut self, source: Range<T>, destination_start: T) {
+        if source.is_empty() {
+            return;
+        }
+        let index = self
+            .entries
+            .partition_point(|(other, _)| other.start < source.start);
+        assert!(
+            (index == 0 || self.entries[index - 1].0.end <= source.start)
+                && self
+                    .entries
+                    .get(index)
+                    .is_none_or(|(other, _)| source.end <= other.start),
+            "Source ranges in a range map shouldn't overlap"
+        );
+        self.entries.insert(index, (source, destination_start));
+    }
+
+    /// Returns the value that a value maps to.
+    #[must_use]
+    pub fn get(&self, value: T) -> T {
+        let index = self
+            .entries
+            .partition_point(|(source, _)| source.end <= value);
+        match self.entries.get(index) {
+            Some((source, destination_start)) if source.start <= value => {
+                *destination_start + (value - source.start)
+            }
+            _ => value,
+        }
+    }
+
+    /// Returns the set of values that the values in a set map to.
+    #[must_use]
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.iter()
+            .flat_map(|range| self.pieces(range))
+            .map(|(piece, mapped_start)| mapped_start..mapped_start + (piece.end - piece.start))
+            .collect()
+    }
+
+    /// Returns a map that has the same effect as applying this map and then the next map.
+    #[must_use]
+    pub fn then(&self, next: &RangeMap<T>) -> RangeMap<T> {
+        // Values inside this map's source ranges are shifted by this map, then split up by the
+        // next map wherever their destinations cross its source ranges.
+        let mut entries = Vec::new();
+        for (source, destination_start) in &self.entries {
+            let destination = *destination_start..*destination_start + (source.end - source.start);
+            for (piece, mapped_start) in next.pieces(destination) {
+                let piece_source_start = source.start + (piece.start - *destination_start);
+                let piece_source =
+                    piece_source_start..piece_source_start + (piece.end - piece.start);
+                entries.push((piece_source, mapped_start));
+            }
+        }
+
+        // Values outside this map's source ranges are unchanged by this map, so they're only
+        // affected by the parts of the next map's source ranges that this map doesn't cover.
+        let sources = self
+            .entries
+            .iter()
+            .map(|(source, _)| source.clone())
+            .collect::<RangeSet<_>>();
+        for (source, destination_start) in &next.entries {
+            for piece in RangeSet::from(source.clone()).difference(&sources).iter() {
+                let mapped_start = *destination_start + (piece.start - source.start);
+                entries.push((piece, mapped_start));
+            }
+        }
+
+        entries.sort_unstable_by_key(|(source, _)| source.start);
+        RangeMap { entries }
+    }
+
+    // Splits a range into consecutive pieces that each either lie inside a single source range or
+    // outside every source range, paired with the value the start of each piece maps to.
+    fn pieces(&self, range: Range<T>) -> Vec<(Range<T>, T)> {
+        let mut pieces = Vec::new();
+        let mut index = self
+            .entries
+            .partition_point(|(source, _)| source.end <= range.start);
+        let mut start = range.start;
+
+        while start < range.end {
+            match self.entries.get(index) {
+                Some((source, destination_start)) if source.start <= start => {
+                    let end = source.end.min(range.end);
+                    pieces.push((start..end, *destination_start + (start - source.start)));
+                    start = end;
+                    index += 1;
+                }
+                next_entry => {
+                    let end =
+                        next_entry.map_or(range.end, |(source, _)| source.start.min(range.end));
+                    pieces.push((start..end, start));
+                    start = end;
+                }
+            }
+        }
+        pieces
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Default for RangeMap<T> {
+    fn default() -> RangeMap<T> {
+        RangeMap::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn set_operations() {
+        let mut set = [0..5, 10..15, 5..7, 20..25]
+            .into_iter()
+            .collect::<RangeSet<_>>();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..7, 10..15, 20..25]);
+        assert!(set.contains(6));
+        assert!(!set.contains(7));
+        assert_eq!(set.total_length(), 17);
+
+        set.remove(3..12);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..3, 12..15, 20..25]);
+
+        let other = [2..13, 24..30].into_iter().collect::<RangeSet<_>>();
+        assert_eq!(
+            set.intersection(&other).iter().collect::<Vec<_>>(),
+            vec![2..3, 12..13, 24..25]
+        );
+        assert_eq!(
+            set.difference(&other).iter().collect::<Vec<_>>(),
+            vec![0..2, 13..15, 20..24]
+        );
+        assert_eq!(
+            set.union(&other).iter().collect::<Vec<_>>(),
+            vec![0..15, 20..30]
+        );
+    }
+
+    #[test]
+    fn map_composition() {
+        let mut first = RangeMap::new();
+        first.insert(0..10, 100);
+        let mut second = RangeMap::new();
+        second.insert(105..200, 5);
+        second.insert(10..20, 50);
+
+        let composed = first.then(&second);
+        for value in 0..30 {
+            assert_eq!(composed.get(value), second.get(first.get(value)));
+        }
+        assert_eq!(
+            composed
+                .map_set(&RangeSet::from(0..30))
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![5..10, 20..30, 50..60, 100..105]
+        );
+    }
+}
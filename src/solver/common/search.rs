@@ -0,0 +1,398 @@
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// These searches are generic over the state type S, which can be anything that identifies a node
+// of the graph being searched (e.g. a position, or a position and a facing direction), and the cost
+// type C. Neighbours are found by calling a function on each state as it's explored, so the graph
+// never needs to be built up front. Every search keeps its frontier in a queue or heap rather than
+// on the call stack, so deep searches can't overflow the stack.
+
+/// A path found by a search, listing every state from the start state to a goal state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S, C> {
+    pub states: Vec<S>,
+    pub cost: C,
+}
+
+/// Uses breadth-first search to find a shortest path from the start state to any goal state, where
+/// every step has a cost of 1. Returns None if no goal state can be reached.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut states = vec![start.clone()];
+    let mut predecessors = vec![None];
+    let mut visited = FxHashSet::default();
+    visited.insert(start);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        if is_goal(&states[index]) {
+            let states = trace_path(index, |index| predecessors[index])
+                .map(|index| states[index].clone())
+                .collect::<Vec<_>>();
+            return Some(Path {
+                cost: states.len() - 1,
+                states,
+            });
+        }
+
+        for neighbour in neighbours(&states[index]) {
+            if visited.insert(neighbour.clone()) {
+                queue.push_back(states.len());
+                states.push(neighbour);
+                predecessors.push(Some(index));
+            }
+        }
+    }
+
+    None
+}
+
+/// Uses Dijkstra's algorithm to find a cheapest path from the start state to any goal state. The
+/// neighbour function returns each neighbouring state along with the cost of stepping to it.
+/// Returns None if no goal state can be reached.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    a_star(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Uses the A* algorithm to find a cheapest path from the start state to any goal state. The
+/// neighbour function returns each neighbouring state along with the cost of stepping to it, and
+/// the heuristic estimates the cost from a state to the nearest goal state. The heuristic must
+/// never overestimate this cost, or the path found might not be the cheapest. Returns None if no
+/// goal state can be reached.
+pub fn a_star<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    search(start, neighbours, heuristic, is_goal, false).path()
+}
+
+/// Uses Dijkstra's algorithm to find every cheapest path from the start state to the goal states.
+/// The neighbour function returns each neighbouring state along with the cost of stepping to it.
+/// Unlike [`dijkstra`], every predecessor that reaches a state with the same cheapest cost is
+/// remembered, so all the paths that tie for the cheapest can be recovered from the result.
+pub fn shortest_paths<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> ShortestPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    search(start, neighbours, |_| C::default(), is_goal, true)
+}
+
+/// Every cheapest path from a start state to the goal states, as found by [`shortest_paths`].
+/// Reaching any goal state counts, so if several goal states tie for the cheapest cost, paths to
+/// all of them are included.
+pub struct ShortestPaths<S, C> {
+    // Every state explored, and the cheapest cost found to reach it. The start state is index 0.
+    states: Vec<S>,
+    costs: Vec<C>,
+    // For each state, the states immediately before it on a cheapest path to it.
+    predecessors: Vec<Vec<usize>>,
+    indices: FxHashMap<S, usize>,
+    // Every goal state reached for the cheapest cost.
+    goals: Vec<usize>,
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> ShortestPaths<S, C> {
+    /// Returns the cost of the cheapest paths, or None if no goal state can be reached.
+    #[must_use]
+    pub fn cost(&self) -> Option<C> {
+        self.goals.first().map(|goal| self.costs[*goal])
+    }
+
+    /// Iterates over every goal state reached for the cheapest cost.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|goal| &self.states[*goal])
+    }
+
+    /// Returns the cheapest cost found to reach a state, or None if the state was never reached.
+    /// States that are more expensive to reach than the goal states may not have been fully
+    /// explored, so their cost is only guaranteed to be the cheapest if it's no more than
+    /// [`ShortestPaths::cost`].
+    #[must_use]
+    pub fn cost_to(&self, state: &S) -> Option<C> {
+        self.indices.get(state).map(|index| self.costs[*index])
+    }
+
+    /// Returns one of the cheapest paths, or None if no goal state can be reached.
+    #[must_use]
+    pub fn path(&self) -> Option<Path<S, C>> {
+        let goal = *self.goals.first()?;
+        Some(Path {
+            states: trace_path(goal, |index| self.predecessors[index].first().copied())
+                .map(|index| self.states[index].clone())
+                .collect(),
+            cost: self.costs[goal],
+        })
+    }
+
+    /// Returns every one of the cheapest paths. The number of paths can grow exponentially with the
+    /// size of the graph, so prefer [`ShortestPaths::states_on_paths`] when only the states
+    /// themselves are needed.
+    #[must_use]
+    pub fn paths(&self) -> Vec<Path<S, C>> {
+        // Each partial path is built backwards from a goal state, and branches whenever a state
+        // has more than one predecessor.
+        let mut partial_paths = self
+            .goals
+            .iter()
+            .map(|goal| vec![*goal])
+            .collect::<Vec<_>>();
+        let mut paths = Vec::new();
+        while let Some(partial_path) = partial_paths.pop() {
+            let last = partial_path[partial_path.len() - 1];
+            if last == 0 {
+                paths.push(Path {
+                    states: partial_path
+                        .iter()
+                        .rev()
+                        .map(|index| self.states[*index].clone())
+                        .collect(),
+                    cost: self.costs[partial_path[0]],
+                });
+                continue;
+            }
+            for predecessor in &self.predecessors[last] {
+                let mut extended_path = partial_path.clone();
+                extended_path.push(*predecessor);
+                partial_paths.push(extended_path);
+            }
+        }
+        paths
+    }
+
+    /// Iterates over every state that lies on at least one of the cheapest paths, including the
+    /// start state and goal states. Each state is only included once.
+    pub fn states_on_paths(&self) -> impl Iterator<Item = &S> {
+        let mut on_paths = FxHashSet::default();
+        let mut stack = self.goals.clone();
+        while let Some(index) = stack.pop() {
+            if on_paths.insert(index) {
+                stack.extend(&self.predecessors[index]);
+            }
+        }
+        on_paths.into_iter().map(|index| &self.states[index])
+    }
+}
+
+// An entry in the priority queue of an A* search. Entries are ordered by priority, which is the
+// cost to reach the state plus the heuristic's estimate of the remaining cost.
+struct QueueEntry<C> {
+    priority: C,
+    cost: C,
+    index: usize,
+}
+
+impl<C: Ord> PartialEq for QueueEntry<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for QueueEntry<C> {}
+
+impl<C: Ord> PartialOrd for QueueEntry<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> Ord for QueueEntry<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+// Runs an A* search from the start state. If find_all is false, the search stops at the first goal
+// state reached and only one predecessor is remembered for each state. Otherwise, the search
+// continues until every state that could be on a cheapest path has been explored, remembering every
+// predecessor that ties for the cheapest cost.
+fn search<S, C, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+    find_all: bool,
+) -> ShortestPaths<S, C>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse(QueueEntry {
+        priority: heuristic(&start),
+        cost: C::default(),
+        index: 0,
+    }));
+    let mut paths = ShortestPaths {
+        states: vec![start.clone()],
+        costs: vec![C::default()],
+        predecessors: vec![Vec::new()],
+        indices: FxHashMap::from_iter([(start, 0)]),
+        goals: Vec::new(),
+    };
+
+    while let Some(Reverse(QueueEntry {
+        priority,
+        cost,
+        index,
+    })) = heap.pop()
+    {
+        // A state is pushed again whenever a cheaper way to reach it is found, so skip any entries
+        // that have since been beaten.
+        if cost > paths.costs[index] {
+            continue;
+        }
+        // Once the cheapest goal has been found, anything with a higher priority can't be on a
+        // cheapest path.
+        if let Some(goal_cost) = paths.cost()
+            && priority > goal_cost
+        {
+            break;
+        }
+
+        let state = paths.states[index].clone();
+        if is_goal(&state) {
+            paths.goals.push(index);
+            if find_all {
+                continue;
+            }
+            break;
+        }
+
+        for (neighbour, step_cost) in neighbours(&state) {
+            let neighbour_cost = cost + step_cost;
+            let neighbour_index = if let Some(neighbour_index) = paths.indices.get(&neighbour) {
+                let neighbour_index = *neighbour_index;
+                match neighbour_cost.cmp(&paths.costs[neighbour_index]) {
+                    Ordering::Less => {
+                        paths.costs[neighbour_index] = neighbour_cost;
+                        paths.predecessors[neighbour_index].clear();
+                    }
+                    Ordering::Equal if find_all => {
+                        paths.predecessors[neighbour_index].push(index);
+                        continue;
+                    }
+                    _ => continue,
+                }
+                neighbour_index
+            } else {
+                let neighbour_index = paths.states.len();
+                paths.indices.insert(neighbour.clone(), neighbour_index);
+                paths.states.push(neighbour);
+                paths.costs.push(neighbour_cost);
+                paths.predecessors.push(Vec::new());
+                neighbour_index
+            };
+
+            paths.predecessors[neighbour_index].push(index);
+            heap.push(Reverse(QueueEntry {
+                priority: neighbour_cost + heuristic(&paths.states[neighbour_index]),
+                cost: neighbour_cost,
+                index: neighbour_index,
+            }));
+        }
+    }
+
+    paths
+}
+
+// Follows predecessors back from the index of the last state on a path to the start state, then
+// returns the indexes along the path in order from the start.
+fn trace_path(
+    last: usize,
+    mut predecessor: impl FnMut(usize) -> Option<usize>,
+) -> impl Iterator<Item = usize> {
+    let mut path = vec![last];
+    let mut current = last;
+    while let Some(previous) = predecessor(current) {
+        path.push(previous);
+        current = previous;
+    }
+    path.into_iter().rev()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A small weighted graph with two cheapest paths from 0 to 4, 0-1-3-4 and 0-2-3-4, and a more
+    // expensive direct edge from 0 to 4.
+    // The searches pass states to the neighbour function by reference, so this can't take u32.
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn neighbours(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 1), (4, 10)],
+            1 | 2 => vec![(3, 2)],
+            3 => vec![(4, 1)],
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn breadth_first_search() {
+        let path = bfs(0, |node: &u32| [node + 1, node * 2], |node| *node == 10)
+            .expect("Path should exist");
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states, vec![0, 1, 2, 4, 5, 10]);
+        assert_eq!(bfs(0, |_: &u32| [], |node| *node == 1), None);
+    }
+
+    #[test]
+    fn cheapest_path() {
+        let path = dijkstra(0, neighbours, |node| *node == 4).expect("Path should exist");
+        assert_eq!(path.cost, 4);
+        assert_eq!(path.states.len(), 4);
+        let path = a_star(0, neighbours, |node| 4 - node.min(&4), |node| *node == 4)
+            .expect("Path should exist");
+        assert_eq!(path.cost, 4);
+        assert_eq!(dijkstra(1, neighbours, |node| *node == 0), None);
+    }
+
+    #[test]
+    fn all_cheapest_paths() {
+        let paths = shortest_paths(0, neighbours, |node| *node == 4);
+        assert_eq!(paths.cost(), Some(4));
+        let mut all_paths = paths
+            .paths()
+            .into_iter()
+            .map(|path| path.states)
+            .collect::<Vec<_>>();
+        all_paths.sort_unstable();
+        assert_eq!(all_paths, vec![vec![0, 1, 3, 4], vec![0, 2, 3, 4]]);
+        let mut states = paths.states_on_paths().copied().collect::<Vec<_>>();
+        states.sort_unstable();
+        assert_eq!(states, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,123 @@
+/// A disjoint-set (union-find) data structure over the elements `0..len`. Every element starts in
+/// its own set, and sets can be merged together. Merging always makes the root of the smaller set a
+/// child of the root of the larger set, and finding an element's root compresses the path to it, so
+/// every operation takes nearly constant amortized time.
+#[derive(Clone, Debug)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    // The number of elements in each set, only kept up to date for root elements.
+    sizes: Vec<usize>,
+    component_count: usize,
+}
+
+impl UnionFind {
+    /// Creates a disjoint set where each of the elements `0..len` is in a set by itself.
+    #[must_use]
+    pub fn new(len: usize) -> UnionFind {
+        UnionFind {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            component_count: len,
+        }
+    }
+
+    /// Returns the number of elements.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Returns true if there are no elements.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Returns the root element of the set containing an element. Two elements are in the same set
+    /// exactly when they have the same root.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // Point every element along the path directly at the root, so finding them again is quick.
+        let mut current = element;
+        while current != root {
+            let parent = self.parents[current];
+            self.parents[current] = root;
+            current = parent;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing two elements. Returns true if they were in different sets, or
+    /// false if they were already in the same set.
+    pub fn union(&mut self, element_a: usize, element_b: usize) -> bool {
+        let root_a = self.find(element_a);
+        let root_b = self.find(element_b);
+        if root_a == root_b {
+            return false;
+        }
+
+        let (larger_root, smaller_root) = if self.sizes[root_a] >= self.sizes[root_b] {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.parents[smaller_root] = larger_root;
+        self.sizes[larger_root] += self.sizes[smaller_root];
+        self.component_count -= 1;
+        true
+    }
+
+    /// Returns true if two elements are in the same set.
+    pub fn connected(&mut self, element_a: usize, element_b: usize) -> bool {
+        self.find(element_a) == self.find(element_b)
+    }
+
+    /// Returns the number of elements in the set containing an element.
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Returns the number of distinct sets.
+    #[must_use]
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+
+    /// Returns the number of elements in each distinct set, in no particular order.
+    #[must_use]
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|element| self.parents[*element] == *element)
+            .map(|root| self.sizes[root])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn union_and_find() {
+        let mut union_find = UnionFind::new(6);
+        assert_eq!(union_find.component_count(), 6);
+        assert!(union_find.union(0, 1));
+        assert!(union_find.union(2, 3));
+        assert!(union_find.union(1, 3));
+        assert!(!union_find.union(0, 2));
+        assert!(union_find.connected(0, 3));
+        assert!(!union_find.connected(0, 4));
+        assert_eq!(union_find.component_size(2), 4);
+        assert_eq!(union_find.component_size(5), 1);
+        assert_eq!(union_find.component_count(), 3);
+        let mut sizes = union_find.component_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 1, 4]);
+    }
+}
@@ -0,0 +1,361 @@
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+use std::str::FromStr;
+
+pub mod trace;
+
+/// Storage for the registers of a [`Machine`], which maps each register to its current value.
+pub trait Registers {
+    /// The type that names a register.
+    type Register: Copy;
+    /// The type of value that a register holds.
+    type Value: Copy;
+
+    /// Returns the value in a register.
+    fn get(&self, register: Self::Register) -> Self::Value;
+
+    /// Returns a mutable reference to the value in a register.
+    fn get_mut(&mut self, register: Self::Register) -> &mut Self::Value;
+}
+
+// A fixed number of registers, named by their index.
+impl<V: Copy, const N: usize> Registers for [V; N] {
+    type Register = usize;
+    type Value = V;
+
+    fn get(&self, register: usize) -> V {
+        self[register]
+    }
+
+    fn get_mut(&mut self, register: usize) -> &mut V {
+        &mut self[register]
+    }
+}
+
+// Registers that are created as they're used, each starting with the default value.
+// Solvers only use FxHashMap, so there's no need to support other hashers.
+#[allow(clippy::implicit_hasher)]
+impl<R: Copy + Eq + Hash, V: Copy + Default> Registers for FxHashMap<R, V> {
+    type Register = R;
+    type Value = V;
+
+    fn get(&self, register: R) -> V {
+        self.get(&register).copied().unwrap_or_default()
+    }
+
+    fn get_mut(&mut self, register: R) -> &mut V {
+        self.entry(register).or_default()
+    }
+}
+
+/// Returns the index of a register named by a single lowercase letter, where `a` is 0.
+///
+/// # Panics
+///
+/// Panics if the name isn't a single lowercase letter.
+#[must_use]
+pub fn letter_register(name: &str) -> usize {
+    match name.as_bytes() {
+        [letter @ b'a'..=b'z'] => usize::from(letter - b'a'),
+        _ => panic!("Register name should be a single lowercase letter"),
+    }
+}
+
+/// Returns the single lowercase letter that names a register, where 0 is `a`. This is the inverse
+/// of [`letter_register`].
+///
+/// # Panics
+///
+/// Panics if the register index is beyond `z`.
+#[must_use]
+pub fn letter_name(register: usize) -> char {
+    u8::try_from(register)
+        .ok()
+        .filter(|register| *register < 26)
+        .map(|register| char::from(b'a' + register))
+        .expect("Register index should correspond to a lowercase letter")
+}
+
+/// An instruction operand that is either the name of a register to read from, or a literal value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegisterOrValue<R, V> {
+    Register(R),
+    Value(V),
+}
+
+impl<R, V: FromStr> RegisterOrValue<R, V> {
+    /// Parses an operand as a literal value if possible, or otherwise as a register name.
+    pub fn parse(operand: &str, parse_register: impl FnOnce(&str) -> R) -> RegisterOrValue<R, V> {
+        match operand.parse() {
+            Ok(value) => RegisterOrValue::Value(value),
+            Err(_) => RegisterOrValue::Register(parse_register(operand)),
+        }
+    }
+}
+
+impl<R: Display, V: Display> Display for RegisterOrValue<R, V> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RegisterOrValue::Register(register) => register.fmt(f),
+            RegisterOrValue::Value(value) => value.fmt(f),
+        }
+    }
+}
+
+impl<R, V> RegisterOrValue<R, V> {
+    /// Converts the register named by the operand, leaving literal values unchanged. This is useful
+    /// for showing register indexes by name.
+    pub fn map_register<S>(self, map: impl FnOnce(R) -> S) -> RegisterOrValue<S, V> {
+        match self {
+            RegisterOrValue::Register(register) => RegisterOrValue::Register(map(register)),
+            RegisterOrValue::Value(value) => RegisterOrValue::Value(value),
+        }
+    }
+}
+
+impl<R: Copy, V: Copy> RegisterOrValue<R, V> {
+    /// Returns the value of the operand, reading it from the registers if it names a register.
+    pub fn get(&self, registers: &impl Registers<Register = R, Value = V>) -> V {
+        match self {
+            RegisterOrValue::Register(register) => registers.get(*register),
+            RegisterOrValue::Value(value) => *value,
+        }
+    }
+}
+
+/// What the machine should do after executing an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    /// Move on to the next instruction.
+    Next,
+    /// Move the program counter by an offset from the current instruction.
+    Jump(isize),
+    /// Move the program counter to an instruction.
+    Goto(usize),
+    /// Stay on the current instruction until more input is available, then execute it again.
+    Wait,
+    /// Stop running the program.
+    Halt,
+}
+
+/// Whether a machine can keep running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Running,
+    /// The current instruction is waiting for input.
+    Waiting,
+    /// The program has finished, either because an instruction halted it or because the program
+    /// counter moved outside of the program.
+    Halted,
+}
+
+/// The definition of a machine's instructions and what they do. The instruction set can hold
+/// state of its own, such as statistics about the instructions that were executed.
+pub trait InstructionSet {
+    type Instruction;
+    type Registers: Registers;
+
+    /// Executes an instruction, updating the machine's state, and returns how the program counter
+    /// should move.
+    fn execute(
+        &mut self,
+        instruction: &Self::Instruction,
+        state: &mut State<Self::Registers>,
+    ) -> Control;
+}
+
+/// The state of a machine that instructions can read and change.
+#[derive(Clone, Debug)]
+pub struct State<R: Registers> {
+    pub registers: R,
+    /// The index of the next instruction to execute.
+    pub program_counter: usize,
+    /// Values waiting to be read by the program.
+    pub input: VecDeque<R::Value>,
+    /// Values written by the program that haven't been taken yet.
+    pub output: VecDeque<R::Value>,
+}
+
+impl<R: Registers> State<R> {
+    /// Returns the value of an operand.
+    pub fn value(&self, operand: &RegisterOrValue<R::Register, R::Value>) -> R::Value {
+        operand.get(&self.registers)
+    }
+
+    /// Returns a mutable reference to the value in a register.
+    pub fn register_mut(&mut self, register: R::Register) -> &mut R::Value {
+        self.registers.get_mut(register)
+    }
+}
+
+/// A machine that runs a program of instructions from an instruction set. Execution starts at the
+/// first instruction, and the program halts when an instruction says so or when the program
+/// counter moves outside of the program.
+pub struct Machine<S: InstructionSet> {
+    pub instruction_set: S,
+    pub program: Vec<S::Instruction>,
+    pub state: State<S::Registers>,
+    status: Status,
+}
+
+impl<S: InstructionSet> Machine<S> {
+    /// Creates a machine that will run a program from its first instruction, with the registers
+    /// starting with the given values.
+    pub fn new(instruction_set: S, program: Vec<S::Instruction>, registers: S::Registers) -> Self {
+        Machine {
+            instruction_set,
+            program,
+            state: State {
+                registers,
+                program_counter: 0,
+                input: VecDeque::new(),
+                output: VecDeque::new(),
+            },
+            status: Status::Running,
+        }
+    }
+
+    /// Executes the current instruction, and returns whether the machine can keep running.
+    pub fn step(&mut self) -> Status {
+        if self.status == Status::Halted {
+            return Status::Halted;
+        }
+        let Some(instruction) = self.program.get(self.state.program_counter) else {
+            self.status = Status::Halted;
+            return Status::Halted;
+        };
+
+        let program_counter = self.state.program_counter;
+        self.status = match self.instruction_set.execute(instruction, &mut self.state) {
+            Control::Next => self.move_to(program_counter + 1),
+            // If the program counter would go below 0, it wraps around to an index that is far
+            // outside of the program, so the program halts.
+            Control::Jump(offset) => self.move_to(program_counter.wrapping_add_signed(offset)),
+            Control::Goto(target) => self.move_to(target),
+            Control::Wait => Status::Waiting,
+            Control::Halt => Status::Halted,
+        };
+        self.status
+    }
+
+    /// Executes instructions until the program halts or waits for input, and returns which one
+    /// happened.
+    pub fn run(&mut self) -> Status {
+        loop {
+            let status = self.step();
+            if status != Status::Running {
+                return status;
+            }
+        }
+    }
+
+    /// Returns the status after the last instruction that was executed.
+    #[must_use]
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Returns true if running the machine can't make any progress, because it has halted or is
+    /// waiting for input that hasn't arrived.
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        match self.status {
+            Status::Running => false,
+            Status::Waiting => self.state.input.is_empty(),
+            Status::Halted => true,
+        }
+    }
+
+    fn move_to(&mut self, program_counter: usize) -> Status {
+        self.state.program_counter = program_counter;
+        if program_counter < self.program.len() {
+            Status::Running
+        } else {
+            Status::Halted
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A small instruction set that reads input into a register, counts the register down to zero
+    // while writing each value to the output, and then stops.
+    enum Instruction {
+        Read(usize),
+        Write(RegisterOrValue<usize, i32>),
+        Decrement(usize),
+        JumpIfPositive(usize, isize),
+    }
+
+    struct Countdown {
+        executed: usize,
+    }
+
+    impl InstructionSet for Countdown {
+        type Instruction = Instruction;
+        type Registers = [i32; 2];
+
+        fn execute(&mut self, instruction: &Instruction, state: &mut State<[i32; 2]>) -> Control {
+            self.executed += 1;
+            match instruction {
+                Instruction::Read(register) => match state.input.pop_front() {
+                    Some(value) => *state.register_mut(*register) = value,
+                    None => return Control::Wait,
+                },
+                Instruction::Write(operand) => {
+                    let value = state.value(operand);
+                    state.output.push_back(value);
+                }
+                Instruction::Decrement(register) => *state.register_mut(*register) -= 1,
+                Instruction::JumpIfPositive(register, offset) => {
+                    if state.registers.get(*register) > 0 {
+                        return Control::Jump(*offset);
+                    }
+                }
+            }
+            Control::Next
+        }
+    }
+
+    #[test]
+    fn machine() {
+        let program = vec![
+            Instruction::Read(0),
+            Instruction::Write(RegisterOrValue::parse("a", letter_register)),
+            Instruction::Decrement(0),
+            Instruction::JumpIfPositive(0, -2),
+            Instruction::Write(RegisterOrValue::parse("-1", letter_register)),
+        ];
+        let mut machine = Machine::new(Countdown { executed: 0 }, program, [0; 2]);
+
+        assert_eq!(machine.run(), Status::Waiting);
+        assert!(machine.is_blocked());
+        machine.state.input.push_back(3);
+        assert!(!machine.is_blocked());
+        assert_eq!(machine.run(), Status::Halted);
+        assert_eq!(machine.state.output, [3, 2, 1, -1]);
+        assert_eq!(machine.instruction_set.executed, 12);
+        assert_eq!(machine.step(), Status::Halted);
+        assert_eq!(
+            RegisterOrValue::<usize, i32>::parse("h", letter_register)
+                .map_register(letter_name)
+                .to_string(),
+            "h"
+        );
+    }
+
+    #[test]
+    fn hash_map_registers() {
+        let mut registers = FxHashMap::default();
+        *Registers::get_mut(&mut registers, 'x') += 5;
+        assert_eq!(RegisterOrValue::Register('x').get(&registers), 5);
+        assert_eq!(
+            RegisterOrValue::<char, i64>::Register('y').get(&registers),
+            0
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use super::{InstructionSet, Machine, Registers, Status, letter_name};
+use rustc_hash::FxHashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::io::{self, BufRead, Write};
+
+/// Registers that can be listed with their names, so they can be shown while tracing.
+pub trait DescribeRegisters: Registers {
+    /// Returns every register and its value, such as `a=1 b=0`.
+    fn describe(&self) -> String;
+}
+
+// Registers named by their index are shown by letter, where index 0 is `a`.
+impl<V: Copy + Display, const N: usize> DescribeRegisters for [V; N] {
+    fn describe(&self) -> String {
+        self.iter()
+            .enumerate()
+            .map(|(register, value)| format!("{}={value}", letter_name(register)))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+// Only registers that have been used exist, and they're shown in order of their names.
+#[allow(clippy::implicit_hasher)]
+impl<R: Copy + Eq + Hash + Ord + Display, V: Copy + Default + Display> DescribeRegisters
+    for FxHashMap<R, V>
+{
+    fn describe(&self) -> String {
+        let mut registers = self.iter().collect::<Vec<_>>();
+        registers.sort_unstable_by_key(|(register, _)| **register);
+        registers
+            .into_iter()
+            .map(|(register, value)| format!("{register}={value}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Settings for tracing a program. Registers are given by name so that the settings can come from
+/// the command line before the program is parsed.
+#[derive(Clone, Debug, Default)]
+pub struct TraceOptions {
+    /// The program counters to stop at before executing the instruction there.
+    pub breakpoints: Vec<usize>,
+    /// The names of registers to report whenever their values change.
+    pub watches: Vec<String>,
+    /// The most instructions to execute before giving up, for programs that run for far too long
+    /// to finish.
+    pub step_limit: Option<usize>,
+    /// The number of most-executed instructions to list once tracing is finished.
+    pub hot_instructions: usize,
+}
+
+/// Runs machines while reporting what they do. Breakpoint stops, watched register changes, and a
+/// histogram of the most-executed instructions are written to the report, and if a trace is given,
+/// every executed instruction is written to it along with the registers afterwards.
+///
+/// If commands are given, each breakpoint waits for commands to be read from them: `s` (or an empty
+/// line) steps over a single instruction, `r` shows the registers, `c` continues to the next
+/// breakpoint, and `q` stops running. Otherwise, breakpoints just show the registers and continue.
+pub struct Tracer<'a> {
+    options: TraceOptions,
+    report: &'a mut dyn Write,
+    trace: Option<&'a mut dyn Write>,
+    commands: Option<&'a mut dyn BufRead>,
+    steps: usize,
+    // The number of times the instruction at each program counter has been executed.
+    hits: Vec<usize>,
+    stepping: bool,
+    stopped: bool,
+}
+
+impl<'a> Tracer<'a> {
+    /// Creates a tracer that writes its report and trace to the given writers.
+    pub fn new(
+        options: TraceOptions,
+        report: &'a mut dyn Write,
+        trace: Option<&'a mut dyn Write>,
+        commands: Option<&'a mut dyn BufRead>,
+    ) -> Tracer<'a> {
+        Tracer {
+            options,
+            report,
+            trace,
+            commands,
+            steps: 0,
+            hits: Vec::new(),
+            stepping: false,
+            stopped: false,
+        }
+    }
+
+    /// Returns the number of instructions executed so far.
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Runs a machine until it halts or waits for input, or until tracing is stopped by the `q`
+    /// command or the step limit. This can be called again to keep running, such as after giving
+    /// the machine more input. Watched registers are found with `parse_register`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a watched register can't be parsed, or if reading commands or writing
+    /// the report or trace fails.
+    pub fn run<S>(
+        &mut self,
+        machine: &mut Machine<S>,
+        parse_register: impl Fn(&str) -> Option<<S::Registers as Registers>::Register>,
+    ) -> io::Result<Status>
+    where
+        S: InstructionSet,
+        S::Instruction: Display,
+        S::Registers: DescribeRegisters,
+        <S::Registers as Registers>::Value: PartialEq + Display,
+    {
+        let watches = self
+            .options
+            .watches
+            .iter()
+            .map(|name| {
+                parse_register(name)
+                    .map(|register| (name.clone(), register))
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("\"{name}\" is not a register"),
+                        )
+                    })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        if self.hits.len() < machine.program.len() {
+            self.hits.resize(machine.program.len(), 0);
+        }
+
+        loop {
+            if self.stopped {
+                return Ok(machine.status());
+            }
+            if self
+                .options
+                .step_limit
+                .is_some_and(|step_limit| self.steps >= step_limit)
+            {
+                writeln!(
+                    self.report,
+                    "Stopped after reaching the step limit of {} steps",
+                    self.steps
+                )?;
+                self.stopped = true;
+                continue;
+            }
+
+            let program_counter = machine.state.program_counter;
+            let Some(instruction) = machine.program.get(program_counter) else {
+                return Ok(machine.step());
+            };
+            if self.stepping || self.options.breakpoints.contains(&program_counter) {
+                self.pause(machine)?;
+                if self.stopped {
+                    continue;
+                }
+            }
+
+            let old_values = watches
+                .iter()
+                .map(|(_, register)| machine.state.registers.get(*register))
+                .collect::<Vec<_>>();
+            let description = instruction.to_string();
+            let status = machine.step();
+            if status == Status::Waiting {
+                return Ok(status);
+            }
+            self.steps += 1;
+            self.hits[program_counter] += 1;
+
+            if let Some(trace) = &mut self.trace {
+                writeln!(
+                    trace,
+                    "{:>10} {program_counter:>5}  {description:<24} {}",
+                    self.steps,
+                    machine.state.registers.describe()
+                )?;
+            }
+            for ((name, register), old_value) in watches.iter().zip(old_values) {
+                let new_value = machine.state.registers.get(*register);
+                if new_value != old_value {
+                    writeln!(
+                        self.report,
+                        "Step {}, pc {program_counter} ({description}): {name} changed from {old_value} to {new_value}",
+                        self.steps
+                    )?;
+                }
+            }
+
+            if status == Status::Halted {
+                return Ok(status);
+            }
+        }
+    }
+
+    /// Writes the final registers of a machine and a histogram of the instructions it executed most
+    /// often.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing the report fails.
+    pub fn finish<S>(&mut self, machine: &Machine<S>) -> io::Result<()>
+    where
+        S: InstructionSet,
+        S::Instruction: Display,
+        S::Registers: DescribeRegisters,
+    {
+        writeln!(
+            self.report,
+            "Executed {} instructions, ending at pc {} with registers {}",
+            self.steps,
+            machine.state.program_counter,
+            machine.state.registers.describe()
+        )?;
+
+        let mut hot_instructions = self
+            .hits
+            .iter()
+            .enumerate()
+            .filter(|(_, hits)| **hits > 0)
+            .collect::<Vec<_>>();
+        hot_instructions.sort_unstable_by_key(|(program_counter, hits)| {
+            (std::cmp::Reverse(**hits), *program_counter)
+        });
+        hot_instructions.truncate(self.options.hot_instructions);
+        if let Some((_, most_hits)) = hot_instructions.first() {
+            writeln!(self.report, "Most executed instructions:")?;
+            let most_hits = **most_hits;
+            for (program_counter, hits) in hot_instructions {
+                // Scale the bars so the most executed instruction gets a bar 40 characters long.
+                let bar_length = (hits * 40).div_ceil(most_hits);
+                writeln!(
+                    self.report,
+                    "{program_counter:>5}  {:<24} {hits:>12} {}",
+                    machine.program[program_counter].to_string(),
+                    "#".repeat(bar_length)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    // Reads commands until one of them resumes execution. Without any commands, this just shows
+    // the registers.
+    fn pause<S>(&mut self, machine: &Machine<S>) -> io::Result<()>
+    where
+        S: InstructionSet,
+        S::Instruction: Display,
+        S::Registers: DescribeRegisters,
+    {
+        let program_counter = machine.state.program_counter;
+        let instruction = &machine.program[program_counter];
+        let registers = machine.state.registers.describe();
+        let Some(commands) = &mut self.commands else {
+            writeln!(
+                self.report,
+                "Breakpoint at pc {program_counter} ({instruction}) after {} steps: {registers}",
+                self.steps
+            )?;
+            return Ok(());
+        };
+
+        loop {
+            write!(self.report, "pc {program_counter} ({instruction})> ")?;
+            self.report.flush()?;
+            let mut command = String::new();
+            // Running out of commands continues to the end of the program.
+            if commands.read_line(&mut command)? == 0 {
+                self.stepping = false;
+                self.commands = None;
+                writeln!(self.report)?;
+                return Ok(());
+            }
+
+            match command.trim() {
+                "" | "s" | "step" => {
+                    self.stepping = true;
+                    return Ok(());
+                }
+                "c" | "continue" => {
+                    self.stepping = false;
+                    return Ok(());
+                }
+                "r" | "registers" => writeln!(self.report, "{registers}")?,
+                "q" | "quit" => {
+                    self.stopped = true;
+                    return Ok(());
+                }
+                other => writeln!(
+                    self.report,
+                    "Unknown command \"{other}\", expected s(tep), c(ontinue), r(egisters), or q(uit)"
+                )?,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{Control, State};
+    use super::*;
+    use std::fmt::{self, Formatter};
+
+    // Adds 1 to register a until it reaches 3, then halts.
+    enum Instruction {
+        Increment,
+        JumpIfBelowThree,
+    }
+
+    impl Display for Instruction {
+        fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+            match self {
+                Instruction::Increment => write!(f, "inc a"),
+                Instruction::JumpIfBelowThree => write!(f, "jlt a 3 -1"),
+            }
+        }
+    }
+
+    struct Counter;
+
+    impl InstructionSet for Counter {
+        type Instruction = Instruction;
+        type Registers = [u32; 1];
+
+        fn execute(&mut self, instruction: &Instruction, state: &mut State<[u32; 1]>) -> Control {
+            match instruction {
+                Instruction::Increment => state.registers[0] += 1,
+                Instruction::JumpIfBelowThree => {
+                    if state.registers[0] < 3 {
+                        return Control::Jump(-1);
+                    }
+                }
+            }
+            Control::Next
+        }
+    }
+
+    fn counter() -> Machine<Counter> {
+        let program = vec![Instruction::Increment, Instruction::JumpIfBelowThree];
+        Machine::new(Counter, program, [0])
+    }
+
+    fn parse_register(name: &str) -> Option<usize> {
+        (name == "a").then_some(0)
+    }
+
+    #[test]
+    fn trace_and_watch() {
+        let options = TraceOptions {
+            watches: vec!["a".to_string()],
+            hot_instructions: 1,
+            ..TraceOptions::default()
+        };
+        let mut report = Vec::new();
+        let mut trace = Vec::new();
+        let mut machine = counter();
+        let mut tracer = Tracer::new(options, &mut report, Some(&mut trace), None);
+        assert_eq!(
+            tracer
+                .run(&mut machine, parse_register)
+                .expect("Tracing should succeed"),
+            Status::Halted
+        );
+        assert_eq!(tracer.steps(), 6);
+        tracer.finish(&machine).expect("Writing should succeed");
+
+        let report = String::from_utf8(report).expect("Report should be UTF-8");
+        let trace = String::from_utf8(trace).expect("Trace should be UTF-8");
+        assert_eq!(trace.lines().count(), 6);
+        assert!(
+            trace
+                .lines()
+                .last()
+                .is_some_and(|line| line.ends_with("a=3"))
+        );
+        assert!(report.contains("Step 5, pc 0 (inc a): a changed from 2 to 3"));
+        assert!(report.contains("    0  inc a"));
+        assert!(!report.contains("jlt"));
+    }
+
+    #[test]
+    fn breakpoints_and_commands() {
+        let options = TraceOptions {
+            breakpoints: vec![1],
+            step_limit: Some(5),
+            ..TraceOptions::default()
+        };
+        let mut report = Vec::new();
+        let mut commands = "r\nc\ns\nx\nq\n".as_bytes();
+        let mut machine = counter();
+        let mut tracer = Tracer::new(options, &mut report, None, Some(&mut commands));
+        assert_eq!(
+            tracer
+                .run(&mut machine, parse_register)
+                .expect("Tracing should succeed"),
+            Status::Running
+        );
+        // Stopped at the breakpoint the second time, stepped once, then quit before the jump.
+        assert_eq!(tracer.steps(), 4);
+        assert_eq!(machine.state.registers, [2]);
+
+        let report = String::from_utf8(report).expect("Report should be UTF-8");
+        assert!(report.starts_with("pc 1 (jlt a 3 -1)> a=1\n"));
+        assert!(report.contains("Unknown command \"x\""));
+
+        let mut report = Vec::new();
+        let options = TraceOptions {
+            watches: vec!["z".to_string()],
+            ..TraceOptions::default()
+        };
+        let mut tracer = Tracer::new(options, &mut report, None, None);
+        assert!(tracer.run(&mut counter(), parse_register).is_err());
+    }
+}
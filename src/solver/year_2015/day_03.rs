@@ -1,5 +1,6 @@
-use crate::solver::{Solution, AdventOfCode};
-use rustc_hash::FxHashSet;
+use crate::solver::common::geometry::Direction;
+use crate::solver::common::infinite_grid::InfiniteGrid;
+use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2015,
@@ -9,80 +10,59 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    // Hash sets only store unique values, so keeping track of houses visited in a hash set ensures
-    // each house is counted once no matter how many times it's visited.
-    let mut visited_positions = FxHashSet::default();
-    visited_positions.insert(Point { x: 0, y: 0 });
-    let mut position = Point { x: 0, y: 0 };
+    // Keeping track of houses visited in a grid ensures each house is counted once no matter how
+    // many times it's visited.
+    let mut visited_houses = InfiniteGrid::new(false);
+    let mut position = (0, 0);
+    visited_houses[position] = true;
 
-    for direction in input.chars() {
-        match direction {
-            '^' => {
-                position.y -= 1;
-            }
-            '>' => {
-                position.x += 1;
-            }
-            'v' => {
-                position.y += 1;
-            }
-            '<' => {
-                position.x -= 1;
-            }
-            _ => {
-                panic!("character does not represent a direction");
-            }
-        }
-        visited_positions.insert(position);
+    for direction in input.chars().map(get_direction) {
+        position = visited_houses.offset(position, direction.offset());
+        visited_houses[position] = true;
     }
 
-    Solution::USize(visited_positions.len())
+    Solution::USize(count_visited_houses(&visited_houses))
 }
 
 fn solve_2(input: &str) -> Solution {
-    let mut visited_positions = FxHashSet::default();
-    visited_positions.insert(Point { x: 0, y: 0 });
-    let mut santa_position = Point { x: 0, y: 0 };
-    let mut robo_santa_position = Point { x: 0, y: 0 };
+    let mut visited_houses = InfiniteGrid::new(false);
+    let mut santa_position = (0, 0);
+    let mut robo_santa_position = (0, 0);
+    visited_houses[santa_position] = true;
     let mut is_robo_santa_move = false;
 
-    for direction in input.chars() {
+    for direction in input.chars().map(get_direction) {
         // Get a mutable reference to the position of whichever Santa is moving in this step.
         let position = if is_robo_santa_move {
             &mut robo_santa_position
         } else {
             &mut santa_position
         };
-        match direction {
-            '^' => {
-                position.y -= 1;
-            }
-            '>' => {
-                position.x += 1;
-            }
-            'v' => {
-                position.y += 1;
-            }
-            '<' => {
-                position.x -= 1;
-            }
-            _ => {
-                panic!("character does not represent a direction");
-            }
-        }
-        visited_positions.insert(*position);
+        *position = visited_houses.offset(*position, direction.offset());
+        visited_houses[*position] = true;
 
         // Switch to the other Santa for the next step.
         is_robo_santa_move = !is_robo_santa_move;
     }
 
-    Solution::USize(visited_positions.len())
+    Solution::USize(count_visited_houses(&visited_houses))
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct Point {
-    x: i32,
-    y: i32,
+fn get_direction(character: char) -> Direction {
+    match character {
+        '^' => Direction::North,
+        '>' => Direction::East,
+        'v' => Direction::South,
+        '<' => Direction::West,
+        _ => panic!("character does not represent a direction"),
+    }
+}
+
+fn count_visited_houses(visited_houses: &InfiniteGrid<bool>) -> usize {
+    visited_houses
+        .iter()
+        .filter(|(_, is_visited)| **is_visited)
+        .count()
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
+use crate::solver::common::circuit::{Circuit, Operation, WireId};
 use crate::solver::{AdventOfCode, Solution};
-use rustc_hash::FxHashMap;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2015,
@@ -9,205 +9,67 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let mut wires = FxHashMap::default();
-    let mut gates = get_gates(input);
-
-    while !gates.is_empty() {
-        // We want to run the execute method on every gate in the Vector exactly once, and remove
-        // every gate who returned true (indicating it successfully executed its operation). This is
-        // exactly what the vector's retain() method does (except it removes elements that return
-        // false so the return value must be flipped with !).
-        gates.retain(|gate| !gate.execute(&mut wires));
-    }
-
-    Solution::U16(*wires.get("a").expect("Should have a value for wire \"a\""))
+    let circuit = Circuit::parse(input);
+    Solution::U16(get_signal_a(&circuit, &[]))
 }
 
 fn solve_2(input: &str) -> Solution {
-    let mut wires = FxHashMap::default();
-    let mut gates = get_gates(input);
-
-    // Remove the gate that assigns a value to wire "b"
-    gates.retain(|gate| {
-        if let Gate::Assign(_value_or_identifier, output) = gate {
-            *output != "b"
-        } else {
-            true
-        }
-    });
-    // Get the solution from part 1.
-    let Solution::U16(wire_b_override) = solve_1(input) else {
-        panic!("Part 1 should return a u16")
-    };
-    // Add a new gate that assigns the solution from part 1 to wire "b".
-    gates.push(Gate::Assign(ValueOrIdentifier::Value(wire_b_override), "b"));
-
-    while !gates.is_empty() {
-        gates.retain(|gate| !gate.execute(&mut wires));
-    }
-
-    Solution::U16(*wires.get("a").expect("Should have a value for wire \"a\""))
+    let circuit = Circuit::parse(input);
+    let wire_b = circuit
+        .wire_id("b")
+        .expect("Circuit should have a wire \"b\"");
+
+    // Override wire "b" with the signal that part 1 finds on wire "a", then find the new signal on
+    // wire "a".
+    let wire_b_override = get_signal_a(&circuit, &[]);
+    Solution::U16(get_signal_a(&circuit, &[(wire_b, wire_b_override)]))
 }
 
-// Represents an argument to a gate that can either be a literal value or an identifier for a wire.
-enum ValueOrIdentifier<'a> {
-    Value(u16),
-    Identifier(&'a str),
-}
-impl<'a> ValueOrIdentifier<'a> {
-    // If the input string can be converted to an integer, it is interpreted as a literal value and
-    // stored as an integer. Otherwise, it is interpreted as an identifier and the original input
-    // string is stored as string.
-    fn new(value_or_identifier: &'a str) -> Self {
-        if let Ok(value) = value_or_identifier.parse() {
-            ValueOrIdentifier::Value(value)
-        } else {
-            ValueOrIdentifier::Identifier(value_or_identifier)
-        }
-    }
-    // Attempts to get the value. If unsuccessful (is an Identifier which hasn't yet been given a
-    // value in the wires hash map), returns None.
-    fn get_value<'b>(&'b self, wires: &'b FxHashMap<&str, u16>) -> Option<&'b u16> {
-        match self {
-            ValueOrIdentifier::Value(value) => Some(value),
-            ValueOrIdentifier::Identifier(identifier) => wires.get(identifier),
-        }
-    }
+// Evaluates the circuit with some wires overridden, and returns the signal on wire "a".
+fn get_signal_a(circuit: &Circuit<GateOperation>, overrides: &[(WireId, u16)]) -> u16 {
+    let wire_a = circuit
+        .wire_id("a")
+        .expect("Circuit should have a wire \"a\"");
+    circuit
+        .evaluate(overrides.iter().copied())
+        .unwrap_or_else(|error| panic!("{error}"))[wire_a]
+        .expect("Should have a signal for wire \"a\"")
 }
 
-enum Gate<'a, 'b, 'c> {
-    Assign(ValueOrIdentifier<'a>, &'c str),
-    Not(ValueOrIdentifier<'a>, &'c str),
-    And(ValueOrIdentifier<'a>, ValueOrIdentifier<'b>, &'c str),
-    Or(ValueOrIdentifier<'a>, ValueOrIdentifier<'b>, &'c str),
-    LShift(ValueOrIdentifier<'a>, ValueOrIdentifier<'b>, &'c str),
-    RShift(ValueOrIdentifier<'a>, ValueOrIdentifier<'b>, &'c str),
+enum GateOperation {
+    Assign,
+    Not,
+    And,
+    Or,
+    LShift,
+    RShift,
 }
-impl<'c> Gate<'_, '_, 'c> {
-    // Attempts to execute the operation of the gate on its arguments. If at least one argument is
-    // an identifier that doesn't yet have a value in the wires hash map, the execution fails and
-    // this method returns false. Otherwise, the operation is carried out, the result is stored in
-    // the wires hash map under the name of the output identifier, and this method returns true.
-    fn execute(&self, wires: &mut FxHashMap<&'c str, u16>) -> bool {
-        match self {
-            Gate::Assign(arg, output) => {
-                let arg = arg.get_value(wires);
-                match arg {
-                    Some(value) => {
-                        wires.insert(output, *value);
-                        true
-                    }
-                    None => false,
-                }
-            }
-            Gate::Not(arg, output) => {
-                let arg = arg.get_value(wires);
-                match arg {
-                    Some(value) => {
-                        wires.insert(output, !*value);
-                        true
-                    }
-                    None => false,
-                }
-            }
-            Gate::And(arg_1, arg_2, output) => {
-                let arg_1 = arg_1.get_value(wires);
-                let arg_2 = arg_2.get_value(wires);
-                match (arg_1, arg_2) {
-                    (Some(value1), Some(value2)) => {
-                        wires.insert(output, *value1 & *value2);
-                        true
-                    }
-                    _ => false,
-                }
-            }
-            Gate::Or(arg_1, arg_2, output) => {
-                let arg_1 = arg_1.get_value(wires);
-                let arg_2 = arg_2.get_value(wires);
-                match (arg_1, arg_2) {
-                    (Some(value1), Some(value2)) => {
-                        wires.insert(output, *value1 | *value2);
-                        true
-                    }
-                    _ => false,
-                }
-            }
-            Gate::LShift(arg_1, arg_2, output) => {
-                let arg_1 = arg_1.get_value(wires);
-                let arg_2 = arg_2.get_value(wires);
-                match (arg_1, arg_2) {
-                    (Some(value1), Some(value2)) => {
-                        wires.insert(output, *value1 << *value2);
-                        true
-                    }
-                    _ => false,
-                }
-            }
-            Gate::RShift(arg_1, arg_2, output) => {
-                let arg_1 = arg_1.get_value(wires);
-                let arg_2 = arg_2.get_value(wires);
-                match (arg_1, arg_2) {
-                    (Some(value1), Some(value2)) => {
-                        wires.insert(output, *value1 >> *value2);
-                        true
-                    }
-                    _ => false,
-                }
-            }
+
+impl Operation for GateOperation {
+    type Value = u16;
+
+    fn from_keyword(keyword: Option<&str>) -> Option<Self> {
+        match keyword {
+            None => Some(GateOperation::Assign),
+            Some("NOT") => Some(GateOperation::Not),
+            Some("AND") => Some(GateOperation::And),
+            Some("OR") => Some(GateOperation::Or),
+            Some("LSHIFT") => Some(GateOperation::LShift),
+            Some("RSHIFT") => Some(GateOperation::RShift),
+            Some(_) => None,
         }
     }
-}
-
-fn get_gates(input: &str) -> Vec<Gate<'_, '_, '_>> {
-    let mut gates = Vec::new();
 
-    for line in input.lines() {
-        let mut word_iter = line.split(' ');
-        let first_word = word_iter
-            .next()
-            .expect("All lines should have at least one word");
-
-        if first_word == "NOT" {
-            let first_argument = word_iter.next().expect("NOT gate should have argument");
-            let first_argument = ValueOrIdentifier::new(first_argument);
-            // Ignore the "->"
-            word_iter.next();
-            let output = word_iter
-                .next()
-                .expect("NOT gate should have output identifier");
-            gates.push(Gate::Not(first_argument, output));
-        } else {
-            let first_argument = ValueOrIdentifier::new(first_word);
-
-            let gate = word_iter
-                .next()
-                .expect("Line should have at least two words");
-            if gate == "->" {
-                let output = word_iter
-                    .next()
-                    .expect("ASSIGN gate should have output identifier");
-                gates.push(Gate::Assign(first_argument, output));
-            } else {
-                let second_argument = word_iter.next().expect("Gate should have second argument");
-                let second_argument = ValueOrIdentifier::new(second_argument);
-                // Ignore the "->"
-                word_iter.next();
-                let output = word_iter
-                    .next()
-                    .expect("Gate should have output identifier");
-
-                match gate {
-                    "AND" => gates.push(Gate::And(first_argument, second_argument, output)),
-                    "OR" => gates.push(Gate::Or(first_argument, second_argument, output)),
-                    "LSHIFT" => gates.push(Gate::LShift(first_argument, second_argument, output)),
-                    "RSHIFT" => gates.push(Gate::RShift(first_argument, second_argument, output)),
-                    _ => panic!("Invalid gate"),
-                }
-            }
+    fn evaluate(&self, inputs: &[u16]) -> u16 {
+        match self {
+            GateOperation::Assign => inputs[0],
+            GateOperation::Not => !inputs[0],
+            GateOperation::And => inputs[0] & inputs[1],
+            GateOperation::Or => inputs[0] | inputs[1],
+            GateOperation::LShift => inputs[0] << inputs[1],
+            GateOperation::RShift => inputs[0] >> inputs[1],
         }
     }
-
-    gates
 }
 
 #[cfg(test)]
@@ -1,4 +1,5 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::math::{divisor_sums, divisor_sums_bounded};
+use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2015,
@@ -16,32 +17,16 @@ fn solve_1(input: &str) -> Solution {
     // target number of presents is guaranteed to be before the target number divided by 10.
     let house_limit = target / 10;
 
-    // Create an array that will store a running total of the number of presents delivered to each
-    // house (i.e. num_presents_at_house[648] stores the number of presents at house 648). All
-    // houses have no presents at the beginning so initialize each value to 0. This needs to use a
-    // Box to allocate on the heap, partly because the exact size isn't known at compile time and
-    // partly because millions of ints would overflow the stack.
-    let mut num_presents_at_house = vec![0; house_limit].into_boxed_slice();
+    // Each elf delivers presents to every house whose number is a multiple of the elf's number, so
+    // the elves that visit a house are exactly the divisors of its number. The number of presents
+    // delivered to each house is therefore 10 times the sum of the divisors of its number.
+    let num_presents_at_house = divisor_sums(house_limit)
+        .into_iter()
+        .map(|divisor_sum| divisor_sum * 10);
 
-    // Iterate over each elf's journey until the house limit is reached (elves with a greater number
-    // will never visit a house whose number is below the house limit).
-    for elf_number in 1..house_limit {
-        // Each elf starts at the house with its number.
-        let mut house_number = elf_number;
-        // Technically each elf delivers presents forever, but we can stop at the house limit
-        // because the puzzle solution is guaranteed to be reached before this house.
-        while house_number < house_limit {
-            // Each elf delivers presents equal to 10 times its number to the house it visits.
-            num_presents_at_house[house_number] += elf_number * 10;
-            // Then the elf moves to the next house that is a multiple of their number.
-            house_number += elf_number;
-        }
-    }
-
-    // All presents have now been delivered to houses under the house limit. Find the first house
-    // that got the target number of presents.
-    for (house_number, presents) in num_presents_at_house.iter().enumerate() {
-        if *presents >= target {
+    // Find the first house that got the target number of presents.
+    for (house_number, presents) in num_presents_at_house.enumerate() {
+        if presents >= target {
             return Solution::USize(house_number);
         }
     }
@@ -52,22 +37,14 @@ fn solve_2(input: &str) -> Solution {
     let target = input.parse::<usize>().expect("Input should be a number");
 
     let house_limit = target / 11;
-    let mut num_presents_at_house = vec![0; house_limit].into_boxed_slice();
-
-    for elf_number in 1..house_limit {
-        let mut house_number = elf_number;
-        let mut houses_visited: u8 = 1;
-        // Elves no longer deliver presents infinitely, so stop after they deliver their 50th
-        // present (or after they reach the house limit).
-        while house_number < house_limit && houses_visited <= 50 {
-            num_presents_at_house[house_number] += elf_number * 11;
-            house_number += elf_number;
-            houses_visited += 1;
-        }
-    }
-
-    for (house_number, presents) in num_presents_at_house.iter().enumerate() {
-        if *presents >= target {
+    // Elves no longer deliver presents infinitely, so each elf only counts as a divisor for the
+    // first 50 houses it visits.
+    let num_presents_at_house = divisor_sums_bounded(house_limit, 50)
+        .into_iter()
+        .map(|divisor_sum| divisor_sum * 11);
+
+    for (house_number, presents) in num_presents_at_house.enumerate() {
+        if presents >= target {
             return Solution::USize(house_number);
         }
     }
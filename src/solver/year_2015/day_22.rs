@@ -1,4 +1,5 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::parse::Pattern;
+use crate::solver::{AdventOfCode, Solution};
 use std::cmp::{max, min};
 use std::collections::VecDeque;
 use std::mem::discriminant;
@@ -303,15 +304,10 @@ fn get_boss(input: &str) -> Boss {
         hit_points: 0,
         damage: 0,
     };
-    for line in input.lines() {
-        let mut colon_iter = line.split(": ");
-        let stat_name = colon_iter.next().expect("Line should have a named stat");
-        let stat_value = colon_iter
-            .next()
-            .expect("Line should have a stat value")
-            .parse()
-            .expect("Stat value should be a number");
-        match stat_name {
+    for stat in Pattern::new("{}: {}").captures_lines(input) {
+        let stat = stat.expect("Line should have a named stat and a value");
+        let stat_value = stat.parse(1).expect("Stat value should be a number");
+        match stat.get(0) {
             "Hit Points" => boss.hit_points = stat_value,
             "Damage" => boss.damage = stat_value,
             _ => panic!("Stat should be one of \"Hit Points\" or \"Damage\""),
@@ -1,4 +1,10 @@
+use crate::solver::common::vm::trace::Tracer;
+use crate::solver::common::vm::{
+    Control, InstructionSet, Machine, State, letter_name, letter_register,
+};
 use crate::solver::{AdventOfCode, Solution};
+use std::fmt::{self, Display, Formatter};
+use std::io;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2015,
@@ -15,82 +21,22 @@ fn solve_2(input: &str) -> Solution {
     solve(input, [1, 0])
 }
 
-// registers[0] is a, registers[1] is b.
-fn solve(input: &str, mut registers: [u32; 2]) -> Solution {
-    // Get all instructions.
-    let mut instructions = Vec::new();
-    for line in input.lines() {
-        let mut instruction_iter = line.split(' ');
-        let instruction_str = instruction_iter
-            .next()
-            .expect("Line should have instruction code");
-
-        match instruction_str {
-            "hlf" => instructions.push(Instruction::Hlf(get_register_arg(instruction_iter))),
-            "tpl" => instructions.push(Instruction::Tpl(get_register_arg(instruction_iter))),
-            "inc" => instructions.push(Instruction::Inc(get_register_arg(instruction_iter))),
-            "jmp" => instructions.push(Instruction::Jmp(get_offset_arg(instruction_iter))),
-            "jie" => {
-                let (register, offset) = get_register_and_offset_args(instruction_iter);
-                instructions.push(Instruction::Jie(register, offset));
-            }
-            "jio" => {
-                let (register, offset) = get_register_and_offset_args(instruction_iter);
-                instructions.push(Instruction::Jio(register, offset));
-            }
-            _ => panic!("Unsupported instruction"),
-        }
-    }
-
-    let mut program_counter = 0;
-
-    // Execute instructions until the program counter reaches an invalid index.
-    while let Some(instruction) = instructions.get(program_counter) {
-        instruction.execute(&mut program_counter, &mut registers);
-    }
-
-    Solution::U32(registers[1])
-}
-
-fn get_register_arg<'a>(mut instruction_iter: impl Iterator<Item = &'a str>) -> usize {
-    let register_str = instruction_iter
-        .next()
-        .expect("Instruction should have an argument");
-    match register_str {
-        "a" => 0,
-        "b" => 1,
-        _ => panic!("Unsupported register"),
-    }
-}
-
-fn get_offset_arg<'a>(mut instruction_iter: impl Iterator<Item = &'a str>) -> isize {
-    instruction_iter
-        .next()
-        .expect("Instruction should have an argument")
-        .parse()
-        .expect("Offset should be a number")
+fn solve(input: &str, registers: [u32; 2]) -> Solution {
+    let mut machine = Machine::new(TuringLock, get_instructions(input), registers);
+    machine.run();
+    Solution::U32(machine.state.registers[1])
 }
 
-fn get_register_and_offset_args<'a>(
-    mut instruction_iter: impl Iterator<Item = &'a str>,
-) -> (usize, isize) {
-    let register_str = instruction_iter
-        .next()
-        .expect("Instruction should have an argument");
-    let register = match register_str.trim_end_matches(',') {
-        "a" => 0,
-        "b" => 1,
-        _ => panic!("Unsupported register"),
-    };
-    let offset = instruction_iter
-        .next()
-        .expect("Instruction should have an argument")
-        .parse()
-        .expect("Offset should be a number");
-    (register, offset)
+pub fn trace(input: &str, part: u8, tracer: &mut Tracer) -> io::Result<()> {
+    let registers = if part == 2 { [1, 0] } else { [0, 0] };
+    let mut machine = Machine::new(TuringLock, get_instructions(input), registers);
+    tracer.run(&mut machine, |name| {
+        ["a", "b"].iter().position(|register| *register == name)
+    })?;
+    tracer.finish(&machine)
 }
 
-// usize refers to register indexes, isize refers to jump instruction offsets.
+// Register indexes, where 0 is a and 1 is b, and offsets for jump instructions.
 enum Instruction {
     Hlf(usize),
     Tpl(usize),
@@ -99,45 +45,80 @@ enum Instruction {
     Jie(usize, isize),
     Jio(usize, isize),
 }
-impl Instruction {
-    fn execute(&self, program_counter: &mut usize, registers: &mut [u32; 2]) {
-        match self {
-            Instruction::Hlf(register_index) => {
-                registers[*register_index] /= 2;
-                *program_counter += 1;
-            }
-            Instruction::Tpl(register_index) => {
-                registers[*register_index] *= 3;
-                *program_counter += 1;
-            }
-            Instruction::Inc(register_index) => {
-                registers[*register_index] += 1;
-                *program_counter += 1;
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Instruction::Hlf(register) => write!(f, "hlf {}", letter_name(register)),
+            Instruction::Tpl(register) => write!(f, "tpl {}", letter_name(register)),
+            Instruction::Inc(register) => write!(f, "inc {}", letter_name(register)),
+            Instruction::Jmp(offset) => write!(f, "jmp {offset:+}"),
+            Instruction::Jie(register, offset) => {
+                write!(f, "jie {}, {offset:+}", letter_name(register))
             }
-            Instruction::Jmp(offset) => {
-                // If the program counter would go below 0, it would wrap around to a ludicrously
-                // high index that should definitely be out-of-bounds (the length of the program in
-                // my puzzle input is orders of magnitude less than usize::MAX).
-                *program_counter = program_counter.wrapping_add_signed(*offset);
+            Instruction::Jio(register, offset) => {
+                write!(f, "jio {}, {offset:+}", letter_name(register))
             }
-            Instruction::Jie(register_index, offset) => {
-                if registers[*register_index].is_multiple_of(2) {
-                    *program_counter = program_counter.wrapping_add_signed(*offset);
-                } else {
-                    *program_counter += 1;
+        }
+    }
+}
+
+struct TuringLock;
+
+impl InstructionSet for TuringLock {
+    type Instruction = Instruction;
+    type Registers = [u32; 2];
+
+    fn execute(&mut self, instruction: &Instruction, state: &mut State<[u32; 2]>) -> Control {
+        let registers = &mut state.registers;
+        match *instruction {
+            Instruction::Hlf(register) => registers[register] /= 2,
+            Instruction::Tpl(register) => registers[register] *= 3,
+            Instruction::Inc(register) => registers[register] += 1,
+            Instruction::Jmp(offset) => return Control::Jump(offset),
+            Instruction::Jie(register, offset) => {
+                if registers[register].is_multiple_of(2) {
+                    return Control::Jump(offset);
                 }
             }
-            Instruction::Jio(register_index, offset) => {
-                if registers[*register_index] == 1 {
-                    *program_counter = program_counter.wrapping_add_signed(*offset);
-                } else {
-                    *program_counter += 1;
+            Instruction::Jio(register, offset) => {
+                if registers[register] == 1 {
+                    return Control::Jump(offset);
                 }
             }
         }
+        Control::Next
     }
 }
 
+fn get_instructions(input: &str) -> Vec<Instruction> {
+    input
+        .lines()
+        .map(|line| {
+            let (name, operands) = line
+                .split_once(' ')
+                .expect("Line should have instruction code and arguments");
+            let mut operands = operands.split(", ");
+            let mut operand = || {
+                operands
+                    .next()
+                    .expect("Instruction should have an argument")
+            };
+            let offset = |operand: &str| operand.parse().expect("Offset should be a number");
+
+            match name {
+                "hlf" => Instruction::Hlf(letter_register(operand())),
+                "tpl" => Instruction::Tpl(letter_register(operand())),
+                "inc" => Instruction::Inc(letter_register(operand())),
+                "jmp" => Instruction::Jmp(offset(operand())),
+                "jie" => Instruction::Jie(letter_register(operand()), offset(operand())),
+                "jio" => Instruction::Jio(letter_register(operand()), offset(operand())),
+                _ => panic!("Unsupported instruction"),
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
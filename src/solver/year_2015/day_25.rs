@@ -1,3 +1,4 @@
+use crate::solver::common::math::{mod_mul, mod_pow};
 use crate::solver::{Solution, AdventOfCode};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -25,22 +26,20 @@ fn solve_1(input: &str) -> Solution {
         .parse::<u32>()
         .expect("Input's second value should be a number after trimming");
 
-    let mut code = 20_151_125;
-    let mut row = 1;
-    let mut column = 1;
-
-    // Keep generating new codes until the row and column numbers match the target row and column.
-    while !(row == target_row && column == target_column) {
-        code = (code * 252_533) % 33_554_393;
-
-        if row == 1 {
-            row = column + 1;
-            column = 1;
-        } else {
-            row -= 1;
-            column += 1;
-        }
-    }
+    // Codes are filled in along diagonals, where the diagonal containing a cell is numbered
+    // row + column - 1 and is filled from the bottom-left up to the top-right. Every earlier
+    // diagonal is completely filled, and the nth diagonal has n cells, so the number of codes
+    // generated before reaching the target cell is a triangular number plus its column.
+    let diagonal = u64::from(target_row + target_column - 1);
+    let previous_codes = diagonal * (diagonal - 1) / 2 + u64::from(target_column) - 1;
+
+    // Each code is the previous code multiplied by 252533 modulo 33554393, so the target code is
+    // the first code multiplied by 252533 raised to the number of previous codes.
+    let code = mod_mul(
+        20_151_125,
+        mod_pow(252_533, previous_codes, 33_554_393),
+        33_554_393,
+    );
 
     Solution::U64(code)
 }
@@ -1,3 +1,4 @@
+use crate::solver::common::hash::md5::{Digest, PrefixHasher};
 use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -8,67 +9,26 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let mut index = 0;
-    let mut password = Vec::with_capacity(8);
-    loop {
-        let mut hash_input = input.to_string();
-        hash_input.push_str(&index.to_string());
-        let hash = super::super::year_2015::day_04::md5(&hash_input);
-        // Checking if the first 5 hexadecimal digits are 0 is equivalent to checking if the first
-        // 20 binary digits are 0. This can be easily tested by right-shifting by 108 (128 - 20) to
-        // truncate all but the upper 20 bits, and testing if the result is equal to 0.
-        if hash >> 108 == 0 {
-            let hash_hexadecimal = format!("{hash:0>32x}");
-            password.push(
-                hash_hexadecimal
-                    .chars()
-                    .nth(5)
-                    .expect("Hashes should have at least 6 characters"),
-            );
-
-            if password.len() == 8 {
-                break;
-            }
-        }
-
-        index += 1;
-    }
-
-    Solution::String(password.iter().collect::<String>())
+    let password = find_interesting_hashes(input)
+        .take(8)
+        .map(|hash| hexadecimal_digit(hash.nibble(5)))
+        .collect();
+    Solution::String(password)
 }
 
 fn solve_2(input: &str) -> Solution {
-    let mut index = 0;
     let mut password = [None; 8];
-    loop {
-        let mut hash_input = input.to_string();
-        hash_input.push_str(&index.to_string());
-        let hash = super::super::year_2015::day_04::md5(&hash_input);
-        if hash >> 108 == 0 {
-            let hash_hexadecimal = format!("{hash:0>32x}");
-            let mut hash_hexadecimal_iter = hash_hexadecimal.chars();
-            let password_index = hash_hexadecimal_iter
-                .nth(5)
-                .expect("Hashes should have at least 6 characters")
-                .to_digit(16)
-                .expect("The sixth character should be a valid hexadecimal digit")
-                as usize;
-            // Check if the index fits in the password and a character for that index hasn't already
-            // been found.
-            if password_index < 8 && password[password_index].is_none() {
-                password[password_index] = Some(
-                    hash_hexadecimal_iter
-                        .next()
-                        .expect("Hashes should have at least 7 characters"),
-                );
-                // Stop looping when all password positions are filled.
-                if password.iter().all(Option::is_some) {
-                    break;
-                }
+    for hash in find_interesting_hashes(input) {
+        let password_index = usize::from(hash.nibble(5));
+        // Check if the index fits in the password and a character for that index hasn't already
+        // been found.
+        if password_index < 8 && password[password_index].is_none() {
+            password[password_index] = Some(hexadecimal_digit(hash.nibble(6)));
+            // Stop looking when all password positions are filled.
+            if password.iter().all(Option::is_some) {
+                break;
             }
         }
-
-        index += 1;
     }
 
     Solution::String(
@@ -81,6 +41,27 @@ fn solve_2(input: &str) -> Solution {
     )
 }
 
+// Returns the hashes of the door ID followed by an increasing index that start with five
+// hexadecimal zeroes, in order of index. Indexes are hashed in parallel, a batch at a time.
+fn find_interesting_hashes(door_id: &str) -> impl Iterator<Item = Digest> {
+    const BATCH_SIZE: u64 = 1 << 16;
+
+    let hasher = PrefixHasher::new(door_id.as_bytes());
+    (0..).flat_map(move |batch| {
+        let start = batch * BATCH_SIZE;
+        hasher
+            .filter_range(start..start + BATCH_SIZE, |hash| {
+                hash.leading_zero_nibbles() >= 5
+            })
+            .into_iter()
+            .map(|(_, hash)| hash)
+    })
+}
+
+fn hexadecimal_digit(nibble: u8) -> char {
+    char::from_digit(u32::from(nibble), 16).expect("Nibble should be a hexadecimal digit")
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
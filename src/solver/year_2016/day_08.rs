@@ -1,12 +1,14 @@
 use std::ops::IndexMut;
 
+use crate::solver::common::grid::Grid;
+use crate::solver::common::ocr;
 use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2016,
     day: 8,
     title: "Two-Factor Authentication",
-    part_solvers: &[solve_1],
+    part_solvers: &[solve_1, solve_2],
 };
 
 fn solve_1(input: &str) -> Solution {
@@ -16,6 +18,14 @@ fn solve_1(input: &str) -> Solution {
     Solution::U32(lit_pixels)
 }
 
+fn solve_2(input: &str) -> Solution {
+    let screen = generate_screen::<50, 6>(input);
+    let grid = Grid::from_cells(50, screen.concat());
+    let code = ocr::recognise(&grid).unwrap_or_else(|error| panic!("{error}"));
+
+    Solution::String(code)
+}
+
 fn generate_screen<const X: usize, const Y: usize>(input: &str) -> [[bool; X]; Y] {
     let mut screen = [[false; X]; Y];
 
@@ -128,4 +138,21 @@ rotate column x=1 by 1",
         let lit_pixels = count_lit_pixels(&screen);
         assert_eq!(lit_pixels, 6);
     }
+
+    #[test]
+    fn read_screen() {
+        // Draws an L by lighting the top row, moving it to the bottom, and then lighting the left
+        // column.
+        let screen = generate_screen::<50, 6>(
+            "\
+rect 4x1
+rotate column x=0 by 5
+rotate column x=1 by 5
+rotate column x=2 by 5
+rotate column x=3 by 5
+rect 1x5",
+        );
+        let grid = Grid::from_cells(50, screen.concat());
+        assert_eq!(ocr::recognise(&grid), Ok("L".to_string()));
+    }
 }
@@ -1,4 +1,6 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::geometry::Direction;
+use crate::solver::common::infinite_grid::InfiniteGrid;
+use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2017,
@@ -69,101 +71,28 @@ fn solve_1(input: &str) -> Solution {
 fn solve_2(input: &str) -> Solution {
     let input = input.parse::<u32>().expect("Error parsing number");
 
-    // Unlike last time, storing the previous numbers seems necessary. However, there are still some
-    // observations that can make it possible to use merely a vector of the spiral numbers in order
-    // for this task (as opposed to a 2D data structure).
-    //  - There are four distinct cases: When on a corner of a loop, immediately before the corner
-    //      of a loop, immediately after the corner of a loop, or anywhere else along the edge of a
-    //      loop. (The first and second 1s in the data don't fall neatly into any of these four
-    //      cases and technically form a fifth and sixth case (zero adjacent numbers and one
-    //      adjacent number). Because no other numbers share these cases, it is simpler to just skip
-    //      these cases by hardcoding the first and second numbers.)
-    //  - If the index of the orthogonally-adjacent number in the inner loop is tracked, then it is
-    //      possible to determine the index of every adjacent number in each of the four cases, and
-    //      whether or not the index should be incremented to remain in sync, by following these
-    //      rules:
-    //       - On a regular edge (not a corner or one off from a corner): The adjacent squares are
-    //           the immediately preceding value, the value at the index, the value immediately
-    //           before that index, and the value immediately after that index. Increment the index
-    //           after this step.
-    //       - Immediately before a corner: The adjacent squares are the immediately preceding
-    //           value, the value at the index, and the value immediately before the index. Do not
-    //           increment the index after this step.
-    //       - On a corner: The adjacent squares are the immediately preceding value and the value
-    //           at the index (which is diagonally adjacent instead of orthogonally adjacent because
-    //           the index wasn't incremented last step). Do not increment the index after this
-    //           step.
-    //       - Immediately after a corner: The adjacent squares are the two preceding values, the
-    //           value at the index (which is again aligned orthogonally since the index wasn't
-    //           incremented after the last two steps), and the value immediately after that index.
-    //           Increment the index after this step.
-    //  - The length of an edge increases by 1 after every second edge. This is because each loop
-    //      lasts four edges and the edge length increases by two after each two, however the way
-    //      one loop leads into the next causes the last edge of the inner loop to be one step
-    //      longer than normal and the first edge of the outer loop to be one step shorter than
-    //      normal.
-
-    let mut spiral: Vec<u32> = vec![1, 1];
-    let mut distance_between_corners: u32 = 1;
-    let mut is_first_corner_of_current_distance = false;
-    let mut distance_to_next_corner: u32 = 1;
-    let mut inner_loop_index = 0;
+    // Build the spiral in a grid, starting with 1 at (0, 0) and initially stepping right. Each new
+    // square's value is the sum of all adjacent squares, where squares that haven't been reached yet
+    // are 0. After each step, the spiral turns left if the square to the left hasn't been reached
+    // yet, which keeps it wrapped tightly around the squares already placed.
+    let mut spiral = InfiniteGrid::new(0);
+    let mut position = (0, 0);
+    let mut direction = Direction::East;
+    spiral[position] = 1;
 
     loop {
-        if distance_to_next_corner == 0 {
-            // If at a corner, reset the distance to the next corner. If this isn't the first corner
-            // with the current distance, also increase the distance between corners.
-            if is_first_corner_of_current_distance {
-                is_first_corner_of_current_distance = false;
-            } else {
-                distance_between_corners += 1;
-                is_first_corner_of_current_distance = true;
-            }
-            distance_to_next_corner = distance_between_corners - 1;
-        } else {
-            // Otherwise (not at a corner), reduce the distance to the next corner.
-            distance_to_next_corner -= 1;
-        }
-
-        // The number immediately preceding the current number and the value at the index are always
-        // adjacent in the spiral, so they can be added first.
-        let mut next_number = spiral[spiral.len() - 1] + spiral[inner_loop_index];
-
-        match distance_to_next_corner {
-            0 => {
-                // Exactly on corner.
-                // (do nothing, the previous value and index value are the only adjacent values and
-                // the index should not be incremented)
-            }
-            1 => {
-                // Immediately before corner.
-                // (add value before the index value; at very start, instead add value before the
-                // preceding value due to geometry of spiral wrapping around on itself. Don't
-                // increment index either way)
-                if distance_between_corners <= 2 {
-                    next_number += spiral[spiral.len() - 2];
-                } else {
-                    next_number += spiral[inner_loop_index - 1];
-                }
-            }
-            x if x == distance_between_corners - 1 => {
-                // Immediately after corner.
-                // (add value before the preceding value and increment index)
-                next_number += spiral[spiral.len() - 2] + spiral[inner_loop_index + 1];
-                inner_loop_index += 1;
-            }
-            _ => {
-                // Elsewhere on edge.
-                // (add values before and after the index value and increment index)
-                next_number += spiral[inner_loop_index - 1] + spiral[inner_loop_index + 1];
-                inner_loop_index += 1;
-            }
+        position = spiral.offset(position, direction.offset());
+        let value = spiral
+            .neighbours_8(position)
+            .map(|neighbour| spiral[neighbour])
+            .sum();
+        if value > input {
+            return Solution::U32(value);
         }
+        spiral[position] = value;
 
-        spiral.push(next_number);
-
-        if spiral[spiral.len() - 1] > input {
-            return Solution::U32(spiral[spiral.len() - 1]);
+        if spiral[spiral.offset(position, direction.left().offset())] == 0 {
+            direction.turn_left();
         }
     }
 }
@@ -187,4 +116,13 @@ mod test {
     fn example1_3() {
         assert_eq!(solve_1("1024"), Solution::U8(31));
     }
+
+    #[test]
+    fn example2_1() {
+        assert_eq!(solve_2("5"), Solution::U8(10));
+    }
+    #[test]
+    fn example2_2() {
+        assert_eq!(solve_2("747"), Solution::U16(806));
+    }
 }
@@ -1,4 +1,5 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::cycle::find_cycle;
+use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2017,
@@ -8,59 +9,52 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    solve(input, false)
+    // The number of redistribution cycles before a configuration is seen twice is the number of
+    // steps before the loop starts, plus the steps taken to go all the way around the loop once.
+    let loop_info = find_cycle(|banks: &Vec<u32>| redistribute(banks), get_banks(input));
+    Solution::USize(loop_info.tail + loop_info.length)
 }
 
 fn solve_2(input: &str) -> Solution {
-    solve(input, true)
+    let loop_info = find_cycle(|banks: &Vec<u32>| redistribute(banks), get_banks(input));
+    Solution::USize(loop_info.length)
 }
 
-fn solve(input: &str, get_loop_size: bool) -> Solution {
-    // Use the input to initialize the first bank configuration and bank history.
-    let mut banks: Box<[u32]> = input
+fn get_banks(input: &str) -> Vec<u32> {
+    input
         .split_whitespace()
         .map(|s| s.parse::<u32>().expect("Error parsing number"))
-        .collect();
-    let num_banks = banks.len();
-    // banks must be cloned so the real banks array can continue to be mutated without changing
-    // history.
-    let mut banks_history = vec![banks.clone()];
-
-    loop {
-        // Find the max value and the index of this value (note: cannot use a iterator with
-        // position_max() as when there are multiple elements with the max value, this returns the
-        // index of the last max element but the index of the first max element is needed.)
-        let mut max_bank = u32::MIN;
-        let mut max_bank_index = 0;
-        for (index, bank) in banks.iter().enumerate() {
-            if *bank > max_bank {
-                max_bank = *bank;
-                max_bank_index = index;
-            }
-        }
+        .collect()
+}
 
-        // Distribute blocks.
-        banks[max_bank_index] = 0;
-        while max_bank > 0 {
-            max_bank -= 1;
-            // modulo operator ensures index wraps around to 0 as needed.
-            max_bank_index = (max_bank_index + 1) % num_banks;
+// Perform one redistribution cycle, returning the new bank configuration.
+fn redistribute(banks: &[u32]) -> Vec<u32> {
+    let mut banks = banks.to_vec();
+    let num_banks = banks.len();
 
-            banks[max_bank_index] += 1;
+    // Find the max value and the index of this value (note: cannot use a iterator with
+    // position_max() as when there are multiple elements with the max value, this returns the index
+    // of the last max element but the index of the first max element is needed.)
+    let mut max_bank = u32::MIN;
+    let mut max_bank_index = 0;
+    for (index, bank) in banks.iter().enumerate() {
+        if *bank > max_bank {
+            max_bank = *bank;
+            max_bank_index = index;
         }
+    }
 
-        // If a position is found, then a match was found between the current banks and the history.
-        if let Some(loop_start_index) = banks_history.iter().position(|x| *x == banks) {
-            // The length of the bank history indicates how many steps have been taken. For part 2,
-            // subtract the index of the start of the loop to get the number of steps in the loop.
-            if get_loop_size {
-                return Solution::USize(banks_history.len() - loop_start_index);
-            }
-            return Solution::USize(banks_history.len());
-        }
+    // Distribute blocks.
+    banks[max_bank_index] = 0;
+    while max_bank > 0 {
+        max_bank -= 1;
+        // modulo operator ensures index wraps around to 0 as needed.
+        max_bank_index = (max_bank_index + 1) % num_banks;
 
-        banks_history.push(banks.clone());
+        banks[max_bank_index] += 1;
     }
+
+    banks
 }
 
 #[cfg(test)]
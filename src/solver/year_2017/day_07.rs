@@ -1,3 +1,4 @@
+use crate::solver::common::dot::DotGraph;
 use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -41,139 +42,40 @@ fn solve_1(input: &str) -> Solution {
 }
 
 fn solve_2(input: &str) -> Solution {
-    struct Disc {
-        weight: usize,            // Weight of just this disc.
-        cumulative_weight: usize, // Weight of this disc plus the weights of all sub-towers on it.
-        sub_towers: Vec<Disc>,
-    }
-
-    impl Disc {
-        pub fn new(input: &str, program: &str) -> Disc {
-            // The logic within building the disc and populating the cumulative weights must be
-            // moved into separate functions so they can be called recursively.
-            let mut disc = Disc::build_disc(input, program);
-            disc.populate_cumulative_weights();
-            disc
-        }
-
-        fn build_disc(input: &str, program: &str) -> Disc {
-            // Search the input for the line starting with the given program name.
-            for line in input.lines() {
-                let mut iter = line.split_whitespace();
-                if iter.next() == Some(program) {
-                    let weight = iter
-                        .next()
-                        .expect("Error reading second value of line")
-                        .trim_matches(|c| c == '(' || c == ')')
-                        .parse()
-                        .expect("Error reading number");
-
-                    // Consume the "->".
-                    iter.next();
-
-                    let sub_towers = iter
-                        .map(|program| Disc::build_disc(input, program.trim_end_matches(',')))
-                        .collect();
-
-                    return Disc {
-                        weight,
-                        cumulative_weight: 0,
-                        sub_towers,
-                    };
-                }
-            }
-
-            // If no value is returned from the above loop, then the program name wasn't found in
-            // any of the lines of the input. This can only happen if the input is malformed or this
-            // solver has a logic error. Either way, no valid Disc can be returned, so panic.
-            panic!("No matching program found");
-        }
-
-        fn populate_cumulative_weights(&mut self) -> usize {
-            // Get the weight of this disc.
-            let mut cumulative_weight = self.weight;
-
-            // Add the weights of all sub-towers on this disc.
-            for sub_tower in &mut self.sub_towers {
-                cumulative_weight += sub_tower.populate_cumulative_weights();
-            }
+    let (_, correct_weight) = find_wrong_weight(input);
+    Solution::USize(correct_weight)
+}
 
-            self.cumulative_weight = cumulative_weight;
-            cumulative_weight
+// Draws the tower with an edge from each program to each program directly above it, labelled with
+// the cumulative weight of the sub-tower above. Each program is labelled with its own weight, and
+// the program with the wrong weight that part 2 finds is highlighted.
+pub fn export_graph(input: &str) -> DotGraph {
+    fn add_disc(graph: &mut DotGraph, disc: &Disc) {
+        graph.add_node(
+            disc.program,
+            Some(format!("{} ({})", disc.program, disc.weight)),
+        );
+        for sub_tower in &disc.sub_towers {
+            graph.add_edge(
+                disc.program,
+                sub_tower.program,
+                Some(sub_tower.cumulative_weight.to_string()),
+            );
+            add_disc(graph, sub_tower);
         }
     }
 
-    fn fix_wrong_weight(correct_cumulative_weight: usize, disc: Disc) -> usize {
-        // We are only concerned with sub-towers that can have the incorrect value, so iterate over
-        // all sub-towers to find the one with the wrong value.
-        for sub_tower in disc.sub_towers {
-            if sub_tower.cumulative_weight != correct_cumulative_weight
-                && !sub_tower.sub_towers.is_empty()
-            {
-                // Check if all sub-towers have equal cumulative weights. If so, the current disc
-                // must be the incorrect disc (as there is exactly one incorrect disc, none of the
-                // sub-towers can be incorrect, and this function is only called on discs with
-                // incorrect cumulative weights). It's assumed that no disk has exactly one
-                // sub-tower, as then the puzzle would have an ambiguous answer as it would be
-                // impossible to tell whether the incorrect value lies with that disc or its single
-                // sub-tower. Unlike with the very bottom disc, tracking the correct cumulative
-                // weight makes it possible to identify which sub-tower has the wrong weight when
-                // there's exactly two of them.
-                let first_sub_tower_cumulative_weight = sub_tower.sub_towers[0].cumulative_weight;
-                let are_sub_towers_equal = sub_tower.sub_towers.iter().all(|sub_tower| {
-                    sub_tower.cumulative_weight == first_sub_tower_cumulative_weight
-                });
-
-                if are_sub_towers_equal {
-                    // This sub-tower has been identified as the disc with the incorrect weight, so
-                    // calculate what its weight should be.
-                    //
-                    // Let the weight be w, cumulative weight be c, correct weight be wc, correct
-                    // cumulative weight be cc, and the sum of weights of all sub-towers be s. The
-                    // cumulative weight is obtained by adding the disc's weight to the weight of
-                    // all sub-towers, so:
-                    // c = w + s
-                    // cc = wc + s
-                    //
-                    // These can be rearranged in terms of s:
-                    // s = c - w
-                    // s = cc - wc
-                    //
-                    // As these are both equal to s, they can be equated and rearranged in terms of
-                    // wc:
-                    // c - w = cc - wc
-                    // wc = w + cc - c
-                    //
-                    // The correct weight wc is the solution to the puzzle, so calculate it using
-                    // this equation and return it.
-                    return (sub_tower.weight + correct_cumulative_weight)
-                        - sub_tower.cumulative_weight;
-                }
-
-                // If code didn't return in above if statement, then this sub-tower has its own
-                // sub-tower with a different weight than all its other sub-towers. As there is
-                // exactly one incorrect weight, it must lie with that different sub-tower or one of
-                // its sub-towers, so call this function recursively on it. Return the value from
-                // the recursive function call to propagate the return value back up to the original
-                // caller.
-                //
-                // As this own sub-tower's weight has been verified to be correct, it can be safely
-                // subtracted from the correct cumulative weight so far to get the correct
-                // cumulative weight of all sub-towers, and then divided by the number of sub-towers
-                // to get the correct cumulative weight per sub-tower.
-                return fix_wrong_weight(
-                    (correct_cumulative_weight - sub_tower.weight) / sub_tower.sub_towers.len(),
-                    sub_tower,
-                );
-            }
-        }
+    let bottom_program = solve_1(input).to_string();
+    let mut graph = DotGraph::new(true);
+    add_disc(&mut graph, &Disc::new(input, &bottom_program));
+    let (wrong_program, _) = find_wrong_weight(input);
+    graph.highlight(wrong_program);
 
-        // If no value is returned from the above loop, then then no sub-tower was found to have an
-        // erroneous weight. This can only happen if the input is malformed or this solver has a
-        // logic error. Either way, no valid correct weight can be returned, so panic.
-        panic!()
-    }
+    graph
+}
 
+// Returns the name of the program with the wrong weight, along with the weight it should have.
+fn find_wrong_weight(input: &str) -> (&str, usize) {
     // Use the solution from part 1 to get the bottom program, from which the Disc tree can be
     // built.
     let bottom_program = solve_1(input).to_string();
@@ -199,9 +101,149 @@ fn solve_2(input: &str) -> Solution {
         cw1
     };
 
-    let correct_weight = fix_wrong_weight(correct_cumulative_weight, bottom_disc);
+    fix_wrong_weight(correct_cumulative_weight, bottom_disc)
+}
 
-    Solution::USize(correct_weight)
+struct Disc<'a> {
+    program: &'a str,         // Name of the program on this disc.
+    weight: usize,            // Weight of just this disc.
+    cumulative_weight: usize, // Weight of this disc plus the weights of all sub-towers on it.
+    sub_towers: Vec<Disc<'a>>,
+}
+
+impl<'a> Disc<'a> {
+    fn new(input: &'a str, program: &str) -> Disc<'a> {
+        // The logic within building the disc and populating the cumulative weights must be
+        // moved into separate functions so they can be called recursively.
+        let mut disc = Disc::build_disc(input, program);
+        disc.populate_cumulative_weights();
+        disc
+    }
+
+    fn build_disc(input: &'a str, program: &str) -> Disc<'a> {
+        // Search the input for the line starting with the given program name.
+        for line in input.lines() {
+            let mut iter = line.split_whitespace();
+            if let Some(name) = iter.next()
+                && name == program
+            {
+                let weight = iter
+                    .next()
+                    .expect("Error reading second value of line")
+                    .trim_matches(|c| c == '(' || c == ')')
+                    .parse()
+                    .expect("Error reading number");
+
+                // Consume the "->".
+                iter.next();
+
+                let sub_towers = iter
+                    .map(|program| Disc::build_disc(input, program.trim_end_matches(',')))
+                    .collect();
+
+                return Disc {
+                    program: name,
+                    weight,
+                    cumulative_weight: 0,
+                    sub_towers,
+                };
+            }
+        }
+
+        // If no value is returned from the above loop, then the program name wasn't found in
+        // any of the lines of the input. This can only happen if the input is malformed or this
+        // solver has a logic error. Either way, no valid Disc can be returned, so panic.
+        panic!("No matching program found");
+    }
+
+    fn populate_cumulative_weights(&mut self) -> usize {
+        // Get the weight of this disc.
+        let mut cumulative_weight = self.weight;
+
+        // Add the weights of all sub-towers on this disc.
+        for sub_tower in &mut self.sub_towers {
+            cumulative_weight += sub_tower.populate_cumulative_weights();
+        }
+
+        self.cumulative_weight = cumulative_weight;
+        cumulative_weight
+    }
+}
+
+// Finds the disc with the wrong weight among the sub-towers of a disc whose cumulative weight is
+// wrong, and returns the name of its program along with the weight it should have.
+fn fix_wrong_weight(correct_cumulative_weight: usize, disc: Disc<'_>) -> (&str, usize) {
+    // We are only concerned with sub-towers that can have the incorrect value, so iterate over
+    // all sub-towers to find the one with the wrong value.
+    for sub_tower in disc.sub_towers {
+        if sub_tower.cumulative_weight != correct_cumulative_weight
+            && !sub_tower.sub_towers.is_empty()
+        {
+            // Check if all sub-towers have equal cumulative weights. If so, the current disc
+            // must be the incorrect disc (as there is exactly one incorrect disc, none of the
+            // sub-towers can be incorrect, and this function is only called on discs with
+            // incorrect cumulative weights). It's assumed that no disk has exactly one
+            // sub-tower, as then the puzzle would have an ambiguous answer as it would be
+            // impossible to tell whether the incorrect value lies with that disc or its single
+            // sub-tower. Unlike with the very bottom disc, tracking the correct cumulative
+            // weight makes it possible to identify which sub-tower has the wrong weight when
+            // there's exactly two of them.
+            let first_sub_tower_cumulative_weight = sub_tower.sub_towers[0].cumulative_weight;
+            let are_sub_towers_equal = sub_tower
+                .sub_towers
+                .iter()
+                .all(|sub_tower| sub_tower.cumulative_weight == first_sub_tower_cumulative_weight);
+
+            if are_sub_towers_equal {
+                // This sub-tower has been identified as the disc with the incorrect weight, so
+                // calculate what its weight should be.
+                //
+                // Let the weight be w, cumulative weight be c, correct weight be wc, correct
+                // cumulative weight be cc, and the sum of weights of all sub-towers be s. The
+                // cumulative weight is obtained by adding the disc's weight to the weight of
+                // all sub-towers, so:
+                // c = w + s
+                // cc = wc + s
+                //
+                // These can be rearranged in terms of s:
+                // s = c - w
+                // s = cc - wc
+                //
+                // As these are both equal to s, they can be equated and rearranged in terms of
+                // wc:
+                // c - w = cc - wc
+                // wc = w + cc - c
+                //
+                // The correct weight wc is the solution to the puzzle, so calculate it using
+                // this equation and return it.
+                return (
+                    sub_tower.program,
+                    (sub_tower.weight + correct_cumulative_weight) - sub_tower.cumulative_weight,
+                );
+            }
+
+            // If code didn't return in above if statement, then this sub-tower has its own
+            // sub-tower with a different weight than all its other sub-towers. As there is
+            // exactly one incorrect weight, it must lie with that different sub-tower or one of
+            // its sub-towers, so call this function recursively on it. Return the value from
+            // the recursive function call to propagate the return value back up to the original
+            // caller.
+            //
+            // As this own sub-tower's weight has been verified to be correct, it can be safely
+            // subtracted from the correct cumulative weight so far to get the correct
+            // cumulative weight of all sub-towers, and then divided by the number of sub-towers
+            // to get the correct cumulative weight per sub-tower.
+            return fix_wrong_weight(
+                (correct_cumulative_weight - sub_tower.weight) / sub_tower.sub_towers.len(),
+                sub_tower,
+            );
+        }
+    }
+
+    // If no value is returned from the above loop, then then no sub-tower was found to have an
+    // erroneous weight. This can only happen if the input is malformed or this solver has a
+    // logic error. Either way, no valid correct weight can be returned, so panic.
+    panic!()
 }
 
 #[cfg(test)]
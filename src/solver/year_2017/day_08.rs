@@ -1,5 +1,10 @@
+use crate::solver::common::vm::trace::Tracer;
+use crate::solver::common::vm::{Control, InstructionSet, Machine, State};
 use crate::solver::{AdventOfCode, Solution};
 use rustc_hash::FxHashMap;
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::marker::PhantomData;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2017,
@@ -9,60 +14,91 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let mut registers = FxHashMap::default();
-
-    for line in input.lines() {
-        let instruction = get_instruction(line);
-
-        let condition_register_ref = registers.entry(instruction.condition_register).or_insert(0);
-        if test_condition(
-            *condition_register_ref,
-            instruction.condition_amount,
-            &instruction.condition,
-        ) {
-            let operation_register_ref =
-                registers.entry(instruction.operation_register).or_insert(0);
-            match instruction.operation {
-                Operation::Increase => *operation_register_ref += instruction.operation_amount,
-                Operation::Decrease => *operation_register_ref -= instruction.operation_amount,
-            }
-        }
-    }
-
-    let largest_register_ref = registers
+    let machine = run(input);
+    let largest_register = machine
+        .state
+        .registers
         .values()
         .max()
         .expect("Error finding maximum value");
-    Solution::I32(*largest_register_ref)
+    Solution::I32(*largest_register)
 }
 
 fn solve_2(input: &str) -> Solution {
-    let mut registers = FxHashMap::default();
-    let mut largest_value = i32::MIN;
+    Solution::I32(run(input).instruction_set.largest_value)
+}
 
-    for line in input.lines() {
-        let instruction = get_instruction(line);
+fn run(input: &str) -> Machine<ConditionalRegisters<'_>> {
+    let mut machine = get_machine(input);
+    machine.run();
+    machine
+}
+
+pub fn trace(input: &str, _part: u8, tracer: &mut Tracer) -> io::Result<()> {
+    let mut machine = get_machine(input);
+    // Watched registers have to be found among the register names in the program, since the
+    // registers are named by slices of the puzzle input.
+    let register_names = machine
+        .program
+        .iter()
+        .flat_map(|instruction| {
+            [
+                instruction.operation_register,
+                instruction.condition_register,
+            ]
+        })
+        .collect::<Vec<_>>();
+    tracer.run(&mut machine, |name| {
+        register_names
+            .iter()
+            .copied()
+            .find(|register| *register == name)
+    })?;
+    tracer.finish(&machine)
+}
+
+fn get_machine(input: &str) -> Machine<ConditionalRegisters<'_>> {
+    let instructions = input.lines().map(get_instruction).collect();
+    let instruction_set = ConditionalRegisters {
+        largest_value: i32::MIN,
+        register_names: PhantomData,
+    };
+    Machine::new(instruction_set, instructions, FxHashMap::default())
+}
+
+// The instructions modify registers named by strings in the puzzle input. The instruction set keeps
+// track of the largest value ever written to a register.
+struct ConditionalRegisters<'a> {
+    largest_value: i32,
+    register_names: PhantomData<&'a str>,
+}
 
-        let condition_register_ref = registers.entry(instruction.condition_register).or_insert(0);
+impl<'a> InstructionSet for ConditionalRegisters<'a> {
+    type Instruction = Instruction<'a>;
+    type Registers = FxHashMap<&'a str, i32>;
+
+    fn execute(
+        &mut self,
+        instruction: &Instruction<'a>,
+        state: &mut State<Self::Registers>,
+    ) -> Control {
+        // Reading a register creates it, so registers that are only ever compared still count
+        // towards the largest value at the end.
+        let condition_value = *state.register_mut(instruction.condition_register);
         if test_condition(
-            *condition_register_ref,
+            condition_value,
             instruction.condition_amount,
             &instruction.condition,
         ) {
-            let operation_register_ref =
-                registers.entry(instruction.operation_register).or_insert(0);
+            let operation_register_ref = state.register_mut(instruction.operation_register);
             match instruction.operation {
                 Operation::Increase => *operation_register_ref += instruction.operation_amount,
                 Operation::Decrease => *operation_register_ref -= instruction.operation_amount,
             }
-
-            if *operation_register_ref > largest_value {
-                largest_value = *operation_register_ref;
-            }
+            self.largest_value = self.largest_value.max(*operation_register_ref);
         }
+        Control::Next
     }
-
-    Solution::I32(largest_value)
 }
 
 enum Operation {
@@ -88,6 +124,31 @@ struct Instruction<'a> {
     condition_amount: i32,
 }
 
+impl Display for Instruction<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let operation = match self.operation {
+            Operation::Increase => "inc",
+            Operation::Decrease => "dec",
+        };
+        let condition = match self.condition {
+            Condition::EqualTo => "==",
+            Condition::NotEqualTo => "!=",
+            Condition::GreaterThan => ">",
+            Condition::GreaterThanOrEqualTo => ">=",
+            Condition::LessThan => "<",
+            Condition::LessThanOrEqualTo => "<=",
+        };
+        write!(
+            f,
+            "{} {operation} {} if {} {condition} {}",
+            self.operation_register,
+            self.operation_amount,
+            self.condition_register,
+            self.condition_amount
+        )
+    }
+}
+
 fn get_instruction(line: &str) -> Instruction<'_> {
     let mut iter = line.split_whitespace();
     let operation_register = iter.next().expect("Missing value");
@@ -1,4 +1,7 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::combinator::{
+    Parser, any_char, char_where, delimited, literal, many, preceded, separated,
+};
+use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2017,
@@ -8,70 +11,72 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let mut score: u32 = 0;
-    let mut level: u32 = 0;
-    let mut is_inside_garbage = false;
-
-    let mut iter = input.chars();
+    Solution::U32(get_stream_totals(input).score)
+}
 
-    while let Some(char) = iter.next() {
-        if is_inside_garbage {
-            match char {
-                '>' => {
-                    is_inside_garbage = false;
-                }
-                '!' => {
-                    // Call next() on the iterator and ignore the value to skip the next character.
-                    iter.next();
-                }
-                _ => {}
-            }
-        } else {
-            match char {
-                '{' => {
-                    level += 1;
-                    score += level;
-                }
-                '}' => {
-                    level -= 1;
-                }
-                '<' => {
-                    is_inside_garbage = true;
-                }
-                _ => {}
-            }
-        }
-    }
+fn solve_2(input: &str) -> Solution {
+    Solution::U32(get_stream_totals(input).garbage_count)
+}
 
-    Solution::U32(score)
+// Totals for a thing in the stream (either a group or garbage) and everything inside it.
+#[derive(Default)]
+struct Totals {
+    groups: u32,
+    // The total score of all groups, if this thing were the outermost thing in the stream.
+    score: u32,
+    garbage_count: u32,
 }
 
-fn solve_2(input: &str) -> Solution {
-    let mut garbage_count: u32 = 0;
-    let mut is_inside_garbage = false;
+fn get_stream_totals(input: &str) -> Totals {
+    let (totals, rest) = thing(input.trim_end()).expect("Stream should be a group or garbage");
+    assert!(
+        rest.is_empty(),
+        "Stream should only contain one outermost thing"
+    );
+    totals
+}
 
-    let mut iter = input.chars();
+// Parses either a group or garbage.
+fn thing(input: &str) -> Option<(Totals, &str)> {
+    group.or(garbage()).parse(input)
+}
 
-    while let Some(char) = iter.next() {
-        if is_inside_garbage {
-            match char {
-                '>' => {
-                    is_inside_garbage = false;
-                }
-                '!' => {
-                    // Call next() on the iterator and ignore the value to skip the next character.
-                    iter.next();
-                }
-                _ => {
-                    garbage_count += 1;
-                }
+// Parses a group, which is a comma-separated list of things between curly braces.
+fn group(input: &str) -> Option<(Totals, &str)> {
+    delimited(literal("{"), separated(thing, literal(",")), literal("}"))
+        .map(|things| {
+            // This group scores 1, and each group inside it scores one more than it would if it were
+            // the outermost group, as it's nested one level deeper.
+            let mut totals = Totals {
+                groups: 1,
+                score: 1,
+                garbage_count: 0,
+            };
+            for thing in things {
+                totals.groups += thing.groups;
+                totals.score += thing.score + thing.groups;
+                totals.garbage_count += thing.garbage_count;
             }
-        } else if char == '<' {
-            is_inside_garbage = true;
-        }
-    }
+            totals
+        })
+        .parse(input)
+}
+
+// Parses garbage, which is any characters between angle brackets, where a '!' cancels the character
+// after it. Cancelled characters and the '!' cancelling them aren't counted as garbage.
+fn garbage<'a>() -> impl Parser<'a, Totals> {
+    let cancelled = preceded(literal("!"), any_char()).map(|_| 0);
+    let garbage_character = char_where(|character| character != '>').map(|_| 1);
 
-    Solution::U32(garbage_count)
+    delimited(
+        literal("<"),
+        many(cancelled.or(garbage_character)),
+        literal(">"),
+    )
+    .map(|counts| Totals {
+        garbage_count: counts.iter().sum(),
+        ..Totals::default()
+    })
 }
 
 #[cfg(test)]
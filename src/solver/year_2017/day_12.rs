@@ -1,5 +1,6 @@
-use crate::solver::{Solution, AdventOfCode};
-use rustc_hash::FxHashMap;
+use crate::solver::common::dot::DotGraph;
+use crate::solver::common::union_find::UnionFind;
+use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2017,
@@ -9,33 +10,52 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let pipes = get_pipes(input);
+    let mut pipe_groups = get_pipe_groups(input);
 
-    Solution::USize(get_pipe_group(0, &pipes).len())
+    Solution::USize(pipe_groups.component_size(0))
 }
 
 fn solve_2(input: &str) -> Solution {
-    let pipes = get_pipes(input);
-
-    let mut groups: u16 = 0;
-    let mut found_pipes = Vec::new();
-
-    // Iterate over every pipe. Check if it's been previously found as a member of a group (tracked
-    // by found_pipes). If not, find all connected pipes and add them to found_pipes, then increment
-    // group count.
-    for starting_pipe in pipes.keys() {
-        if !found_pipes.contains(starting_pipe) {
-            found_pipes.append(&mut get_pipe_group(*starting_pipe, &pipes));
-            groups += 1;
+    let pipe_groups = get_pipe_groups(input);
+
+    Solution::USize(pipe_groups.component_count())
+}
+
+// Draws the pipes with an edge for each connection, highlighting the pipes in the group containing
+// pipe 0 that part 1 counts.
+pub fn export_graph(input: &str) -> DotGraph {
+    let mut pipe_groups = get_pipe_groups(input);
+    let mut graph = DotGraph::new(false);
+
+    for line in input.lines() {
+        let (pipe, connected_pipes) = line
+            .split_once(" <-> ")
+            .expect("Line should have a \" <-> \"");
+        graph.add_node(pipe, None);
+        for connected_pipe in connected_pipes.split(", ") {
+            // Every connection is listed by the pipes at both ends, so only draw it from the pipe
+            // with the lower ID.
+            let parse = |pipe: &str| pipe.parse::<usize>().expect("Error parsing number");
+            if parse(pipe) <= parse(connected_pipe) {
+                graph.add_edge(pipe, connected_pipe, None);
+            }
+        }
+    }
+
+    for pipe in 0..pipe_groups.len() {
+        if pipe_groups.connected(0, pipe) {
+            graph.highlight(&pipe.to_string());
         }
     }
 
-    Solution::U16(groups)
+    graph
 }
 
-// Get hashmap of pipe IDs to all pipes its connected to.
-fn get_pipes(input: &str) -> FxHashMap<u16, Vec<u16>> {
-    let mut pipes: FxHashMap<u16, Vec<u16>> = FxHashMap::default();
+// Get the groups of pipes, where pipes are in the same group if they're connected to each other
+// either directly or through other pipes. Each line of the input lists one pipe, in increasing
+// order of IDs starting from 0, so the pipe IDs can be used as indexes.
+fn get_pipe_groups(input: &str) -> UnionFind {
+    let mut pipe_groups = UnionFind::new(input.lines().count());
 
     for line in input.lines() {
         let mut iter = line.split_whitespace();
@@ -48,37 +68,16 @@ fn get_pipes(input: &str) -> FxHashMap<u16, Vec<u16>> {
         // Consume the "<->".
         iter.next();
 
-        let connected_pipes = iter
-            .map(|pipe| {
-                pipe.trim_end_matches(',')
-                    .parse()
-                    .expect("Error parsing number")
-            })
-            .collect();
-
-        pipes.insert(current_pipe, connected_pipes);
-    }
-
-    pipes
-}
-
-// Get vector of all pipes connected to starting_pipe.
-fn get_pipe_group(starting_pipe: u16, pipes: &FxHashMap<u16, Vec<u16>>) -> Vec<u16> {
-    let mut pipe_stack = vec![starting_pipe];
-    let mut found_pipes = vec![starting_pipe];
-
-    while let Some(pipe) = pipe_stack.pop() {
-        let connected_pipes = pipes.get(&pipe).expect("Pipe missing from hashmap");
-
-        for connected_pipe in connected_pipes {
-            if !found_pipes.contains(connected_pipe) {
-                found_pipes.push(*connected_pipe);
-                pipe_stack.push(*connected_pipe);
-            }
+        for connected_pipe in iter {
+            let connected_pipe = connected_pipe
+                .trim_end_matches(',')
+                .parse()
+                .expect("Error parsing number");
+            pipe_groups.union(current_pipe, connected_pipe);
         }
     }
 
-    found_pipes
+    pipe_groups
 }
 
 #[cfg(test)]
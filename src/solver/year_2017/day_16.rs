@@ -1,4 +1,5 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::cycle::nth_state;
+use crate::solver::{AdventOfCode, Solution};
 use std::collections::VecDeque;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -19,26 +20,21 @@ fn solve_1(input: &str) -> Solution {
 }
 
 fn solve_2(input: &str) -> Solution {
-    // The positions the dancers are in after each dance loops quickly. By identifying the loop
-    // size, most dances can be skipped.
-    let mut dancers = VecDeque::from([
+    // The positions the dancers are in after each dance loop quickly. Once a loop is found, most
+    // dances can be skipped.
+    let dancers = VecDeque::from([
         'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
     ]);
-    let starting_positions = dancers.clone();
-
-    // Do one initial dance so the while loop condition isn't initially unsatisfied.
-    dance(&mut dancers, input);
-    let mut dance_loop_size = 1;
 
-    while dancers != starting_positions {
-        dance(&mut dancers, input);
-        dance_loop_size += 1;
-    }
-
-    let unique_dances = 1_000_000_000 % dance_loop_size;
-    for _ in 0..unique_dances {
-        dance(&mut dancers, input);
-    }
+    let dancers = nth_state(
+        |dancers| {
+            let mut dancers = dancers.clone();
+            dance(&mut dancers, input);
+            dancers
+        },
+        dancers,
+        1_000_000_000,
+    );
 
     Solution::String(dancers.iter().collect())
 }
@@ -1,6 +1,9 @@
+use crate::solver::common::vm::trace::Tracer;
+use crate::solver::common::vm::{Control, InstructionSet, Machine, RegisterOrValue, State, Status};
 use crate::solver::{AdventOfCode, Solution};
 use rustc_hash::FxHashMap;
-use std::{cell::RefCell, collections::VecDeque, rc::Rc, str::SplitWhitespace};
+use std::fmt::{self, Display, Formatter};
+use std::io;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2017,
@@ -10,328 +13,202 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let instructions = get_instructions(input);
-    let mut program_state = ProgramState {
-        program_counter: 0,
-        last_frequency: None,
-        registers: FxHashMap::default(),
-        is_terminating: false,
-        this_message_queue: None,
-        other_message_queue: None,
-        sent_messages: 0,
-    };
-
-    // Repeatedly execute instructions until an instruction sets the is_terminating flag (which
-    // happens then rcv is called with a non-zero value). The program counter tracks the index of
-    // the next instruction to execute.
-    while !program_state.is_terminating {
-        instructions[program_state.program_counter].execute_set_1(&mut program_state);
-    }
+    // Sounds are played by writing them to the output, and the program halts once rcv is called
+    // with a non-zero value.
+    let mut machine = Machine::new(Duet::Sound, get_instructions(input), FxHashMap::default());
+    machine.run();
 
-    if let Some(last_frequency) = program_state.last_frequency {
-        Solution::I64(last_frequency)
+    if let Some(last_frequency) = machine.state.output.back() {
+        Solution::I64(*last_frequency)
     } else {
         panic!("No frequency was ever sounded")
     }
 }
 
 fn solve_2(input: &str) -> Solution {
-    let instructions = get_instructions(input);
-
-    // Both programs need to be able to access each others' message queues so that when they have a
-    // value to send, they can push it to the other's message queue. This requires reference
-    // counting with interior mutability.
-    let program_0_message_queue = Rc::new(RefCell::new(VecDeque::new()));
-    let program_1_message_queue = Rc::new(RefCell::new(VecDeque::new()));
+    let (mut program_0, mut program_1) = get_duet_programs(input);
+    let sent_messages = run_duet(&mut program_0, &mut program_1, |program| Ok(program.run()))
+        .expect("Running without tracing can't fail");
+    Solution::USize(sent_messages)
+}
 
-    let mut program_state_0 = ProgramState {
-        program_counter: 0,
-        last_frequency: None,
-        registers: FxHashMap::default(),
-        is_terminating: false,
-        this_message_queue: Some(Rc::clone(&program_0_message_queue)),
-        other_message_queue: Some(Rc::clone(&program_1_message_queue)),
-        sent_messages: 0,
-    };
-    let mut program_state_1 = ProgramState {
-        program_counter: 0,
-        last_frequency: None,
-        registers: FxHashMap::default(),
-        is_terminating: false,
-        this_message_queue: Some(program_1_message_queue),
-        other_message_queue: Some(program_0_message_queue),
-        sent_messages: 0,
+pub fn trace(input: &str, part: u8, tracer: &mut Tracer) -> io::Result<()> {
+    let parse_register = |name: &str| {
+        let mut chars = name.chars();
+        chars.next().filter(|_| chars.next().is_none())
     };
-    // No need to explicitly set program 0 to have register p start with value 0 since 0 is the
-    // default value of a register.
-    program_state_1.registers.insert('p', 1);
 
-    // When a program attempts to receive and no value is available to be received, it remains on
-    // that instruction (the program counter is not incremented) and it sets the is_terminating flag
-    // to true. However, it will continue to check for a new received value every loop, and if it
-    // receives one, it will set is_terminating back to false and continue executing. If both
-    // programs set their is_terminating flag to true at a same time, then they have reached a
-    // deadlock, so the loop exits.
-    while !(program_state_0.is_terminating && program_state_1.is_terminating) {
-        instructions[program_state_0.program_counter].execute_set_2(&mut program_state_0);
-        instructions[program_state_1.program_counter].execute_set_2(&mut program_state_1);
+    if part == 2 {
+        // Only program 0 is traced, with program 1 running alongside it.
+        let (mut program_0, mut program_1) = get_duet_programs(input);
+        run_duet(&mut program_0, &mut program_1, |program| {
+            tracer.run(program, parse_register)
+        })?;
+        tracer.finish(&program_0)
+    } else {
+        let mut machine = Machine::new(Duet::Sound, get_instructions(input), FxHashMap::default());
+        tracer.run(&mut machine, parse_register)?;
+        tracer.finish(&machine)
     }
-
-    Solution::U64(program_state_1.sent_messages)
 }
 
-// Representation of data necessary to run the program.
-struct ProgramState {
-    program_counter: usize,
-    last_frequency: Option<i64>,
-    registers: FxHashMap<char, i64>,
-    is_terminating: bool,
-    this_message_queue: Option<Rc<RefCell<VecDeque<i64>>>>,
-    other_message_queue: Option<Rc<RefCell<VecDeque<i64>>>>,
-    sent_messages: u64,
-}
-// Representation of instruction operands, which can either be a register name or an integer
-// literal.
-enum RegisterOrValue {
-    Register(char),
-    Value(i64),
-}
-// Representation of individual instructions.
-enum Instruction {
-    Snd(RegisterOrValue),
-    Set(RegisterOrValue, RegisterOrValue),
-    Add(RegisterOrValue, RegisterOrValue),
-    Mul(RegisterOrValue, RegisterOrValue),
-    Mod(RegisterOrValue, RegisterOrValue),
-    Rcv(RegisterOrValue),
-    Jgz(RegisterOrValue, RegisterOrValue),
+fn get_duet_programs(input: &str) -> (Machine<Duet>, Machine<Duet>) {
+    let instructions = get_instructions(input);
+    let program_0 = Machine::new(Duet::Messages, instructions.clone(), FxHashMap::default());
+    let mut program_1 = Machine::new(Duet::Messages, instructions, FxHashMap::default());
+    // No need to explicitly set program 0 to have register p start with value 0 since 0 is the
+    // default value of a register.
+    *program_1.state.register_mut('p') = 1;
+    (program_0, program_1)
 }
 
-impl RegisterOrValue {
-    // Get the value from the operand, either by directly returning it if the operand is an
-    // integer literal, or by fetching the value from the register if the operand is a
-    // register name.
-    fn get_value(&self, program_state: &ProgramState) -> i64 {
-        match self {
-            RegisterOrValue::Register(register) => {
-                *program_state.registers.get(register).unwrap_or(&0)
-            }
-            RegisterOrValue::Value(value) => *value,
+// Runs both programs until they reach a deadlock, and returns the number of values program 1 sent.
+// Program 0 is run with the given function so that it can be traced, and if that function stops
+// running it before it halts or waits, both programs are stopped early.
+fn run_duet(
+    program_0: &mut Machine<Duet>,
+    program_1: &mut Machine<Duet>,
+    mut run_program_0: impl FnMut(&mut Machine<Duet>) -> io::Result<Status>,
+) -> io::Result<usize> {
+    // Each program sends values by writing them to its output, and receives values by reading them
+    // from its input. When a program attempts to receive and no value is available, it waits on
+    // that instruction, so run each program until it halts or waits, then deliver the values each
+    // has sent to the other. If neither program can make any progress after that, they have
+    // reached a deadlock.
+    let mut sent_messages = 0;
+    loop {
+        if run_program_0(program_0)? == Status::Running {
+            break;
         }
-    }
-    // Get a mutable reference to the value in the operand. It's assumed this operand will only be
-    // called for operands that are going to be written to (first operand of the set, add, mul, and
-    // mod instructions, as well the rcv instruction for part 2), which only makes sense when the
-    // operand is a register, so this function panics if the operand is a literal.
-    fn get_value_mut<'a>(&self, program_state: &'a mut ProgramState) -> &'a mut i64 {
-        match self {
-            RegisterOrValue::Register(register) => {
-                program_state.registers.entry(*register).or_insert(0)
-            }
-            RegisterOrValue::Value(_) => {
-                panic!("Should not be getting a mutable reference to a literal operand")
-            }
+        program_1.run();
+
+        sent_messages += program_1.state.output.len();
+        program_0
+            .state
+            .input
+            .extend(program_1.state.output.drain(..));
+        program_1
+            .state
+            .input
+            .extend(program_0.state.output.drain(..));
+
+        if program_0.is_blocked() && program_1.is_blocked() {
+            break;
         }
     }
+
+    Ok(sent_messages)
 }
 
-impl Instruction {
-    // Instruction mappings for part 1.
-    fn execute_set_1(&self, program_state: &mut ProgramState) {
-        match self {
-            Instruction::Snd(op_1) => Instruction::sound(program_state, op_1),
-            Instruction::Set(op_1, op_2) => Instruction::set(program_state, op_1, op_2),
-            Instruction::Add(op_1, op_2) => Instruction::add(program_state, op_1, op_2),
-            Instruction::Mul(op_1, op_2) => Instruction::multiply(program_state, op_1, op_2),
-            Instruction::Mod(op_1, op_2) => Instruction::modulo(program_state, op_1, op_2),
-            Instruction::Rcv(op_1) => Instruction::recover(program_state, op_1),
-            Instruction::Jgz(op_1, op_2) => {
-                Instruction::jump_greater_zero(program_state, op_1, op_2);
-            }
-        }
-    }
-    // Instruction mappings for part 2.
-    fn execute_set_2(&self, program_state: &mut ProgramState) {
-        match self {
-            Instruction::Snd(op_1) => Instruction::send(program_state, op_1),
-            Instruction::Set(op_1, op_2) => Instruction::set(program_state, op_1, op_2),
-            Instruction::Add(op_1, op_2) => Instruction::add(program_state, op_1, op_2),
-            Instruction::Mul(op_1, op_2) => Instruction::multiply(program_state, op_1, op_2),
-            Instruction::Mod(op_1, op_2) => Instruction::modulo(program_state, op_1, op_2),
-            Instruction::Rcv(op_1) => Instruction::receive(program_state, op_1),
-            Instruction::Jgz(op_1, op_2) => {
-                Instruction::jump_greater_zero(program_state, op_1, op_2);
-            }
-        }
-    }
+type Operand = RegisterOrValue<char, i64>;
 
-    // Shared instructions
-    fn set(program_state: &mut ProgramState, op_1: &RegisterOrValue, op_2: &RegisterOrValue) {
-        let op_2 = op_2.get_value(program_state);
-        let op_1 = op_1.get_value_mut(program_state);
-        *op_1 = op_2;
-        program_state.program_counter += 1;
-    }
-    fn add(program_state: &mut ProgramState, op_1: &RegisterOrValue, op_2: &RegisterOrValue) {
-        let op_2 = op_2.get_value(program_state);
-        let op_1 = op_1.get_value_mut(program_state);
-        *op_1 += op_2;
-        program_state.program_counter += 1;
-    }
-    fn multiply(program_state: &mut ProgramState, op_1: &RegisterOrValue, op_2: &RegisterOrValue) {
-        let op_2 = op_2.get_value(program_state);
-        let op_1 = op_1.get_value_mut(program_state);
-        *op_1 *= op_2;
-        program_state.program_counter += 1;
-    }
-    fn modulo(program_state: &mut ProgramState, op_1: &RegisterOrValue, op_2: &RegisterOrValue) {
-        let op_2 = op_2.get_value(program_state);
-        let op_1 = op_1.get_value_mut(program_state);
-        *op_1 %= op_2;
-        program_state.program_counter += 1;
-    }
-    fn jump_greater_zero(
-        program_state: &mut ProgramState,
-        op_1: &RegisterOrValue,
-        op_2: &RegisterOrValue,
-    ) {
-        let op_1 = op_1.get_value(program_state);
-        if op_1 > 0 {
-            let op_2 = op_2.get_value(program_state);
-            if op_2.is_negative() {
-                program_state.program_counter -= usize::try_from(op_2.unsigned_abs())
-                    .expect("Should be able to convert to usize losslessly");
-            } else {
-                program_state.program_counter +=
-                    usize::try_from(op_2).expect("Should be able to convert to usize losslessly");
-            }
-        } else {
-            program_state.program_counter += 1;
-        }
-    }
+// Representation of individual instructions. Operands that are written to must be registers, so
+// they're stored as register names.
+#[derive(Clone)]
+enum Instruction {
+    Snd(Operand),
+    Set(char, Operand),
+    Add(char, Operand),
+    Mul(char, Operand),
+    Mod(char, Operand),
+    Rcv(char),
+    Jgz(Operand, Operand),
+}
 
-    // Part 1 exclusive instructions
-    fn sound(program_state: &mut ProgramState, op_1: &RegisterOrValue) {
-        let op_1 = op_1.get_value(program_state);
-        program_state.last_frequency = Some(op_1);
-        program_state.program_counter += 1;
-    }
-    fn recover(program_state: &mut ProgramState, op_1: &RegisterOrValue) {
-        let op_1 = op_1.get_value(program_state);
-        if op_1 != 0 {
-            program_state.is_terminating = true;
-        } else {
-            program_state.program_counter += 1;
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Instruction::Snd(op_1) => write!(f, "snd {op_1}"),
+            Instruction::Set(op_1, op_2) => write!(f, "set {op_1} {op_2}"),
+            Instruction::Add(op_1, op_2) => write!(f, "add {op_1} {op_2}"),
+            Instruction::Mul(op_1, op_2) => write!(f, "mul {op_1} {op_2}"),
+            Instruction::Mod(op_1, op_2) => write!(f, "mod {op_1} {op_2}"),
+            Instruction::Rcv(op_1) => write!(f, "rcv {op_1}"),
+            Instruction::Jgz(op_1, op_2) => write!(f, "jgz {op_1} {op_2}"),
         }
     }
+}
 
-    // Part 2 exclusive instructions
-    fn send(program_state: &mut ProgramState, op_1: &RegisterOrValue) {
-        let op_1 = op_1.get_value(program_state);
-        // Dereference the Rc and RefCell to access the message queue within and push to it.
-        program_state
-            .other_message_queue
-            .as_ref()
-            .expect("Should have a message queue")
-            .as_ref()
-            .borrow_mut()
-            .push_back(op_1);
-        program_state.sent_messages += 1;
-        program_state.program_counter += 1;
-    }
-    fn receive(program_state: &mut ProgramState, op_1: &RegisterOrValue) {
-        // Dereference the Rc and RefCell to access the message queue within and retrieve the front
-        // value from it.
-        let value = program_state
-            .this_message_queue
-            .as_ref()
-            .expect("Should have a message queue")
-            .as_ref()
-            .borrow_mut()
-            .pop_front();
-        match value {
-            Some(value) => {
-                let op_1 = op_1.get_value_mut(program_state);
-                *op_1 = value;
-                // It's possible this code was blocking for some time and set the is_terminating
-                // flag to true, so explicitly set it back to false now that a value has been
-                // received and execution can continue.
-                program_state.is_terminating = false;
-                program_state.program_counter += 1;
+// The two ways of interpreting the snd and rcv instructions. For part 1 they play and recover
+// sounds, and for part 2 they send and receive messages.
+enum Duet {
+    Sound,
+    Messages,
+}
+
+impl InstructionSet for Duet {
+    type Instruction = Instruction;
+    type Registers = FxHashMap<char, i64>;
+
+    fn execute(
+        &mut self,
+        instruction: &Instruction,
+        state: &mut State<Self::Registers>,
+    ) -> Control {
+        match instruction {
+            Instruction::Snd(op_1) => {
+                let op_1 = state.value(op_1);
+                state.output.push_back(op_1);
             }
-            None => {
-                // If popping returned none, the other program has not yet sent a value, so block
-                // until one is available. This is achieved by not incrementing the program counter
-                // so this instruction will continue to be executed until a value is available. The
-                // is_terminating flag is also set so the outer code can detect when the deadlock
-                // occurs.
-                program_state.is_terminating = true;
+            Instruction::Set(op_1, op_2) => *state.register_mut(*op_1) = state.value(op_2),
+            Instruction::Add(op_1, op_2) => *state.register_mut(*op_1) += state.value(op_2),
+            Instruction::Mul(op_1, op_2) => *state.register_mut(*op_1) *= state.value(op_2),
+            Instruction::Mod(op_1, op_2) => *state.register_mut(*op_1) %= state.value(op_2),
+            Instruction::Rcv(op_1) => match self {
+                Duet::Sound => {
+                    if state.value(&RegisterOrValue::Register(*op_1)) != 0 {
+                        return Control::Halt;
+                    }
+                }
+                Duet::Messages => match state.input.pop_front() {
+                    Some(value) => *state.register_mut(*op_1) = value,
+                    None => return Control::Wait,
+                },
+            },
+            Instruction::Jgz(op_1, op_2) => {
+                if state.value(op_1) > 0 {
+                    return Control::Jump(
+                        isize::try_from(state.value(op_2))
+                            .expect("Should be able to convert to isize losslessly"),
+                    );
+                }
             }
         }
+        Control::Next
     }
 }
 
 fn get_instructions(input: &str) -> Vec<Instruction> {
-    // Helper function to extract an operand.
-    fn get_operand(iter: &mut SplitWhitespace) -> RegisterOrValue {
-        let op = iter.next().expect("Line should have third value");
-
-        if let Ok(value) = op.parse() {
-            RegisterOrValue::Value(value)
-        } else {
-            let char = op
-                .chars()
-                .next()
-                .expect("Operand should be a number or single character");
-            RegisterOrValue::Register(char)
+    // Helper functions to extract operands.
+    fn get_register(operand: &str) -> char {
+        let mut chars = operand.chars();
+        match (chars.next(), chars.next()) {
+            (Some(register), None) => register,
+            _ => panic!("Register should be a single character"),
         }
     }
-
-    let mut instructions = Vec::new();
-
-    for line in input.lines() {
-        let mut iter = line.split_whitespace();
-        let instruction = iter.next().expect("Line should have first value");
-        let instruction = match instruction {
-            "snd" => {
-                let op_1 = get_operand(&mut iter);
-                Instruction::Snd(op_1)
-            }
-            "set" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Set(op_1, op_2)
+    fn get_operand(operand: &str) -> Operand {
+        RegisterOrValue::parse(operand, get_register)
+    }
+
+    input
+        .lines()
+        .map(|line| {
+            let mut iter = line.split_whitespace();
+            let instruction = iter.next().expect("Line should have first value");
+            let mut operand = || iter.next().expect("Instruction should have an operand");
+            match instruction {
+                "snd" => Instruction::Snd(get_operand(operand())),
+                "set" => Instruction::Set(get_register(operand()), get_operand(operand())),
+                "add" => Instruction::Add(get_register(operand()), get_operand(operand())),
+                "mul" => Instruction::Mul(get_register(operand()), get_operand(operand())),
+                "mod" => Instruction::Mod(get_register(operand()), get_operand(operand())),
+                "rcv" => Instruction::Rcv(get_register(operand())),
+                "jgz" => Instruction::Jgz(get_operand(operand()), get_operand(operand())),
+                _ => panic!("Invalid instruction name"),
             }
-            "add" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Add(op_1, op_2)
-            }
-            "mul" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Mul(op_1, op_2)
-            }
-            "mod" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Mod(op_1, op_2)
-            }
-            "rcv" => {
-                let op_1 = get_operand(&mut iter);
-                Instruction::Rcv(op_1)
-            }
-            "jgz" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Jgz(op_1, op_2)
-            }
-            _ => panic!("Invalid instruction name"),
-        };
-        instructions.push(instruction);
-    }
-
-    instructions
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -1,3 +1,4 @@
+use crate::solver::common::geometry::Direction;
 use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -48,13 +49,6 @@ fn traverse_path<T, F: FnMut(&[Vec<char>], &mut T, usize, usize)>(
     update: &mut F,
     update_value: &mut T,
 ) {
-    enum Direction {
-        Up,
-        Right,
-        Down,
-        Left,
-    }
-
     let mut grid = Vec::new();
     for line in input.lines() {
         let mut grid_line = Vec::new();
@@ -70,66 +64,34 @@ fn traverse_path<T, F: FnMut(&[Vec<char>], &mut T, usize, usize)>(
         .iter()
         .position(|c| *c != ' ')
         .expect("Should have a non-space character in first row");
-    let mut y = 0;
-    let mut direction = Direction::Down;
+    let mut y: usize = 0;
+    let mut direction = Direction::South;
+
+    // Gets the character one step away in the given direction, treating everything outside the grid
+    // as a space.
+    let get_next_char = |x: usize, y: usize, direction: Direction| {
+        let (dx, dy) = direction.offset();
+        grid.get(y.wrapping_add_signed(dy))
+            .and_then(|grid_line| grid_line.get(x.wrapping_add_signed(dx)))
+            .copied()
+            .unwrap_or(' ')
+    };
 
     loop {
-        match direction {
-            Direction::Up => {
-                y -= 1;
-                update(&grid, update_value, x, y);
-
-                if grid[y - 1][x] == ' ' {
-                    if grid[y][x + 1] != ' ' {
-                        direction = Direction::Right;
-                    } else if grid[y][x - 1] != ' ' {
-                        direction = Direction::Left;
-                    } else {
-                        break;
-                    }
-                }
-            }
-            Direction::Right => {
-                x += 1;
-                update(&grid, update_value, x, y);
-
-                if grid[y][x + 1] == ' ' {
-                    if grid[y + 1][x] != ' ' {
-                        direction = Direction::Down;
-                    } else if grid[y - 1][x] != ' ' {
-                        direction = Direction::Up;
-                    } else {
-                        break;
-                    }
-                }
-            }
-            Direction::Down => {
-                y += 1;
-                update(&grid, update_value, x, y);
-
-                if grid[y + 1][x] == ' ' {
-                    if grid[y][x + 1] != ' ' {
-                        direction = Direction::Right;
-                    } else if grid[y][x - 1] != ' ' {
-                        direction = Direction::Left;
-                    } else {
-                        break;
-                    }
-                }
-            }
-            Direction::Left => {
-                x -= 1;
-                update(&grid, update_value, x, y);
-
-                if grid[y][x - 1] == ' ' {
-                    if grid[y + 1][x] != ' ' {
-                        direction = Direction::Down;
-                    } else if grid[y - 1][x] != ' ' {
-                        direction = Direction::Up;
-                    } else {
-                        break;
-                    }
-                }
+        let (dx, dy) = direction.offset();
+        x = x.wrapping_add_signed(dx);
+        y = y.wrapping_add_signed(dy);
+        update(&grid, update_value, x, y);
+
+        // If the path doesn't continue straight ahead, it must turn left or right. If it does
+        // neither, this is the end of the path.
+        if get_next_char(x, y, direction) == ' ' {
+            match [direction.left(), direction.right()]
+                .into_iter()
+                .find(|turn| get_next_char(x, y, *turn) != ' ')
+            {
+                Some(turn) => direction = turn,
+                None => break,
             }
         }
     }
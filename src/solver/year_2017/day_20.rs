@@ -1,4 +1,5 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::geometry::Point3;
+use crate::solver::{AdventOfCode, Solution};
 use itertools::Itertools;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -47,19 +48,10 @@ fn solve_1(input: &str) -> Solution {
 }
 
 fn solve_2(input: &str) -> Solution {
-    // This vector refers to a Euclidean vector (a value with a magnitude and direction), not the
-    // type of a collection in Rust named Vec. It's postfixed with "3d" to make this distinction
-    // more explicit.
-    #[derive(Clone, PartialEq)]
-    struct Vector3d {
-        x: i64,
-        y: i64,
-        z: i64,
-    }
     struct Particle {
-        position: Vector3d,
-        velocity: Vector3d,
-        acceleration: Vector3d,
+        position: Point3<i64>,
+        velocity: Point3<i64>,
+        acceleration: Point3<i64>,
     }
     impl Particle {
         fn new(line: &str) -> Particle {
@@ -74,7 +66,7 @@ fn solve_2(input: &str) -> Solution {
                 acceleration,
             }
         }
-        fn get_vector(line_split: Option<&str>) -> Vector3d {
+        fn get_vector(line_split: Option<&str>) -> Point3<i64> {
             let mut component_iter = line_split
                 .expect("Line should have three vectors")
                 .rsplit('<')
@@ -86,7 +78,7 @@ fn solve_2(input: &str) -> Solution {
             let y = Particle::get_component(component_iter.next());
             let z = Particle::get_component(component_iter.next());
 
-            Vector3d { x, y, z }
+            Point3 { x, y, z }
         }
         fn get_component(component_split: Option<&str>) -> i64 {
             component_split
@@ -112,23 +104,19 @@ fn solve_2(input: &str) -> Solution {
         let mut collision_positions = Vec::with_capacity(particles.len());
         for (particle_0, particle_1) in particles.iter().tuple_combinations() {
             if particle_0.position == particle_1.position {
-                // The position must be cloned instead of borrowed in case this particle is removed
+                // The position must be copied instead of borrowed in case this particle is removed
                 // before other particles that collided at this position are checked (a mutable
                 // borrow of an immutable reference would occur in the retain statement if this were
-                // not cloned).
-                collision_positions.push(particle_0.position.clone());
+                // not copied).
+                collision_positions.push(particle_0.position);
             }
         }
 
         particles.retain(|particle| !collision_positions.contains(&particle.position));
 
         for particle in &mut particles {
-            particle.velocity.x += particle.acceleration.x;
-            particle.velocity.y += particle.acceleration.y;
-            particle.velocity.z += particle.acceleration.z;
-            particle.position.x += particle.velocity.x;
-            particle.position.y += particle.velocity.y;
-            particle.position.z += particle.velocity.z;
+            particle.velocity += particle.acceleration;
+            particle.position += particle.velocity;
         }
     }
 
@@ -1,5 +1,15 @@
+use crate::solver::common::math::is_prime;
+use crate::solver::common::vm::trace::Tracer;
+use crate::solver::common::vm::{
+    Control, InstructionSet, Machine, RegisterOrValue, State, Status, letter_name, letter_register,
+};
 use crate::solver::{AdventOfCode, Solution};
-use std::str::SplitWhitespace;
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::ops::Range;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2017,
@@ -9,12 +19,11 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let instructions = get_instructions(input);
-    let mut program_state = ProgramState {
-        program_counter: 0,
-        registers: [0; 8],
-        mul_invocations: 0,
-    };
+    let mut machine = Machine::new(
+        Coprocessor { mul_invocations: 0 },
+        get_instructions(input),
+        [0; 8],
+    );
 
     // The puzzle description does not state under what conditions the code in the puzzle input
     // terminates. If we assume that the puzzle has a finite answer, then at some point, the mul
@@ -24,11 +33,27 @@ fn solve_1(input: &str) -> Solution {
     // outside of the code. Through testing my own puzzle input, I have verified that the latter is
     // true (my code eventually jumps to an instruction outside of the code), so I will assume this
     // is the intended termination condition for all puzzle inputs.
-    while let Some(instruction) = instructions.get(program_state.program_counter) {
-        instruction.execute(&mut program_state);
-    }
+    machine.run();
 
-    Solution::U32(program_state.mul_invocations)
+    Solution::U32(machine.instruction_set.mul_invocations)
+}
+
+pub fn trace(input: &str, part: u8, tracer: &mut Tracer) -> io::Result<()> {
+    // Part 2 runs the full program with register a set to 1, which takes far too long to finish
+    // without a step limit.
+    let mut registers = [0; 8];
+    if part == 2 {
+        registers[0] = 1;
+    }
+    let mut machine = Machine::new(
+        Coprocessor { mul_invocations: 0 },
+        get_instructions(input),
+        registers,
+    );
+    tracer.run(&mut machine, |name| {
+        (name.len() == 1 && ("a"..="h").contains(&name)).then(|| letter_register(name))
+    })?;
+    tracer.finish(&machine)
 }
 
 fn solve_2(input: &str) -> Solution {
@@ -53,254 +78,543 @@ fn solve_2(input: &str) -> Solution {
     // by the bounds of registers b and c and the hardcoded offset, and stores the count in register
     // h.
     //
-    // I assume this is the intended trick of the puzzle, and that every puzzle input is set up to
-    // follow the rules described above. As such, my solution searches the program in the puzzle
-    // input for every line that modifies registers b or c, executes all of them except for the last
-    // one, assumes the values for b and c I end up with are the initial values for them, and
-    // assumes the literal argument to the last instruction instead defines the offset. I ignore all
-    // other lines in the puzzle input, assuming that the rest of it is just implementation details
-    // for counting composite numbers. I use the extracted b, c, and offset values to set up the
-    // full list of values, then use my own implementation for counting composite numbers. If any of
-    // the assumptions I have made for this solution are incorrect, then my solution might not work
-    // for all possible puzzle inputs.
-
-    fn is_prime(x: u32) -> bool {
-        // Technically, an is_prime() function would need to check if the input is 1 or lower and
-        // immediately return false if so, but it's known this function will never be ran for a
-        // value smaller than 2 within this solver, so this check is omitted.
-
-        // When checking for prime numbers, only integers up to the square root of the number need
-        // to be checked (any factor greater than the square root will need to be multiplied by a
-        // factor smaller than the square root to equal the given number, so if the number isn't
-        // prime, it will always have at least one factor (other than 1) that is smaller than its
-        // square root).
-
-        // Intentionally truncating the float (this is accounted for by using an inclusive range
-        // "..=" which includes the end instead of a normal range ".." which excludes the end).
-        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
-        for test in 2..=(f64::from(x)).sqrt() as u32 {
-            if x.is_multiple_of(test) {
-                return false;
-            }
-        }
-        true
+    // I assume this is the intended trick of the puzzle. Rather than trusting that every puzzle
+    // input follows it, the program is disassembled into basic blocks and loops, and checked
+    // against the exact shape described above (see find_composite_count), which gives a
+    // descriptive error if a puzzle input doesn't match. The setup code before the outer loop is
+    // run as normal to find the initial values of b and c, and the step between values is taken
+    // from the "sub b -<SOME VALUE>" instruction. The composite numbers among those values are
+    // then counted directly.
+    let instructions = get_instructions(input);
+    let composite_count =
+        find_composite_count(&instructions).unwrap_or_else(|error| panic!("{error}"));
+
+    let composites = (composite_count.start..=composite_count.end)
+        .step_by(composite_count.step)
+        .filter(|value| !is_prime(*value))
+        .count();
+    Solution::USize(composite_count.initial_h + composites)
+}
+
+// Note that unlike in day 18, it is specified that only the first 8 letters ('a' to 'h') are used.
+// This is taken advantage of so that each letter is an index into an array of 8 ints, rather than a
+// HashMap. Operands that are written to must be registers, so they're stored as register indexes.
+type Operand = RegisterOrValue<usize, i32>;
+
+// Representation of individual instructions.
+#[derive(Clone, Copy)]
+enum Instruction {
+    Set(usize, Operand),
+    Sub(usize, Operand),
+    Mul(usize, Operand),
+    Jnz(Operand, Operand),
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let (name, op_1, op_2) = match *self {
+            Instruction::Set(op_1, op_2) => ("set", RegisterOrValue::Register(op_1), op_2),
+            Instruction::Sub(op_1, op_2) => ("sub", RegisterOrValue::Register(op_1), op_2),
+            Instruction::Mul(op_1, op_2) => ("mul", RegisterOrValue::Register(op_1), op_2),
+            Instruction::Jnz(op_1, op_2) => ("jnz", op_1, op_2),
+        };
+        write!(
+            f,
+            "{name} {} {}",
+            op_1.map_register(letter_name),
+            op_2.map_register(letter_name)
+        )
     }
+}
+
+// The instruction set counts how many times the mul instruction is invoked.
+struct Coprocessor {
+    mul_invocations: u32,
+}
+
+impl InstructionSet for Coprocessor {
+    type Instruction = Instruction;
+    type Registers = [i32; 8];
 
-    // Predicate that says whether the given instruction will modify the value in the b or c
-    // registers.
-    fn is_writing_to_b_or_c(instruction: &Instruction) -> bool {
+    fn execute(&mut self, instruction: &Instruction, state: &mut State<[i32; 8]>) -> Control {
         match instruction {
-            Instruction::Set(op_1, _) | Instruction::Sub(op_1, _) | Instruction::Mul(op_1, _) => {
-                if let RegisterOrValue::Register(index) = op_1 {
-                    // Registers b and c have indexes 1 and 2 respectively.
-                    return *index == 1 || *index == 2;
+            Instruction::Set(op_1, op_2) => *state.register_mut(*op_1) = state.value(op_2),
+            Instruction::Sub(op_1, op_2) => *state.register_mut(*op_1) -= state.value(op_2),
+            Instruction::Mul(op_1, op_2) => {
+                *state.register_mut(*op_1) *= state.value(op_2);
+                self.mul_invocations += 1;
+            }
+            Instruction::Jnz(op_1, op_2) => {
+                if state.value(op_1) != 0 {
+                    return Control::Jump(
+                        isize::try_from(state.value(op_2))
+                            .expect("Should be able to convert to isize losslessly"),
+                    );
                 }
-                false
             }
-            // The jnz function doesn't modify any registers so always return false (in principle
-            // they might affect b and c through looping around another instruction that affects it,
-            // however since only the initial values of b and c are needed, it's assumed that once
-            // jumping is done to begin looping, the initial values of b and c have been finalized
-            // and this jnz functions can still be safely ignored).
-            Instruction::Jnz(_, _) => false,
         }
+        Control::Next
     }
+}
 
-    let mut instructions = get_instructions(input);
-    // Remove all instructions that don't modify b or c.
-    instructions.retain(is_writing_to_b_or_c);
-
-    let mut program_state = ProgramState {
-        program_counter: 0,
-        registers: [1, 0, 0, 0, 0, 0, 0, 0],
-        mul_invocations: 0,
-    };
+fn get_instructions(input: &str) -> Vec<Instruction> {
+    input
+        .lines()
+        .map(|line| {
+            let mut iter = line.split_whitespace();
+            let instruction = iter.next().expect("Line should have first value");
+            let mut operand = || iter.next().expect("Instruction should have an operand");
+            let value = |operand| RegisterOrValue::parse(operand, letter_register);
+            match instruction {
+                "set" => Instruction::Set(letter_register(operand()), value(operand())),
+                "sub" => Instruction::Sub(letter_register(operand()), value(operand())),
+                "mul" => Instruction::Mul(letter_register(operand()), value(operand())),
+                "jnz" => Instruction::Jnz(value(operand()), value(operand())),
+                _ => panic!("Invalid instruction name"),
+            }
+        })
+        .collect()
+}
 
-    let mut instruction_iter = instructions.iter();
-
-    // First take the last value off the list of instructions and use it to find the offset.
-    let last_instruction = instruction_iter
-        .next_back()
-        .expect("Instructions should have last element");
-    let offset = if let Instruction::Sub(_, op_2) = last_instruction {
-        if let RegisterOrValue::Value(value) = op_2 {
-            // value is expected to be negative as it's used in a sub instruction with the intention
-            // of adding an amount to register b, so the absolute value of it must be taken.
-            value.unsigned_abs()
-        } else {
-            panic!("Offset should be an integer literal")
-        }
-    } else {
-        panic!("Last instruction should be a sub instruction to add the offset")
-    };
+// The code that solve_2 expects the outer loop to be, from its first instruction to the end of the
+// program. The placeholder stands for the instruction that adds the step between values to b.
+const STEP_PLACEHOLDER: &str = "sub b -<step>";
+const COMPOSITE_LOOP: [&str; 24] = [
+    "set f 1",
+    "set d 2",
+    "set e 2",
+    "set g d",
+    "mul g e",
+    "sub g b",
+    "jnz g 2",
+    "set f 0",
+    "sub e -1",
+    "set g e",
+    "sub g b",
+    "jnz g -8",
+    "sub d -1",
+    "set g d",
+    "sub g b",
+    "jnz g -13",
+    "jnz f 2",
+    "sub h -1",
+    "set g b",
+    "sub g c",
+    "jnz g 2",
+    "jnz 1 3",
+    STEP_PLACEHOLDER,
+    "jnz 1 -23",
+];
 
-    // Execute all instructions except the last one (which is no longer in instruction_iter).
-    for instruction in instruction_iter {
-        instruction.execute(&mut program_state);
-    }
+// The most instructions the setup code before the outer loop may execute.
+const SETUP_STEP_LIMIT: usize = 1000;
 
-    // It's expected that these values are positive. unsigned_abs() helps convert to u32.
-    // Register b:
-    let mut range_start = program_state.registers[1].unsigned_abs();
-    // Register c:
-    let range_end = program_state.registers[2].unsigned_abs();
+// The values that a program counts the composite numbers among, and the value of h that the count
+// is added to.
+struct CompositeCount {
+    start: u64,
+    end: u64,
+    step: usize,
+    initial_h: usize,
+}
 
-    let mut values_in_range = Vec::with_capacity(1001);
+// Why a program doesn't have the shape that solve_2 expects.
+#[derive(Debug)]
+struct PatternMismatch {
+    program_counter: Option<usize>,
+    message: String,
+}
 
-    while range_start <= range_end {
-        values_in_range.push(range_start);
-        range_start += offset;
+impl Display for PatternMismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The program doesn't match the composite-counting pattern: "
+        )?;
+        if let Some(program_counter) = self.program_counter {
+            write!(f, "at instruction {program_counter}, ")?;
+        }
+        write!(f, "{}", self.message)
     }
+}
 
-    // Count composite numbers.
-    let mut composites = 0;
-    for value in values_in_range {
-        if !is_prime(value) {
-            composites += 1;
-        }
+fn mismatch(program_counter: Option<usize>, message: String) -> PatternMismatch {
+    PatternMismatch {
+        program_counter,
+        message,
     }
+}
+
+// A run of instructions that always execute one after another, since only the first can be jumped
+// to and only the last can jump.
+struct BasicBlock {
+    instructions: Range<usize>,
+    // The indexes of the instructions that can execute after this block. An index at the end of the
+    // program means the program can halt after this block.
+    successors: Vec<usize>,
+}
 
-    Solution::U32(composites)
+// A loop formed by a jump back to an earlier instruction. Every instruction from the one jumped to
+// up to and including the jump is part of the loop.
+struct Loop {
+    body: Range<usize>,
 }
 
-struct ProgramState {
+// Returns the index of the instruction that a jnz instruction jumps to. Jumps to anywhere outside
+// of the program halt it, so they all go to the index just past the end of the program.
+fn jump_target(
+    instructions: &[Instruction],
     program_counter: usize,
-    registers: [i32; 8],
-    mul_invocations: u32,
+    offset: Operand,
+) -> Result<usize, PatternMismatch> {
+    let RegisterOrValue::Value(offset) = offset else {
+        return Err(mismatch(
+            Some(program_counter),
+            "jumps should have a literal offset".to_string(),
+        ));
+    };
+    Ok(isize::try_from(offset)
+        .ok()
+        .and_then(|offset| program_counter.checked_add_signed(offset))
+        .filter(|target| *target < instructions.len())
+        .unwrap_or(instructions.len()))
 }
-// Note that unlike in day 18, it is specified that only the first 8 letters ('a' to 'h') are used.
-// This is taken advantage of so that each letter is an index into an array of 8 ints, rather than a
-// HashMap. Hence the Register enum uses a usize instead of a char here.
-enum RegisterOrValue {
-    Register(usize),
-    Value(i32),
+
+// Splits a program into basic blocks, in order. A new block starts at the beginning of the program,
+// at every instruction that can be jumped to, and after every jump.
+fn get_basic_blocks(instructions: &[Instruction]) -> Result<Vec<BasicBlock>, PatternMismatch> {
+    let mut leaders = BTreeSet::from([0, instructions.len()]);
+    for (program_counter, instruction) in instructions.iter().enumerate() {
+        if let Instruction::Jnz(_, offset) = *instruction {
+            leaders.insert(program_counter + 1);
+            leaders.insert(jump_target(instructions, program_counter, offset)?);
+        }
+    }
+
+    let mut blocks = Vec::new();
+    for (&start, &end) in leaders.iter().tuple_windows() {
+        let last = end - 1;
+        let successors = match instructions[last] {
+            // A jump's condition decides whether it can fall through, jump, or both.
+            Instruction::Jnz(condition, offset) => {
+                let target = jump_target(instructions, last, offset)?;
+                match condition {
+                    RegisterOrValue::Value(0) => vec![end],
+                    RegisterOrValue::Value(_) => vec![target],
+                    RegisterOrValue::Register(_) => vec![end, target],
+                }
+            }
+            _ => vec![end],
+        };
+        blocks.push(BasicBlock {
+            instructions: start..end,
+            successors,
+        });
+    }
+    Ok(blocks)
 }
-// Representation of individual instructions.
-enum Instruction {
-    Set(RegisterOrValue, RegisterOrValue),
-    Sub(RegisterOrValue, RegisterOrValue),
-    Mul(RegisterOrValue, RegisterOrValue),
-    Jnz(RegisterOrValue, RegisterOrValue),
+
+// Finds every loop, with loops that start earlier first, and outer loops before the loops nested
+// inside them when they start at the same place.
+fn get_loops(blocks: &[BasicBlock]) -> Vec<Loop> {
+    let mut loops = blocks
+        .iter()
+        .flat_map(|block| {
+            block
+                .successors
+                .iter()
+                .filter(|successor| **successor <= block.instructions.start)
+                .map(|successor| Loop {
+                    body: *successor..block.instructions.end,
+                })
+        })
+        .collect::<Vec<_>>();
+    loops.sort_unstable_by_key(|loop_| (loop_.body.start, Reverse(loop_.body.end)));
+    loops
 }
 
-impl RegisterOrValue {
-    // Get the value from the operand, either by directly returning it if the operand is an integer
-    // literal, or by fetching the value from the register if the operand is a register name.
-    fn get_value(&self, program_state: &ProgramState) -> i32 {
-        match self {
-            RegisterOrValue::Register(index) => program_state.registers[*index],
-            RegisterOrValue::Value(value) => *value,
-        }
+// Checks that a program has the shape described in solve_2: some setup code, followed by three
+// nested loops that count the composite numbers between b and c. The setup code is run to find the
+// initial values of the registers.
+fn find_composite_count(instructions: &[Instruction]) -> Result<CompositeCount, PatternMismatch> {
+    let blocks = get_basic_blocks(instructions)?;
+    let loops = get_loops(&blocks);
+    let [outer, middle, inner] = loops.as_slice() else {
+        return Err(mismatch(
+            None,
+            format!("expected 3 nested loops, but found {}", loops.len()),
+        ));
+    };
+    let contains = |outer: &Loop, inner: &Loop| {
+        outer.body.start <= inner.body.start && inner.body.end <= outer.body.end
+    };
+    if !contains(outer, middle) || !contains(middle, inner) {
+        return Err(mismatch(
+            Some(middle.body.start),
+            "the loops should be nested inside each other".to_string(),
+        ));
+    }
+    if outer.body.end != instructions.len() {
+        return Err(mismatch(
+            Some(outer.body.end),
+            "the outer loop should be at the end of the program".to_string(),
+        ));
     }
-    // Get a mutable reference to the value in the operand. It's assumed this operand will only be
-    // called for operands that are going to be written to (first operand of the set, sub, and mul
-    // instructions), which only makes sense when the operand is a register, so this function panics
-    // if the operand is a literal.
-    fn get_value_mut<'a>(&self, program_state: &'a mut ProgramState) -> &'a mut i32 {
-        match self {
-            RegisterOrValue::Register(index) => &mut program_state.registers[*index],
-            RegisterOrValue::Value(_) => {
-                panic!("Should not be getting a mutable reference to a literal operand")
+    if outer.body.len() != COMPOSITE_LOOP.len() {
+        return Err(mismatch(
+            Some(outer.body.start),
+            format!(
+                "the outer loop should have {} instructions, but it has {}",
+                COMPOSITE_LOOP.len(),
+                outer.body.len()
+            ),
+        ));
+    }
+
+    let mut step = None;
+    for (program_counter, expected) in outer.body.clone().zip(COMPOSITE_LOOP) {
+        let instruction = instructions[program_counter];
+        if expected == STEP_PLACEHOLDER {
+            // Subtracting a negative literal from register b adds the step to it.
+            if let Instruction::Sub(1, RegisterOrValue::Value(negative_step @ ..0)) = instruction {
+                step = Some(negative_step.unsigned_abs());
+                continue;
             }
+        } else if instruction.to_string() == expected {
+            continue;
         }
+        return Err(mismatch(
+            Some(program_counter),
+            format!("expected \"{expected}\", but found \"{instruction}\""),
+        ));
     }
+    let step = step.expect("Step placeholder should be in the composite loop");
+
+    // Run the setup code with register a set to 1 until it reaches the outer loop.
+    let mut machine = Machine::new(
+        Coprocessor { mul_invocations: 0 },
+        instructions.to_vec(),
+        [1, 0, 0, 0, 0, 0, 0, 0],
+    );
+    let mut steps = 0;
+    while machine.state.program_counter != outer.body.start {
+        if steps == SETUP_STEP_LIMIT || machine.step() == Status::Halted {
+            return Err(mismatch(
+                None,
+                format!(
+                    "the setup code should reach the outer loop within {SETUP_STEP_LIMIT} steps"
+                ),
+            ));
+        }
+        steps += 1;
+    }
+
+    let [_, b, c, _, _, _, _, h] = machine.state.registers;
+    let (Ok(start), Ok(end), Ok(initial_h)) =
+        (u64::try_from(b), u64::try_from(c), usize::try_from(h))
+    else {
+        return Err(mismatch(
+            None,
+            format!("b, c, and h should start non-negative, but they are {b}, {c}, and {h}"),
+        ));
+    };
+    // b only ever increases by the step, so the program only halts if b eventually equals c.
+    if start > end || !(end - start).is_multiple_of(u64::from(step)) {
+        return Err(mismatch(
+            None,
+            format!("b should reach c in steps of {step}, but b is {b} and c is {c}"),
+        ));
+    }
+    // The inner loops count d and e up from 2 until they equal b, which never happens for a b
+    // below 2.
+    if start < 2 {
+        return Err(mismatch(
+            None,
+            format!("b should start at 2 or more, but it is {b}"),
+        ));
+    }
+
+    Ok(CompositeCount {
+        start,
+        end,
+        step: usize::try_from(step).expect("Step should fit in a usize"),
+        initial_h,
+    })
 }
 
-impl Instruction {
-    // Instruction mappings for part 1.
-    fn execute(&self, program_state: &mut ProgramState) {
-        match self {
-            Instruction::Set(op_1, op_2) => {
-                let op_2 = op_2.get_value(program_state);
-                let op_1 = op_1.get_value_mut(program_state);
-                *op_1 = op_2;
-                program_state.program_counter += 1;
-            }
-            Instruction::Sub(op_1, op_2) => {
-                let op_2 = op_2.get_value(program_state);
-                let op_1 = op_1.get_value_mut(program_state);
-                *op_1 -= op_2;
-                program_state.program_counter += 1;
-            }
-            Instruction::Mul(op_1, op_2) => {
-                let op_2 = op_2.get_value(program_state);
-                let op_1 = op_1.get_value_mut(program_state);
-                *op_1 *= op_2;
-                program_state.program_counter += 1;
-                program_state.mul_invocations += 1;
-            }
-            Instruction::Jnz(op_1, op_2) => {
-                let op_1 = op_1.get_value(program_state);
-                if op_1 != 0 {
-                    let op_2 = op_2.get_value(program_state);
-                    if op_2.is_negative() {
-                        program_state.program_counter -= usize::try_from(op_2.unsigned_abs())
-                            .expect("Should be able to convert to usize losslessly");
+// Writes a program as pseudo-code, with each basic block labelled by the index of its first
+// instruction and indented by the number of loops it's inside. This is followed by whether the
+// program matches the pattern that solve_2 expects.
+pub fn disassemble(input: &str, output: &mut dyn Write) -> io::Result<()> {
+    let instructions = get_instructions(input);
+    let blocks = get_basic_blocks(&instructions)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+    let loops = get_loops(&blocks);
+
+    for block in &blocks {
+        let start = block.instructions.start;
+        let depth = loops
+            .iter()
+            .filter(|loop_| loop_.body.contains(&start))
+            .count();
+        write!(
+            output,
+            "{}L{start}:",
+            "    ".repeat(depth.saturating_sub(1))
+        )?;
+        for loop_ in loops.iter().filter(|loop_| loop_.body.start == start) {
+            write!(output, " // loop, repeated from {}", loop_.body.end - 1)?;
+        }
+        writeln!(output)?;
+
+        let indent = "    ".repeat(depth.max(1));
+        for program_counter in block.instructions.clone() {
+            let statement = match instructions[program_counter] {
+                Instruction::Set(op_1, op_2) => {
+                    format!("{} = {}", letter_name(op_1), op_2.map_register(letter_name))
+                }
+                Instruction::Sub(op_1, RegisterOrValue::Value(value @ ..0)) => {
+                    format!("{} += {}", letter_name(op_1), value.unsigned_abs())
+                }
+                Instruction::Sub(op_1, op_2) => {
+                    format!(
+                        "{} -= {}",
+                        letter_name(op_1),
+                        op_2.map_register(letter_name)
+                    )
+                }
+                Instruction::Mul(op_1, op_2) => {
+                    format!(
+                        "{} *= {}",
+                        letter_name(op_1),
+                        op_2.map_register(letter_name)
+                    )
+                }
+                Instruction::Jnz(condition, offset) => {
+                    let target = jump_target(&instructions, program_counter, offset)
+                        .expect("Jumps should have been checked by get_basic_blocks");
+                    let jump = if target == instructions.len() {
+                        "halt".to_string()
                     } else {
-                        program_state.program_counter += usize::try_from(op_2)
-                            .expect("Should be able to convert to usize losslessly");
+                        format!("goto L{target}")
+                    };
+                    match condition {
+                        RegisterOrValue::Value(0) => "nop".to_string(),
+                        RegisterOrValue::Value(_) => jump,
+                        RegisterOrValue::Register(register) => {
+                            format!("if {} != 0 {jump}", letter_name(register))
+                        }
                     }
-                } else {
-                    program_state.program_counter += 1;
                 }
-            }
+            };
+            writeln!(output, "{indent}{statement}")?;
         }
     }
-}
 
-fn get_instructions(input: &str) -> Vec<Instruction> {
-    // Helper function to extract an operand.
-    fn get_operand(iter: &mut SplitWhitespace) -> RegisterOrValue {
-        let op = iter.next().expect("Line should have third value");
-
-        if let Ok(value) = op.parse() {
-            RegisterOrValue::Value(value)
-        } else {
-            let mut index = op
-                .chars()
-                .next()
-                .expect("Operand should be a number or single character")
-                as usize;
-            // ASCII value of 'a' is 97, so subtracting 97 gives an index from 0 to 7 (7
-            // corresponding to 'h' with ASCII value 104).
-            index -= 97;
-
-            RegisterOrValue::Register(index)
-        }
+    match find_composite_count(&instructions) {
+        Ok(composite_count) => writeln!(
+            output,
+            "// Sets h to {} plus the number of composite numbers from {} to {} in steps of {}",
+            composite_count.initial_h,
+            composite_count.start,
+            composite_count.end,
+            composite_count.step
+        ),
+        Err(error) => writeln!(output, "// {error}"),
     }
+}
 
-    let mut instructions = Vec::new();
+#[cfg(test)]
+mod test {
+    use super::*;
 
-    for line in input.lines() {
-        let mut iter = line.split_whitespace();
-        let instruction = iter.next().expect("Line should have first value");
-        let instruction = match instruction {
-            "set" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Set(op_1, op_2)
-            }
-            "sub" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Sub(op_1, op_2)
-            }
-            "mul" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Mul(op_1, op_2)
-            }
-            "jnz" => {
-                let op_1 = get_operand(&mut iter);
-                let op_2 = get_operand(&mut iter);
-                Instruction::Jnz(op_1, op_2)
-            }
-            _ => panic!("Invalid instruction name"),
-        };
-        instructions.push(instruction);
+    // A program with the same shape as a puzzle input, but with a much smaller range of values so
+    // that it can be run to completion.
+    const SMALL_PROGRAM: &str = "\
+set b 84
+set c b
+jnz a 2
+jnz 1 5
+mul b 1
+sub b -0
+set c b
+sub c -170
+set f 1
+set d 2
+set e 2
+set g d
+mul g e
+sub g b
+jnz g 2
+set f 0
+sub e -1
+set g e
+sub g b
+jnz g -8
+sub d -1
+set g d
+sub g b
+jnz g -13
+jnz f 2
+sub h -1
+set g b
+sub g c
+jnz g 2
+jnz 1 3
+sub b -17
+jnz 1 -23";
+
+    #[test]
+    fn composite_pattern() {
+        let mut machine = Machine::new(
+            Coprocessor { mul_invocations: 0 },
+            get_instructions(SMALL_PROGRAM),
+            [1, 0, 0, 0, 0, 0, 0, 0],
+        );
+        machine.run();
+        let h = usize::try_from(machine.state.registers[7]).expect("h should be positive");
+        assert_eq!(solve_2(SMALL_PROGRAM), Solution::USize(h));
+
+        let mut pseudo_code = Vec::new();
+        disassemble(SMALL_PROGRAM, &mut pseudo_code).expect("Writing should succeed");
+        let pseudo_code = String::from_utf8(pseudo_code).expect("Pseudo-code should be UTF-8");
+        assert!(pseudo_code.contains("L8: // loop, repeated from 31\n"));
+        assert!(pseudo_code.contains("        L11: // loop, repeated from 19\n"));
+        assert!(pseudo_code.contains("            if g != 0 goto L11\n"));
+        assert!(pseudo_code.ends_with("from 84 to 254 in steps of 17\n"));
     }
 
-    instructions
-}
+    #[test]
+    fn pattern_mismatch() {
+        let program = SMALL_PROGRAM.replace("set f 0", "set f 2");
+        let error = find_composite_count(&get_instructions(&program))
+            .err()
+            .expect("Program shouldn't match the pattern");
+        assert_eq!(error.program_counter, Some(15));
+        assert!(
+            error
+                .to_string()
+                .contains("expected \"set f 0\", but found \"set f 2\"")
+        );
 
-// The puzzle description provides no examples for this puzzle.
+        let program = SMALL_PROGRAM.replace("jnz g -8", "jnz g 1");
+        let error = find_composite_count(&get_instructions(&program))
+            .err()
+            .expect("Program shouldn't match the pattern");
+        assert!(
+            error
+                .to_string()
+                .contains("expected 3 nested loops, but found 2")
+        );
+
+        let program = SMALL_PROGRAM.replace("set b 84", "set b 1");
+        let error = find_composite_count(&get_instructions(&program))
+            .err()
+            .expect("Program shouldn't halt for b below 2");
+        assert!(
+            error
+                .to_string()
+                .contains("b should start at 2 or more, but it is 1")
+        );
+    }
+}
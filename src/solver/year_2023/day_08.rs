@@ -1,3 +1,4 @@
+use crate::solver::common::math::lcm;
 use crate::solver::{AdventOfCode, Solution};
 use rustc_hash::FxHashMap;
 
@@ -119,20 +120,6 @@ fn solve_2(input: &str) -> Solution {
     // problem from counting steps to determining when the loop cycles all line up again. This will
     // occur on the lowest common multiple of the length of all individual loops.
 
-    fn gcd(mut a: u64, mut b: u64) -> u64 {
-        while b != 0 {
-            let temp = b;
-            b = a % b;
-            a = temp;
-        }
-
-        a
-    }
-
-    fn lcm(a: u64, b: u64) -> u64 {
-        a * b / gcd(a, b)
-    }
-
     let (directions, node_map) = get_directions_and_node_map(input);
 
     let step_count =
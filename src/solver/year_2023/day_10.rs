@@ -1,3 +1,4 @@
+use crate::solver::common::geometry::Direction;
 use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -7,14 +8,6 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
     part_solvers: &[solve_1],
 };
 
-#[derive(Debug)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
 #[derive(Clone, Copy, Debug)]
 enum PipeShape {
     Vertical,
@@ -1,3 +1,4 @@
+use crate::solver::common::grid::Grid;
 use crate::solver::{AdventOfCode, Solution};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -8,87 +9,37 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let letters = get_letters(input);
+    // The eight directions the word "XMAS" can be written in, as an x and y offset per letter.
+    const DIRECTIONS: [(isize, isize); 8] = [
+        (-1, -1),
+        (0, -1),
+        (1, -1),
+        (-1, 0),
+        (1, 0),
+        (-1, 1),
+        (0, 1),
+        (1, 1),
+    ];
 
-    // Iterate over every character in the letters array. Ignore all characters other than 'X'. When
+    let letters = Grid::parse(input, |character| character);
+
+    // Iterate over every character in the letters grid. Ignore all characters other than 'X'. When
     // an 'X' is found, check in all 8 directions from that 'X' if the rest of the word "XMAS" is in
     // that direction.
     let mut xmas_appearances = 0;
-    let x_len = letters[0].len();
-    let y_len = letters.len();
-    for (y, letter_line) in letters.iter().enumerate() {
-        for (x, letter) in letter_line.iter().enumerate() {
-            if *letter == 'X' {
-                // Only search up-left, up, and up-right if y >= 3 (as otherwise there isn't enough
-                // room for the full word in any of those directions)
-                if y >= 3 {
-                    // Search up-left.
-                    if x >= 3
-                        && letters[y - 1][x - 1] == 'M'
-                        && letters[y - 2][x - 2] == 'A'
-                        && letters[y - 3][x - 3] == 'S'
-                    {
-                        xmas_appearances += 1;
-                    }
-                    // Search up.
-                    if letters[y - 1][x] == 'M'
-                        && letters[y - 2][x] == 'A'
-                        && letters[y - 3][x] == 'S'
-                    {
-                        xmas_appearances += 1;
-                    }
-                    // Search up-right.
-                    if x + 3 < x_len
-                        && letters[y - 1][x + 1] == 'M'
-                        && letters[y - 2][x + 2] == 'A'
-                        && letters[y - 3][x + 3] == 'S'
-                    {
-                        xmas_appearances += 1;
-                    }
-                }
-                // Search left.
-                if x >= 3
-                    && letters[y][x - 1] == 'M'
-                    && letters[y][x - 2] == 'A'
-                    && letters[y][x - 3] == 'S'
-                {
+    for (position, letter) in letters.iter() {
+        if *letter == 'X' {
+            for (dx, dy) in DIRECTIONS {
+                // The offset method returns None if the position is outside the grid, which
+                // happens when there isn't enough room for the full word in this direction.
+                let is_xmas = "MAS".chars().zip(1..).all(|(expected_letter, distance)| {
+                    letters
+                        .offset(position, (dx * distance, dy * distance))
+                        .is_some_and(|letter_position| letters[letter_position] == expected_letter)
+                });
+                if is_xmas {
                     xmas_appearances += 1;
                 }
-                // Search right.
-                if x + 3 < x_len
-                    && letters[y][x + 1] == 'M'
-                    && letters[y][x + 2] == 'A'
-                    && letters[y][x + 3] == 'S'
-                {
-                    xmas_appearances += 1;
-                }
-                // Only search down-left, down, and down-right if y + 3 < y_len (as otherwise there isn't enough
-                // room for the full word in any of those directions)
-                if y + 3 < y_len {
-                    // Search down-left.
-                    if x >= 3
-                        && letters[y + 1][x - 1] == 'M'
-                        && letters[y + 2][x - 2] == 'A'
-                        && letters[y + 3][x - 3] == 'S'
-                    {
-                        xmas_appearances += 1;
-                    }
-                    // Search down.
-                    if letters[y + 1][x] == 'M'
-                        && letters[y + 2][x] == 'A'
-                        && letters[y + 3][x] == 'S'
-                    {
-                        xmas_appearances += 1;
-                    }
-                    // Search down-right.
-                    if x + 3 < x_len
-                        && letters[y + 1][x + 1] == 'M'
-                        && letters[y + 2][x + 2] == 'A'
-                        && letters[y + 3][x + 3] == 'S'
-                    {
-                        xmas_appearances += 1;
-                    }
-                }
             }
         }
     }
@@ -97,9 +48,9 @@ fn solve_1(input: &str) -> Solution {
 }
 
 fn solve_2(input: &str) -> Solution {
-    let letters = get_letters(input);
+    let letters = Grid::parse(input, |character| character);
 
-    // Iterate over every character in the letters array, except for those in the first row, last
+    // Iterate over every character in the letters grid, except for those in the first row, last
     // row, first column, or last column as the center of an X-MAS cannot appear on the edges.
     // Ignore all characters other than 'A'. When an 'A' is found, check the four
     // diagonally-adjacent characters to see if they consist of exactly two 'M' and two 'S', and
@@ -107,48 +58,31 @@ fn solve_2(input: &str) -> Solution {
     // lower-left characters aren't both 'M' to avoid any "MAM" patterns (a "SAS" pattern only
     // exists alongside a "MAM" pattern so there is no need to check for "SAS" patterns too).
     let mut cross_mas_appearances = 0;
-    let x_len = letters[0].len();
-    let y_len = letters.len();
-    for (y, letter_line) in letters.iter().enumerate().skip(1) {
-        if y + 1 == y_len {
-            break;
-        }
-        for (x, letter) in letter_line.iter().enumerate().skip(1) {
-            if x + 1 == x_len {
-                break;
-            }
-            if *letter == 'A' {
-                // By skipping the edges of the array, bounds checks are not required.
+    for y in 1..letters.height().saturating_sub(1) {
+        for x in 1..letters.width().saturating_sub(1) {
+            if letters[(x, y)] == 'A' {
+                // By skipping the edges of the grid, bounds checks are not required.
+                let upper_left = letters[(x - 1, y - 1)];
+                let upper_right = letters[(x + 1, y - 1)];
+                let lower_left = letters[(x - 1, y + 1)];
+                let lower_right = letters[(x + 1, y + 1)];
+
                 let mut m_count = 0;
                 let mut s_count = 0;
-
-                match letters[y - 1][x - 1] {
-                    'M' => m_count += 1,
-                    'S' => s_count += 1,
-                    // If an 'A' or 'X' is ever encountered, there's no way for this to be an X-MAS,
-                    // so skip to the next letter.
-                    _ => continue,
-                }
-                match letters[y - 1][x + 1] {
-                    'M' => m_count += 1,
-                    'S' => s_count += 1,
-                    _ => continue,
-                }
-                match letters[y + 1][x - 1] {
-                    'M' => m_count += 1,
-                    'S' => s_count += 1,
-                    _ => continue,
-                }
-                match letters[y + 1][x + 1] {
-                    'M' => m_count += 1,
-                    'S' => s_count += 1,
-                    _ => continue,
+                for letter in [upper_left, upper_right, lower_left, lower_right] {
+                    match letter {
+                        'M' => m_count += 1,
+                        'S' => s_count += 1,
+                        // If an 'A' or 'X' is ever encountered, there's no way for this to be an
+                        // X-MAS.
+                        _ => {}
+                    }
                 }
 
                 if m_count == 2
                     && s_count == 2
-                    && !(letters[y - 1][x - 1] == 'M' && letters[y + 1][x + 1] == 'M')
-                    && !(letters[y - 1][x + 1] == 'M' && letters[y + 1][x - 1] == 'M')
+                    && !(upper_left == 'M' && lower_right == 'M')
+                    && !(upper_right == 'M' && lower_left == 'M')
                 {
                     cross_mas_appearances += 1;
                 }
@@ -159,20 +93,6 @@ fn solve_2(input: &str) -> Solution {
     Solution::U32(cross_mas_appearances)
 }
 
-// Create a vector of vectors (an array) to store each character in its original position.
-fn get_letters(input: &str) -> Vec<Vec<char>> {
-    let mut letters = Vec::new();
-    for line in input.lines() {
-        let mut letters_line = Vec::new();
-        for character in line.chars() {
-            letters_line.push(character);
-        }
-        letters.push(letters_line);
-    }
-
-    letters
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,4 +1,5 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::geometry::Point2;
+use crate::solver::{AdventOfCode, Solution};
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
 
@@ -22,25 +23,18 @@ fn solve_1(input: &str) -> Solution {
         for (antenna_1, antenna_2) in antennas_with_frequency.iter().tuple_combinations() {
             // Get the difference between the first and second position (as a mathematical vector
             // pointing from the first position to the second position).
-            let dx = antenna_2.x - antenna_1.x;
-            let dy = antenna_2.y - antenna_1.y;
+            let difference = *antenna_2 - *antenna_1;
             // The difference points from the first position to the second position, so the two
             // antinodes are obtained by subtracting the difference from the first position and
             // adding it to the second position.
-            let antinode_1 = Position {
-                x: antenna_1.x - dx,
-                y: antenna_1.y - dy,
-            };
-            let antinode_2 = Position {
-                x: antenna_2.x + dx,
-                y: antenna_2.y + dy,
-            };
+            let antinode_1 = *antenna_1 - difference;
+            let antinode_2 = *antenna_2 + difference;
             // For each antinode, if it is in bounds, add it to the set of antinodes. There is no
             // need to check for duplicate values because sets only allow unique values.
-            if antinode_1.is_within_bounds(x_len, y_len) {
+            if is_within_bounds(antinode_1, x_len, y_len) {
                 antinodes.insert(antinode_1);
             }
-            if antinode_2.is_within_bounds(x_len, y_len) {
+            if is_within_bounds(antinode_2, x_len, y_len) {
                 antinodes.insert(antinode_2);
             }
         }
@@ -68,8 +62,7 @@ fn solve_2(input: &str) -> Solution {
         // Frequencies that only have a single antenna will be skipped as they have zero pairs to
         // iterate over, thus no antinodes will be assigned to such antennas.
         for (antenna_1, antenna_2) in antennas_with_frequency.iter().tuple_combinations() {
-            let dx = antenna_2.x - antenna_1.x;
-            let dy = antenna_2.y - antenna_1.y;
+            let difference = *antenna_2 - *antenna_1;
 
             // The difference vector is multiplied by the number of loop repetitions until an
             // out-of-bounds antinode is found to get antinodes at every resonant frequency. By
@@ -77,11 +70,8 @@ fn solve_2(input: &str) -> Solution {
             // antennas.
             let mut repetitions = 0;
             loop {
-                let antinode = Position {
-                    x: antenna_1.x - (dx * repetitions),
-                    y: antenna_1.y - (dy * repetitions),
-                };
-                if antinode.is_within_bounds(x_len, y_len) {
+                let antinode = *antenna_1 - difference * repetitions;
+                if is_within_bounds(antinode, x_len, y_len) {
                     antinodes.insert(antinode);
                     repetitions += 1;
                 } else {
@@ -91,11 +81,8 @@ fn solve_2(input: &str) -> Solution {
 
             repetitions = 0;
             loop {
-                let antinode = Position {
-                    x: antenna_2.x + (dx * repetitions),
-                    y: antenna_2.y + (dy * repetitions),
-                };
-                if antinode.is_within_bounds(x_len, y_len) {
+                let antinode = *antenna_2 + difference * repetitions;
+                if is_within_bounds(antinode, x_len, y_len) {
                     antinodes.insert(antinode);
                     repetitions += 1;
                 } else {
@@ -115,18 +102,13 @@ fn solve_2(input: &str) -> Solution {
     Solution::USize(antinodes.len())
 }
 
-#[derive(PartialEq, Eq, Hash)]
-struct Position {
-    x: isize,
-    y: isize,
-}
-impl Position {
-    // It is assumed that the puzzle input isn't big enough to cause issues with converting usize to
-    // isize (it will only wrap around if the input grid has isize::MAX rows or columns).
-    #[allow(clippy::cast_possible_wrap)]
-    fn is_within_bounds(&self, x_len: usize, y_len: usize) -> bool {
-        self.x >= 0 && self.y >= 0 && self.x < x_len as isize && self.y < y_len as isize
-    }
+type Position = Point2<isize>;
+
+// It is assumed that the puzzle input isn't big enough to cause issues with converting usize to
+// isize (it will only wrap around if the input grid has isize::MAX rows or columns).
+#[allow(clippy::cast_possible_wrap)]
+fn is_within_bounds(position: Position, x_len: usize, y_len: usize) -> bool {
+    position.x >= 0 && position.y >= 0 && position.x < x_len as isize && position.y < y_len as isize
 }
 
 fn get_antennas_and_lens(input: &str) -> (FxHashMap<char, Vec<Position>>, usize, usize) {
@@ -143,10 +125,7 @@ fn get_antennas_and_lens(input: &str) -> (FxHashMap<char, Vec<Position>>, usize,
                 antennas
                     .entry(character)
                     .or_insert(Vec::new())
-                    .push(Position {
-                        x: x as isize,
-                        y: y as isize,
-                    });
+                    .push(Point2::new(x as isize, y as isize));
             }
         }
     }
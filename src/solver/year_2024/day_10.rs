@@ -1,4 +1,5 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::grid::Grid;
+use crate::solver::{AdventOfCode, Solution};
 use rustc_hash::FxHashSet;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -13,50 +14,20 @@ fn solve_1(input: &str) -> Solution {
     // to check if their height is one greater. When a height of 9 is found, the position is
     // inserted into a hash set of found positions at that height (hash sets ensure no duplicates)
     fn find_hiking_trails(
-        topographic_map: &[Vec<u32>],
-        position: &Position,
+        topographic_map: &Grid<u32>,
+        position: (usize, usize),
         current_height: u32,
-        found_nine_heights: &mut FxHashSet<Position>,
+        found_nine_heights: &mut FxHashSet<(usize, usize)>,
     ) {
-        let mut next_positions = Vec::with_capacity(4);
-        if position.x > 0 {
-            next_positions.push(Position {
-                x: position.x - 1,
-                y: position.y,
-            });
-        }
-        if position.y > 0 {
-            next_positions.push(Position {
-                x: position.x,
-                y: position.y - 1,
-            });
-        }
-        if position.x + 1 < topographic_map[0].len() {
-            next_positions.push(Position {
-                x: position.x + 1,
-                y: position.y,
-            });
-        }
-        if position.y + 1 < topographic_map.len() {
-            next_positions.push(Position {
-                x: position.x,
-                y: position.y + 1,
-            });
-        }
-
         let next_height = current_height + 1;
-        if next_height == 9 {
-            for next_position in next_positions {
-                if topographic_map[next_position.y][next_position.x] == 9 {
+        for next_position in topographic_map.neighbours_4(position) {
+            if topographic_map[next_position] == next_height {
+                if next_height == 9 {
                     found_nine_heights.insert(next_position);
-                }
-            }
-        } else {
-            for next_position in next_positions {
-                if topographic_map[next_position.y][next_position.x] == next_height {
+                } else {
                     find_hiking_trails(
                         topographic_map,
-                        &next_position,
+                        next_position,
                         next_height,
                         found_nine_heights,
                     );
@@ -70,18 +41,11 @@ fn solve_1(input: &str) -> Solution {
     // them to the score.
     let topographic_map = get_topographic_map(input);
     let mut score = 0;
-    for (y, topographic_map_line) in topographic_map.iter().enumerate() {
-        for (x, height) in topographic_map_line.iter().enumerate() {
-            if *height == 0 {
-                let mut found_nine_heights = FxHashSet::default();
-                find_hiking_trails(
-                    &topographic_map,
-                    &Position { x, y },
-                    0,
-                    &mut found_nine_heights,
-                );
-                score += found_nine_heights.len();
-            }
+    for (position, height) in topographic_map.iter() {
+        if *height == 0 {
+            let mut found_nine_heights = FxHashSet::default();
+            find_hiking_trails(&topographic_map, position, 0, &mut found_nine_heights);
+            score += found_nine_heights.len();
         }
     }
 
@@ -93,48 +57,18 @@ fn solve_2(input: &str) -> Solution {
     // to check if their height is one greater. When a height of 9 is found, the score is
     // incremented by 1.
     fn find_hiking_trails(
-        topographic_map: &[Vec<u32>],
-        position: &Position,
+        topographic_map: &Grid<u32>,
+        position: (usize, usize),
         current_height: u32,
     ) -> u32 {
-        let mut next_positions = Vec::with_capacity(4);
-        if position.x > 0 {
-            next_positions.push(Position {
-                x: position.x - 1,
-                y: position.y,
-            });
-        }
-        if position.y > 0 {
-            next_positions.push(Position {
-                x: position.x,
-                y: position.y - 1,
-            });
-        }
-        if position.x + 1 < topographic_map[0].len() {
-            next_positions.push(Position {
-                x: position.x + 1,
-                y: position.y,
-            });
-        }
-        if position.y + 1 < topographic_map.len() {
-            next_positions.push(Position {
-                x: position.x,
-                y: position.y + 1,
-            });
-        }
-
         let mut score = 0;
         let next_height = current_height + 1;
-        if next_height == 9 {
-            for next_position in next_positions {
-                if topographic_map[next_position.y][next_position.x] == 9 {
+        for next_position in topographic_map.neighbours_4(position) {
+            if topographic_map[next_position] == next_height {
+                if next_height == 9 {
                     score += 1;
-                }
-            }
-        } else {
-            for next_position in next_positions {
-                if topographic_map[next_position.y][next_position.x] == next_height {
-                    score += find_hiking_trails(topographic_map, &next_position, next_height);
+                } else {
+                    score += find_hiking_trails(topographic_map, next_position, next_height);
                 }
             }
         }
@@ -146,37 +80,21 @@ fn solve_2(input: &str) -> Solution {
     // of them to the score.
     let topographic_map = get_topographic_map(input);
     let mut score = 0;
-    for (y, topographic_map_line) in topographic_map.iter().enumerate() {
-        for (x, height) in topographic_map_line.iter().enumerate() {
-            if *height == 0 {
-                score += find_hiking_trails(&topographic_map, &Position { x, y }, 0);
-            }
+    for (position, height) in topographic_map.iter() {
+        if *height == 0 {
+            score += find_hiking_trails(&topographic_map, position, 0);
         }
     }
 
     Solution::U32(score)
 }
 
-#[derive(PartialEq, Eq, Hash)]
-struct Position {
-    x: usize,
-    y: usize,
-}
-
-fn get_topographic_map(input: &str) -> Vec<Vec<u32>> {
-    let mut topographic_map = Vec::new();
-    for line in input.lines() {
-        let mut topographic_map_line = Vec::new();
-        for digit in line.chars().map(|character| {
-            character
-                .to_digit(10)
-                .expect("Every character should be a digit from 0 to 9")
-        }) {
-            topographic_map_line.push(digit);
-        }
-        topographic_map.push(topographic_map_line);
-    }
-    topographic_map
+fn get_topographic_map(input: &str) -> Grid<u32> {
+    Grid::parse(input, |character| {
+        character
+            .to_digit(10)
+            .expect("Every character should be a digit from 0 to 9")
+    })
 }
 
 #[cfg(test)]
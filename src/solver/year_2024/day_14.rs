@@ -1,4 +1,5 @@
-use crate::solver::{Solution, AdventOfCode};
+use crate::solver::common::geometry::Point2;
+use crate::solver::{AdventOfCode, Solution};
 use rustc_hash::FxHashSet;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
@@ -95,12 +96,12 @@ fn solve_2(input: &str) -> Solution {
     fn print_robot_formation(robots: &Vec<Robot>, width: i32, height: i32) {
         let mut positions = FxHashSet::default();
         for robot in robots {
-            positions.insert(robot.position.clone());
+            positions.insert(robot.position);
         }
 
         for y in 0..height {
             for x in 0..width {
-                if positions.contains(&Vector { x, y }) {
+                if positions.contains(&Point2 { x, y }) {
                     print!("*");
                 } else {
                     print!(" ");
@@ -121,7 +122,7 @@ fn solve_2(input: &str) -> Solution {
         // Get every unique robot position.
         let mut positions = FxHashSet::default();
         for robot in &robots {
-            positions.insert(robot.position.clone());
+            positions.insert(robot.position);
         }
 
         // Get the distribution of robot positions among both the x and y axes, as well as the count
@@ -166,22 +167,6 @@ fn solve_2(input: &str) -> Solution {
     }
 }
 
-// A two-dimensional mathematical vector, not to be confused with Rust's Vec type.
-#[derive(PartialEq, Eq, Hash, Clone)]
-struct Vector {
-    x: i32,
-    y: i32,
-}
-impl Vector {
-    fn wrap(&mut self, width: i32, height: i32) {
-        // The % operator calculates the remainder, which is not desired here as we want negative
-        // numbers to immediately wrap around to the maximum value. The rem_euclid function
-        // calculates the modulus, which has the desired wrapping behaviour.
-        self.x = self.x.rem_euclid(width);
-        self.y = self.y.rem_euclid(height);
-    }
-}
-
 enum Quadrant {
     TopRight,
     BottomRight,
@@ -190,8 +175,8 @@ enum Quadrant {
 }
 
 struct Robot {
-    position: Vector,
-    velocity: Vector,
+    position: Point2<i32>,
+    velocity: Point2<i32>,
 }
 impl Robot {
     fn new(line: &str) -> Robot {
@@ -229,11 +214,11 @@ impl Robot {
             .expect("Second value should be a number");
 
         Robot {
-            position: Vector {
+            position: Point2 {
                 x: position_x,
                 y: position_y,
             },
-            velocity: Vector {
+            velocity: Point2 {
                 x: velocity_x,
                 y: velocity_y,
             },
@@ -242,9 +227,12 @@ impl Robot {
 
     // Move for one second, teleporting at the edges of the room.
     fn walk(&mut self, width: i32, height: i32) {
-        self.position.x += self.velocity.x;
-        self.position.y += self.velocity.y;
-        self.position.wrap(width, height);
+        self.position += self.velocity;
+        // The % operator calculates the remainder, which is not desired here as we want negative
+        // numbers to immediately wrap around to the maximum value. The rem_euclid function
+        // calculates the modulus, which has the desired wrapping behaviour.
+        self.position.x = self.position.x.rem_euclid(width);
+        self.position.y = self.position.y.rem_euclid(height);
     }
 
     // Return the quadrant the robot is in, or None if the robot is not in a quadrant (exactly in
@@ -1,5 +1,11 @@
+use crate::solver::common::vm::trace::Tracer;
+use crate::solver::common::vm::{
+    Control, InstructionSet, Machine, RegisterOrValue, State, letter_name,
+};
 use crate::solver::{AdventOfCode, Solution};
 use itertools::Itertools;
+use std::fmt::{self, Display, Formatter};
+use std::io;
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2024,
@@ -9,343 +15,228 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    struct Registers {
-        a: u32,
-        b: u32,
-        c: u32,
-    }
+    let (registers, program) = get_registers_and_program(input);
+    let outputs = execute(&program, registers);
 
-    // A program value represents one of the three-bit numbers in a program. It contains several
-    // methods for either converting a u32 into a new program value, or reading the program value as
-    // an operand.
-    enum ProgramValue {
-        Zero,
-        One,
-        Two,
-        Three,
-        Four,
-        Five,
-        Six,
-        Seven,
-    }
-    impl ProgramValue {
-        fn new(value: &str) -> ProgramValue {
-            match value {
-                "0" => ProgramValue::Zero,
-                "1" => ProgramValue::One,
-                "2" => ProgramValue::Two,
-                "3" => ProgramValue::Three,
-                "4" => ProgramValue::Four,
-                "5" => ProgramValue::Five,
-                "6" => ProgramValue::Six,
-                "7" => ProgramValue::Seven,
-                _ => panic!("Value was not a number from 0 to 7"),
-            }
-        }
-        fn read_literal_operand(&self) -> u32 {
-            match self {
-                ProgramValue::Zero => 0,
-                ProgramValue::One => 1,
-                ProgramValue::Two => 2,
-                ProgramValue::Three => 3,
-                ProgramValue::Four => 4,
-                ProgramValue::Five => 5,
-                ProgramValue::Six => 6,
-                ProgramValue::Seven => 7,
-            }
-        }
-        fn read_combo_operand(&self, registers: &Registers) -> u32 {
-            match self {
-                ProgramValue::Zero => 0,
-                ProgramValue::One => 1,
-                ProgramValue::Two => 2,
-                ProgramValue::Three => 3,
-                ProgramValue::Four => registers.a,
-                ProgramValue::Five => registers.b,
-                ProgramValue::Six => registers.c,
-                ProgramValue::Seven => {
-                    panic!("7 does not appear as a combo operand in valid programs")
-                }
-            }
+    // Convert the output integers into a string of digits separated by commas.
+    let output_string = outputs.into_iter().join(",");
+    Solution::String(output_string)
+}
+
+// Registers a, b, and c have indexes 0, 1, and 2 respectively.
+type Registers = [u64; 3];
+
+// Each instruction is made of an opcode and the operand that follows it. Jumps can go to any index
+// in the program, so there's an instruction starting at every value except the last (which has no
+// operand, so the program halts if it reaches it). The program counter then moves forward by 2 to
+// skip past the operand after executing most instructions.
+#[derive(Clone, Copy)]
+struct Instruction {
+    opcode: u8,
+    operand: u8,
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // Show combo operands that read registers by the register's name.
+        let combo_operand = || match self.operand {
+            7 => "?".to_string(),
+            operand => combo_operand(operand).map_register(letter_name).to_string(),
+        };
+        match self.opcode {
+            0 => write!(f, "adv {}", combo_operand()),
+            1 => write!(f, "bxl {}", self.operand),
+            2 => write!(f, "bst {}", combo_operand()),
+            3 => write!(f, "jnz {}", self.operand),
+            4 => write!(f, "bxc"),
+            5 => write!(f, "out {}", combo_operand()),
+            6 => write!(f, "bdv {}", combo_operand()),
+            7 => write!(f, "cdv {}", combo_operand()),
+            _ => write!(f, "??? {}", self.operand),
         }
     }
+}
 
-    // Given the opcode, operand, and current register values, execute the instruction corresponding
-    // to the opcode. This may mutate the instruction pointer and the outputs, so also provide those
-    // as mutable arguments.
-    fn execute(
-        opcode: &ProgramValue,
-        operand: &ProgramValue,
-        registers: &mut Registers,
-        instruction_pointer: &mut usize,
-        outputs: &mut Vec<u32>,
-    ) {
-        match opcode {
-            ProgramValue::Zero => {
-                // adv
-                let operand_value = operand.read_combo_operand(registers);
-                registers.a /= 2_u32.pow(operand_value);
-                *instruction_pointer += 2;
-            }
-            ProgramValue::One => {
-                // bxl
-                let operand_value = operand.read_literal_operand();
-                registers.b ^= operand_value;
-                *instruction_pointer += 2;
-            }
-            ProgramValue::Two => {
-                // bst
-                let operand_value = operand.read_combo_operand(registers);
-                registers.b = operand_value % 8;
-                *instruction_pointer += 2;
-            }
-            ProgramValue::Three => {
-                // jnz
-                if registers.a == 0 {
-                    *instruction_pointer += 2;
-                } else {
-                    let operand_value = operand.read_literal_operand();
-                    *instruction_pointer = operand_value as usize;
+struct ChronospatialComputer;
+
+impl InstructionSet for ChronospatialComputer {
+    type Instruction = Instruction;
+    type Registers = Registers;
+
+    fn execute(&mut self, instruction: &Instruction, state: &mut State<Registers>) -> Control {
+        let literal_operand = u64::from(instruction.operand);
+        let combo_operand = || state.value(&combo_operand(instruction.operand));
+        match instruction.opcode {
+            // adv
+            0 => state.registers[0] = divide_by_power_of_two(state.registers[0], combo_operand()),
+            // bxl
+            1 => state.registers[1] ^= literal_operand,
+            // bst
+            2 => state.registers[1] = combo_operand() % 8,
+            // jnz
+            3 => {
+                if state.registers[0] != 0 {
+                    return Control::Goto(usize::from(instruction.operand));
                 }
             }
-            ProgramValue::Four => {
-                // bxc
-                registers.b ^= registers.c;
-                *instruction_pointer += 2;
-            }
-            ProgramValue::Five => {
-                // out
-                let operand_value = operand.read_combo_operand(registers);
-                outputs.push(operand_value % 8);
-                *instruction_pointer += 2;
-            }
-            ProgramValue::Six => {
-                // bdv
-                let operand_value = operand.read_combo_operand(registers);
-                registers.b = registers.a / 2_u32.pow(operand_value);
-                *instruction_pointer += 2;
-            }
-            ProgramValue::Seven => {
-                // cdv
-                let operand_value = operand.read_combo_operand(registers);
-                registers.c = registers.a / 2_u32.pow(operand_value);
-                *instruction_pointer += 2;
+            // bxc
+            4 => state.registers[1] ^= state.registers[2],
+            // out
+            5 => {
+                let output = combo_operand() % 8;
+                state.output.push_back(output);
             }
+            // bdv
+            6 => state.registers[1] = divide_by_power_of_two(state.registers[0], combo_operand()),
+            // cdv
+            7 => state.registers[2] = divide_by_power_of_two(state.registers[0], combo_operand()),
+            _ => panic!("Opcode should be a number from 0 to 7"),
         }
+        Control::Jump(2)
     }
+}
+
+// Combo operands 0 to 3 are literal values, while 4, 5, and 6 read registers a, b, and c.
+fn combo_operand(operand: u8) -> RegisterOrValue<usize, u64> {
+    match operand {
+        0..=3 => RegisterOrValue::Value(u64::from(operand)),
+        4..=6 => RegisterOrValue::Register(usize::from(operand - 4)),
+        _ => panic!("7 does not appear as a combo operand in valid programs"),
+    }
+}
+
+// Divides a number by 2 to the power of an exponent, rounding down. Exponents too large for a shift
+// divide every possible value down to 0.
+fn divide_by_power_of_two(numerator: u64, exponent: u64) -> u64 {
+    u32::try_from(exponent)
+        .ok()
+        .and_then(|exponent| numerator.checked_shr(exponent))
+        .unwrap_or(0)
+}
+
+// Runs the program with the given initial register values until it halts, and returns its outputs.
+fn execute(program: &[u8], registers: Registers) -> Vec<u64> {
+    let mut machine = get_machine(program, registers);
+    machine.run();
+    machine.state.output.into()
+}
+
+pub fn trace(input: &str, _part: u8, tracer: &mut Tracer) -> io::Result<()> {
+    let (registers, program) = get_registers_and_program(input);
+    let mut machine = get_machine(&program, registers);
+    tracer.run(&mut machine, |name| {
+        ["a", "b", "c"]
+            .iter()
+            .position(|register| *register == name)
+    })?;
+    tracer.finish(&machine)
+}
 
-    // Get the initial register values
+fn get_machine(program: &[u8], registers: Registers) -> Machine<ChronospatialComputer> {
+    let instructions = program
+        .windows(2)
+        .map(|pair| Instruction {
+            opcode: pair[0],
+            operand: pair[1],
+        })
+        .collect();
+    Machine::new(ChronospatialComputer, instructions, registers)
+}
+
+fn get_registers_and_program(input: &str) -> (Registers, Vec<u8>) {
     let mut line_iter = input.lines();
-    let register_a = line_iter
-        .next()
-        .expect("Input should have first line")
-        .trim_start_matches("Register A: ")
-        .parse()
-        .expect("First line should be a number after trimming");
-    let register_b = line_iter
-        .next()
-        .expect("Input should have second line")
-        .trim_start_matches("Register B: ")
-        .parse()
-        .expect("Second line should be a number after trimming");
-    let register_c = line_iter
-        .next()
-        .expect("Input should have third line")
-        .trim_start_matches("Register C: ")
-        .parse()
-        .expect("Third line should be a number after trimming");
-    let mut registers = Registers {
-        a: register_a,
-        b: register_b,
-        c: register_c,
-    };
+    let mut registers = [0; 3];
+    for (register, name) in registers.iter_mut().zip(["A", "B", "C"]) {
+        *register = line_iter
+            .next()
+            .expect("Input should have a line for each register")
+            .trim_start_matches(&format!("Register {name}: "))
+            .parse()
+            .expect("Register line should be a number after trimming");
+    }
 
-    // Get the program (a vector of ProgramValues)
     let program = line_iter
         .nth(1)
         .expect("Input should have fourth and fifth lines")
         .trim_start_matches("Program: ")
         .split(',')
-        .map(ProgramValue::new)
-        .collect::<Vec<_>>();
-
-    // Initialize the instruction pointer and output.
-    let mut instruction_pointer = 0;
-    let mut outputs = Vec::new();
-
-    // Execute instructions until the program halts (which occurs when it attempts to read a value
-    // outside of the program, in which case a None would be returned from one of the get methods)
-    while let Some((opcode, operand)) = program
-        .get(instruction_pointer)
-        .zip(program.get(instruction_pointer + 1))
-    {
-        execute(
-            opcode,
-            operand,
-            &mut registers,
-            &mut instruction_pointer,
-            &mut outputs,
-        );
-    }
-
-    // Convert the output integers into a string of digits separated by commas.
-    let output_string = outputs.into_iter().join(",");
-    Solution::String(output_string)
+        .map(|value| match value.parse() {
+            Ok(value @ 0..=7) => value,
+            _ => panic!("Value was not a number from 0 to 7"),
+        })
+        .collect();
+    (registers, program)
 }
 
-// Possible truncation happens in the exponents of the .pow() methods. However, the values there
-// (roughly the number of digits in another number) aren't close to the maximum value a u32 could
-// hold, so truncation won't happen.
-#[allow(clippy::cast_possible_truncation)]
 fn solve_2(input: &str) -> Solution {
-    // Solving this part in general for any possible input seems impractical and obviously not the
-    // intended solution. For my own puzzle input, I was able to determine the answer is at least
-    // 35,184,372,088,832 and at most 281,474,976,710,655 (8^15 and 8^16 - 1), which leaves
-    // 246,290,604,621,824 possible answers to check, an amount that will take at least several
-    // hours to search. As such, I have to take advantage of patterns I find in my puzzle input that
-    // weren't given in the puzzle description. I have no way to check if the patterns I take
-    // advantage of are present in all puzzle inputs, so it's possible this solver will give
-    // incorrect answers for other puzzle inputs.
+    // Searching every possible value of a is impractical, since the answer can be in the hundreds of
+    // trillions. Instead, this relies on the shape that puzzle inputs are built with: the program is
+    // a single loop that ends with "jnz 0" (jumping back to the start while a isn't 0), divides a
+    // by 8 exactly once per iteration with "adv 3", and outputs exactly one value per iteration.
+    // That shape is checked first, so a program that doesn't have it is rejected rather than
+    // giving a wrong answer.
     //
-    // By converting the opcodes and operands in my puzzle inputs into their corresponding
-    // instructions, I find that the program in my puzzle input is equivalent to the following
-    // pseudocode (^ represents XOR):
+    // With this shape, it's easiest to think of a as an octal number. Each iteration removes the
+    // last octal digit of a, so the number of outputs is the number of digits in a, and the last
+    // output only depends on the first digit of a, the second-last output only depends on the first
+    // two digits, and so on. So a can be built up one digit at a time, working backwards from the
+    // last output: for every value of a that produces the last n values of the program, try every
+    // digit appended to it, and keep the ones that produce the last n + 1 values. Each of these is
+    // checked by running the real program. Once every output value has been matched, the smallest
+    // value of a found is the answer.
+    let (registers, program) = get_registers_and_program(input);
+    check_loop_shape(&program);
 
-    // loop: {
-    //     b = a % 8;
-    //     b = b ^ 1;
-    //     c = a / 2.pow(b);
-    //     b = b ^ 5;
-    //     b = b ^ c;
-    //     outputs.push(b % 8);
-    //     a = a / 8;
-    //     if a == 0 {
-    //         break;
-    //     }
-    // }
-
-    // The initial values of registers b and c are irrelevant, because they are assigned to before
-    // they are first read. a is divided by 8 many times, so it is easier to think of a as an octal
-    // number (written in base 8). In octal form, the line "b = a % 8" sets b to the value of the
-    // last digit in a, the line "a = a / 8" removes the last digit in a, and the condition
-    // "if a == 0" checks if all digits in a have been removed. With this information, this
-    // pseudocode can be simplified to:
-
-    // while(a has digits): {
-    //     b = a_last_digit;
-    //     b = b ^ 1;
-    //     c = a / 2.pow(b);
-    //     b = b ^ 5;
-    //     b = b ^ c;
-    //     outputs.push(b % 8);
-    //     a.remove_last_digit()
-    // }
-
-    // We can use this pseudocode to conclude that on each iteration, the output digit that is
-    // pushed is given by `output = (((a_last_digit ^ 1) ^ 5) ^ (a / 2.pow(a_last_digit ^ 1))) % 8`.
-    // We know what each output digit needs to be, so in principle, we can work backwards from this
-    // to determine what a_last_digit must have been to produce that output digit.
-    //
-    // However, the fact that both a and a_last_digit change each loop iteration is problematic.
-    // Thought this can be resolved by flipping the logic and working backwards from the final
-    // output value. To ensure that the program doesn't loop again after the final output value, a
-    // must be on its last digit at this point, which means a and a_last_digit are equal. Once a
-    // value for a is determined for this loop, this "locks in" the first digit of a. Then the
-    // second-last value of the output can be considered, where we know that
-    // a = 8 * a_prev + a_digit (where a_prev is the value of a calculated for the final output
-    // digit). This strategy can be continued all the way down, where a_prev is the octal number
-    // formed by all digits of a found so far, multiplied by 8 (so the final digit is determined by
-    // a_last_digit, not a_prev).
-    //
-    // Working backwards like this is also convenient in that it helps find the minimum value
-    // easily. For each step, if there are multiple values of a_last_digit that solve the equation,
-    // we simply choose the smallest value. It's also possible there might be no values of
-    // a_last_digit that solve the equation, in which case an earlier value of a_last_digit isn't
-    // usable so go back to that value and continue checking greater numbers from there. As long as
-    // digits are checked in increasing order like this, the first full set of digits that solves
-    // the equation for every output value must be the digits that give the smallest overall number
-    // for a.
-
-    // This function recursively finds the octal digits of a, working backwards from the last output
-    // number.
-    fn get_a_octal_digits(
-        a_octal_digits: &mut Vec<u64>,
-        output: &Vec<u64>,
-        output_index: usize,
-        // For the very first digit, it must be forced to be 1 or greater, as if it were 0, a
-        // wouldn't have enough digits to loop enough times to produce all the output values. No
-        // other digit in a has this restriction, so they can be 0.
-        first_a_digit: u64,
-    ) -> bool {
-        let output_digit = output[output_index];
-        let a_prev = a_octal_digits
+    let mut candidates = vec![0];
+    for output_index in (0..program.len()).rev() {
+        let expected_output = program[output_index..]
             .iter()
-            .rev()
-            .enumerate()
-            // Get the value of the octal number by multiplying each digit by 8 to the power of that
-            // digit's position.
-            .fold(0, |acc, (place, digit)| {
-                acc + digit * 8_u64.pow(place as u32)
-            })
-            // Multiply 8 to shift all digits one position over, so that the last digit is
-            // determined by a_last_digit.
-            * 8;
-
-        for a_last_digit in first_a_digit..8 {
-            if output_digit
-                == (((a_last_digit ^ 1) ^ 5)
-                    ^ ((a_prev + a_last_digit) / (2_u64.pow((a_last_digit ^ 1) as u32))))
-                    % 8
-            {
-                // a_last_digit solves the above equation, so add this digit to the number and check
-                // if digits can be found after this that leads to a complete number. If so, return
-                // true. If output_index == 0, then this was the last digit and a complete number
-                // has just been found, so also return true in that case.
-                a_octal_digits.push(a_last_digit);
-                if output_index == 0
-                    || get_a_octal_digits(a_octal_digits, output, output_index - 1, 0)
-                {
-                    return true;
-                }
-
-                // If the inner get_a_octal_digits returned false, then there is no set of a digits
-                // after this digit that completes the output sequence correctly. So remove this
-                // digit and move on to checking the next digit.
-                a_octal_digits.pop();
-            }
-        }
-
-        false
+            .map(|value| u64::from(*value))
+            .collect::<Vec<_>>();
+        candidates = candidates
+            .into_iter()
+            .flat_map(|a| (0..8).map(move |digit| a * 8 + digit))
+            .filter(|a| execute(&program, [*a, registers[1], registers[2]]) == expected_output)
+            .collect();
     }
 
-    // The initial register values do not matter. a is overwritten by whatever value causes the
-    // program to output itself, and b and c are written to before they are read. So just skip ahead
-    // to reading the program values.
-    let outputs = input
-        .lines()
-        .nth(4)
-        .expect("Input should have five lines")
-        .trim_start_matches("Program: ")
-        .split(',')
-        .map(|value| {
-            value
-                .parse()
-                .expect("Line should only have numbers after trimming and splitting")
-        })
-        .collect::<Vec<_>>();
-    let mut a_octal_digits = Vec::with_capacity(outputs.len());
+    let register_a = candidates
+        .into_iter()
+        .min()
+        .expect("Some value of register a should make the program output itself");
+    Solution::U64(register_a)
+}
 
-    get_a_octal_digits(&mut a_octal_digits, &outputs, outputs.len() - 1, 1);
+// Checks that the program is a single loop that ends with "jnz 0", divides a by 8 exactly once per
+// iteration with "adv 3", and outputs exactly one value per iteration.
+fn check_loop_shape(program: &[u8]) {
+    assert!(
+        program.len().is_multiple_of(2),
+        "Program should be made of pairs of opcodes and operands"
+    );
+    let instructions = program.chunks(2).collect::<Vec<_>>();
+    let count = |opcode: u8| {
+        instructions
+            .iter()
+            .filter(|instruction| instruction[0] == opcode)
+            .count()
+    };
 
-    // Convert the octal digits into a decimal number.
-    let register_a = a_octal_digits
-        .iter()
-        .rev()
-        .enumerate()
-        .fold(0, |acc, (place, digit)| {
-            acc + digit * 8_u64.pow(place as u32)
-        });
-    Solution::U64(register_a)
+    assert_eq!(
+        instructions.last(),
+        Some(&[3, 0].as_slice()),
+        "Program should end with jnz 0 to loop back to the start"
+    );
+    assert_eq!(count(3), 1, "Program should only have one jnz instruction");
+    assert!(
+        count(0) == 1 && instructions.contains(&[0, 3].as_slice()),
+        "Program should have exactly one adv instruction, which should be adv 3"
+    );
+    assert_eq!(
+        count(5),
+        1,
+        "Program should have exactly one out instruction"
+    );
 }
 
 #[cfg(test)]
@@ -395,6 +286,18 @@ Program: 0,1,5,4,3,0"
         );
     }
 
-    // A test for part 2 is provided, but it violates the assumptions I had to make about my own puzzle
-    // input, so it cannot be used to test my solution.
+    #[test]
+    fn example2_1() {
+        assert_eq!(
+            solve_2(
+                "\
+Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0"
+            ),
+            Solution::U32(117_440)
+        );
+    }
 }
@@ -1,8 +1,10 @@
+use crate::solver::common::grid::Grid;
+use crate::solver::common::parse::unsigned_integers;
+use crate::solver::common::search::a_star;
+use crate::solver::common::union_find::UnionFind;
 use crate::solver::{AdventOfCode, Solution};
+use itertools::Itertools;
 
-// Temporarily allow dead code, as this solver is disabled for now for taking several minutes to
-// run. It will be re-enabled once a more optimized solution is found.
-#[allow(dead_code)]
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2024,
     day: 18,
@@ -22,131 +24,102 @@ fn solve_1_with_grid(input: &str, width: usize, height: usize, fallen_bytes: usi
     // true represents a safe byte, false represents a corrupted byte. Note that valid x values for
     // the bytes includes the width and valid y values for the bytes include the height, so the
     // lengths are actually width + 1 and height + 1.
-    let mut bytes = vec![vec![true; width + 1]; height + 1];
+    let mut bytes = Grid::new(width + 1, height + 1, true);
 
     // For the indicated number of fallen bytes, corrupt the square it lands on.
     for line in input.lines().take(fallen_bytes) {
-        let mut char_iter = line.split(',');
-        let x = char_iter
-            .next()
-            .expect("Line should have first value")
-            .parse::<usize>()
-            .expect("First value should be a number");
-        let y = char_iter
-            .next()
-            .expect("Line should have second value")
-            .parse::<usize>()
-            .expect("Second value should be a number");
-        bytes[y][x] = false;
+        bytes[get_byte_position(line)] = false;
     }
 
-    let shortest_path = a_star(&bytes).expect("A valid path should have been found");
+    let shortest_path = find_shortest_path(&bytes).expect("A valid path should have been found");
     Solution::USize(shortest_path)
 }
 
 fn solve_2_with_grid(input: &str, width: usize, height: usize) -> Solution {
-    let mut bytes = vec![vec![true; width + 1]; height + 1];
-
-    // Corrupt squares one at a time. After each corruption, check if the A* algorithm can find a
-    // path to the end. If not, return the coordinates of the byte that just fell.
-    for line in input.lines() {
-        let mut char_iter = line.split(',');
-        let x = char_iter
-            .next()
-            .expect("Line should have first value")
-            .parse::<usize>()
-            .expect("First value should be a number");
-        let y = char_iter
-            .next()
-            .expect("Line should have second value")
-            .parse::<usize>()
-            .expect("Second value should be a number");
-        bytes[y][x] = false;
-
-        if a_star(&bytes).is_none() {
-            return Solution::String(line.to_string());
-        }
+    let fallen_bytes = input
+        .lines()
+        .map(|line| (line, get_byte_position(line)))
+        .collect::<Vec<_>>();
+    let mut bytes = Grid::new(width + 1, height + 1, true);
+    // The index of the first byte to fall on each square, as later bytes landing on the same square
+    // don't change anything.
+    let mut first_fallen = Grid::new(width + 1, height + 1, usize::MAX);
+    for (index, (_, position)) in fallen_bytes.iter().enumerate() {
+        bytes[*position] = false;
+        first_fallen[*position] = first_fallen[*position].min(index);
     }
 
-    panic!("The path to the end should have been blocked");
-}
-
-fn a_star(bytes: &[Vec<bool>]) -> Option<usize> {
-    #[derive(PartialEq)]
-    struct Position {
-        x: usize,
-        y: usize,
+    // Rather than corrupting squares one at a time and searching for a path after each one, start
+    // with every byte fallen and remove them in reverse order. Safe squares are joined to their safe
+    // neighbours as they're found, so the first byte whose removal connects the start to the end is
+    // the byte that cut the path off.
+    let index_of = |(x, y): (usize, usize)| y * (width + 1) + x;
+    let start = index_of((0, 0));
+    let end = index_of((width, height));
+    let mut regions = UnionFind::new((width + 1) * (height + 1));
+    for (position, is_safe) in bytes.iter() {
+        if *is_safe {
+            for neighbour in bytes
+                .neighbours_4(position)
+                .filter(|neighbour| bytes[*neighbour])
+            {
+                regions.union(index_of(position), index_of(neighbour));
+            }
+        }
     }
 
-    let width = bytes[0].len() - 1;
-    let height = bytes.len() - 1;
-
-    // Use the A* algorithm to find the shortest path from (0, 0) to (width, height).
-    let mut open_set = Vec::with_capacity(width * height);
-    open_set.push(Position { x: 0, y: 0 });
-    let mut byte_g = vec![vec![usize::MAX; width + 1]; height + 1];
-    byte_g[0][0] = 0;
-    let mut byte_f = vec![vec![usize::MAX; width + 1]; height + 1];
-    // width + height is the distance from (0, 0), the start point, to (width, height), the end
-    // point.
-    byte_f[0][0] = width + height;
-
-    // This solution would run faster if open_set was a priority queue, but such optimizations will
-    // be made after seeing Part 2. For simplicity, just store the open set as a vector that is
-    // sorted according to byte_f before each pop for now.
-    while let Some(byte) = open_set.pop() {
-        if byte.x == width && byte.y == height {
-            return Some(byte_g[height][width]);
-        }
+    assert!(
+        !regions.connected(start, end),
+        "The path to the end should have been blocked"
+    );
 
-        let mut neighbours = Vec::with_capacity(4);
-        if byte.y > 0 && bytes[byte.y - 1][byte.x] {
-            neighbours.push(Position {
-                x: byte.x,
-                y: byte.y - 1,
-            });
-        }
-        if byte.x < width && bytes[byte.y][byte.x + 1] {
-            neighbours.push(Position {
-                x: byte.x + 1,
-                y: byte.y,
-            });
-        }
-        if byte.y < height && bytes[byte.y + 1][byte.x] {
-            neighbours.push(Position {
-                x: byte.x,
-                y: byte.y + 1,
-            });
+    for (index, (line, position)) in fallen_bytes.iter().enumerate().rev() {
+        // A square stays corrupted until the first byte that fell on it is removed.
+        if first_fallen[*position] != index {
+            continue;
         }
-        if byte.x > 0 && bytes[byte.y][byte.x - 1] {
-            neighbours.push(Position {
-                x: byte.x - 1,
-                y: byte.y,
-            });
+        bytes[*position] = true;
+        for neighbour in bytes
+            .neighbours_4(*position)
+            .filter(|neighbour| bytes[*neighbour])
+        {
+            regions.union(index_of(*position), index_of(neighbour));
         }
 
-        for neighbour in neighbours {
-            let neighbour_tentative_g = byte_g[byte.y][byte.x] + 1;
-            if neighbour_tentative_g < byte_g[neighbour.y][neighbour.x] {
-                byte_g[neighbour.y][neighbour.x] = neighbour_tentative_g;
-                // The h function is the distance to the bottom-left corner, which is at (width,
-                // height). As this is always to the right and beneath any other point, this
-                // distance can be calculated by width + height - x - y
-                byte_f[neighbour.y][neighbour.x] =
-                    neighbour_tentative_g + width + height - neighbour.x - neighbour.y;
-                if !open_set.contains(&neighbour) {
-                    open_set.push(neighbour);
-                }
-            }
+        if regions.connected(start, end) {
+            return Solution::String(line.to_string());
         }
-
-        // Sort the elements in open set by the byte_f score at their positions. Reverse the
-        // comparison so the vector is sorted biggest to smallest, so the byte with the smallest
-        // byte_f score is popped first.
-        open_set.sort_unstable_by(|a, b| byte_f[a.y][a.x].cmp(&byte_f[b.y][b.x]).reverse());
     }
 
-    None
+    panic!("The start should be connected to the end once every byte is removed");
+}
+
+fn get_byte_position(line: &str) -> (usize, usize) {
+    unsigned_integers(line)
+        .collect_tuple()
+        .expect("Line should have two numbers")
+}
+
+// Use the A* algorithm to find the length of the shortest path from the top-left corner to the
+// bottom-right corner, or None if there is no such path.
+fn find_shortest_path(bytes: &Grid<bool>) -> Option<usize> {
+    let end = (bytes.width() - 1, bytes.height() - 1);
+
+    a_star(
+        (0, 0),
+        |byte| {
+            bytes
+                .neighbours_4(*byte)
+                .filter(|neighbour| bytes[*neighbour])
+                .map(|neighbour| (neighbour, 1))
+                .collect::<Vec<_>>()
+        },
+        // The heuristic is the distance to the bottom-right corner. As this is always to the right
+        // and beneath any other point, this distance can be calculated by subtracting x and y.
+        |(x, y)| end.0 - x + end.1 - y,
+        |byte| *byte == end,
+    )
+    .map(|path| path.cost)
 }
 
 #[cfg(test)]
@@ -227,4 +200,20 @@ mod test {
             Solution::String("6,1".to_string())
         );
     }
+
+    #[test]
+    fn repeated_byte() {
+        // The third byte lands on the same square as the first, so the path is still cut off by
+        // the second byte.
+        assert_eq!(
+            solve_2_with_grid("1,0\n0,1\n1,0", 1, 1),
+            Solution::String("0,1".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The path to the end should have been blocked")]
+    fn never_blocked() {
+        solve_2_with_grid("1,0", 1, 1);
+    }
 }
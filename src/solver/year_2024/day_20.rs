@@ -1,3 +1,4 @@
+use crate::solver::common::search::a_star;
 use crate::solver::{AdventOfCode, Solution};
 use rustc_hash::FxHashMap;
 use std::cmp::min;
@@ -208,88 +209,45 @@ fn get_shortest_path_steps(
     start_position: &Position,
     end_position: &Position,
 ) -> FxHashMap<Position, usize> {
-    let mut shortest_path_steps = FxHashMap::default();
-
-    let mut open_set = vec![start_position.clone()];
-    let mut came_from: FxHashMap<Position, Position> = FxHashMap::default();
-    let mut g_score = FxHashMap::default();
-    g_score.insert(start_position.clone(), 0);
-    let mut f_score = FxHashMap::default();
-    f_score.insert(
+    let shortest_path = a_star(
         start_position.clone(),
-        start_position.distance_from(end_position),
-    );
-
-    while let Some(current) = open_set.pop() {
-        if current == *end_position {
-            shortest_path_steps.insert(
-                current.clone(),
-                *g_score
-                    .get(&current)
-                    .expect("Every tile on the shortest path should have a g score"),
-            );
-            let mut last_position = current.clone();
-            while let Some(next_position) = came_from.get(&last_position) {
-                shortest_path_steps.insert(
-                    next_position.clone(),
-                    *g_score
-                        .get(next_position)
-                        .expect("Every tile on the shortest path should have a g score"),
-                );
-                last_position = next_position.clone();
-            }
-        }
-
-        let mut neighbours = Vec::with_capacity(4);
-        // Bounds checks are not required for neighbours as the maze is surrounded by a layer of
-        // walls.
-        if maze[current.y - 1][current.x] {
-            neighbours.push(Position {
-                x: current.x,
-                y: current.y - 1,
-            });
-        }
-        if maze[current.y][current.x + 1] {
-            neighbours.push(Position {
-                x: current.x + 1,
-                y: current.y,
-            });
-        }
-        if maze[current.y + 1][current.x] {
-            neighbours.push(Position {
-                x: current.x,
-                y: current.y + 1,
-            });
-        }
-        if maze[current.y][current.x - 1] {
-            neighbours.push(Position {
-                x: current.x - 1,
-                y: current.y,
-            });
-        }
-
-        let tentative_g = *g_score.entry(current.clone()).or_insert(usize::MAX) + 1;
-        for neighbour in neighbours {
-            let neighbour_g = g_score.entry(neighbour.clone()).or_insert(usize::MAX);
-            if tentative_g < *neighbour_g {
-                came_from.insert(neighbour.clone(), current.clone());
-                *neighbour_g = tentative_g;
-                f_score.insert(
-                    neighbour.clone(),
-                    tentative_g + neighbour.distance_from(end_position),
-                );
-                if !open_set.contains(&neighbour) {
-                    open_set.push(neighbour);
-                }
-            }
-        }
-
-        // Sort the open set so the position with the lowest f score is moved to the end, to be
-        // popped next.
-        open_set.sort_unstable_by(|a, b| f_score.get(a).cmp(&f_score.get(b)).reverse());
-    }
-
-    shortest_path_steps
+        |current: &Position| {
+            // Bounds checks are not required for neighbours as the maze is surrounded by a layer of
+            // walls.
+            [
+                Position {
+                    x: current.x,
+                    y: current.y - 1,
+                },
+                Position {
+                    x: current.x + 1,
+                    y: current.y,
+                },
+                Position {
+                    x: current.x,
+                    y: current.y + 1,
+                },
+                Position {
+                    x: current.x - 1,
+                    y: current.y,
+                },
+            ]
+            .into_iter()
+            .filter(|neighbour| maze[neighbour.y][neighbour.x])
+            .map(|neighbour| (neighbour, 1))
+        },
+        |position| position.distance_from(end_position),
+        |position| position == end_position,
+    )
+    .expect("Maze should have a path from the start to the end");
+
+    // The number of steps taken to reach each position is its index along the path.
+    shortest_path
+        .states
+        .into_iter()
+        .enumerate()
+        .map(|(picoseconds, position)| (position, picoseconds))
+        .collect()
 }
 
 // The examples provided are too different in their format (showing exact picoseconds saved by
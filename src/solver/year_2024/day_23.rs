@@ -1,3 +1,4 @@
+use crate::solver::common::dot::DotGraph;
 use crate::solver::{AdventOfCode, Solution};
 use itertools::Itertools;
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -74,6 +75,36 @@ fn solve_1(input: &str) -> Solution {
 }
 
 fn solve_2(input: &str) -> Solution {
+    // There is no need to sort the computers before combining their names into the password, as
+    // BTreeSet automatically sorts its elements according to its Ord trait, and the Computer's
+    // derived Ord trait automatically orders the computers alphabetically. The join method turns the
+    // Computers into strings using their Display trait, which is manually implemented to be a string
+    // containing the computer's two characters in order.
+    let password = find_largest_fully_connected_group(input)
+        .into_iter()
+        .join(",");
+
+    Solution::String(password)
+}
+
+// Draws the network with an edge for each direct connection, highlighting the computers in the
+// largest fully connected group that part 2 finds.
+pub fn export_graph(input: &str) -> DotGraph {
+    let mut graph = DotGraph::new(false);
+    for line in input.lines() {
+        let (first_computer, second_computer) = line
+            .split_once('-')
+            .expect("Line should have two computers");
+        graph.add_edge(first_computer, second_computer, None);
+    }
+    for computer in find_largest_fully_connected_group(input) {
+        graph.highlight(&computer.to_string());
+    }
+
+    graph
+}
+
+fn find_largest_fully_connected_group(input: &str) -> BTreeSet<Computer> {
     // Every computer has a key in this map, and the value is a vector of all other computers
     // directly connected to that computer.
     let mut direct_connections_from_computer = FxHashMap::default();
@@ -159,24 +190,12 @@ fn solve_2(input: &str) -> Solution {
         fully_connected_groups.insert(new_fully_connected_group);
     }
 
-    // All fully connected groups have been found, so take the biggest one and get the password from
-    // it. There is no need to sort the computers before combining their names into the password, as
-    // BTreeSet automatically sorts its elements according to its Ord trait, and the Computer's
-    // derived Ord trait automatically orders the computers alphabetically.
-    let password = fully_connected_groups
+    // All fully connected groups have been found, so take the biggest one. It is assumed there is
+    // only one such group, as otherwise the problem would not have a single unambiguous answer.
+    fully_connected_groups
         .into_iter()
-        // Get the fully connected group with the maximum length. It is assumed there is only one
-        // such group, as otherwise the problem would not have a single unambiguous answer.
         .max_by_key(BTreeSet::len)
         .expect("The set of fully connected groups should have at least one element")
-        // The largest fully connected group has been obtained and all groups are presorted
-        // alphabetically. So simply join the computers together into one string. The join method
-        // turns the Computers into strings using their Display trait, which is manually implemented
-        // to be a string containing the computer's two characters in order.
-        .into_iter()
-        .join(",");
-
-    Solution::String(password)
 }
 
 #[derive(PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
@@ -1,7 +1,10 @@
+use crate::solver::common::circuit::{Circuit, GateId, Input, Operation, WireId};
+use crate::solver::common::dot::DotGraph;
 use crate::solver::{AdventOfCode, Solution};
-use itertools::{repeat_n, Itertools};
-use rustc_hash::{FxHashMap, FxHashSet};
-use std::{cmp::max, collections::BTreeSet};
+use itertools::Itertools;
+use rustc_hash::FxHashSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Display, Formatter};
 
 pub const SOLVER: AdventOfCode = AdventOfCode {
     year: 2024,
@@ -11,21 +14,18 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
 };
 
 fn solve_1(input: &str) -> Solution {
-    let (mut wire_values, mut pending_gates) = get_wire_values_and_gates(input);
-
-    // Continue trying to execute gates until all gates are executed successfully. The retain method
-    // removes gates whose execute method returns true, so repeatedly running this method until all
-    // gates are removed ensures all gates are executed.
-    while !pending_gates.is_empty() {
-        pending_gates.retain(|gate| !gate.execute(&mut wire_values));
-    }
+    let (initial_signals, circuit) = get_initial_signals_and_circuit(input);
+    let signals = circuit
+        .evaluate(initial_signals)
+        .unwrap_or_else(|error| panic!("{error}"));
 
     // Construct the output from the wires whose names begin with 'z'.
-    let mut decimal_output = 0;
-    for (wire, wire_value) in wire_values {
-        if wire_value && wire.starts_with('z') {
-            let wire_index = wire
-                .trim_start_matches('z')
+    let mut decimal_output: u64 = 0;
+    for (wire, name) in circuit.wires() {
+        if let Some(wire_index) = name.strip_prefix('z')
+            && signals[wire] == Some(true)
+        {
+            let wire_index = wire_index
                 .parse::<u8>()
                 .expect("Wire name should only contain digits after the leading 'z' is removed");
             // Take the digit 1 and left-shift it until it is in the proper binary digit position,
@@ -37,521 +37,482 @@ fn solve_1(input: &str) -> Solution {
     Solution::U64(decimal_output)
 }
 
-// Strangely, my part 2 current calculates that only six logic gates are incorrect. As it is
-// explicitly stated in the puzzle description that exactly eight logic gates are incorrect, this
-// means there must be an error in my solution. However, I have extensively checked my logic and
-// haven't found any faults, so for now I am stumped as to how I am somehow wrongly verifying at
-// least two incorrect gates.
 fn solve_2(input: &str) -> Solution {
-    let (initial_wire_values, mut gates) = get_wire_values_and_gates(input);
+    let (_, circuit) = get_initial_signals_and_circuit(input);
+
+    // Get the names of output wires that have been swapped, sorted alphabetically by collecting
+    // them in a BTreeSet, and join them to create the final output.
+    let swapped_output_wires = find_swaps(&circuit)
+        .expect("Swapping pairs of output wires should make the circuit an adder")
+        .iter()
+        .flat_map(|swap| swap.violations.map(|violation| violation.wire))
+        .collect::<BTreeSet<_>>();
+    Solution::String(swapped_output_wires.iter().join(","))
+}
 
-    let mut verified_gates = FxHashSet::default();
-    let mut swapped_output_wires = BTreeSet::new();
-
-    // Get how many binary digits are in the input numbers.
-    let mut largest_input_index = 0;
-    for initial_wire in initial_wire_values.keys() {
-        if initial_wire.starts_with('x') {
-            let input_index = initial_wire
-                .trim_start_matches('x')
-                .parse::<u8>()
-                .expect("Wire name should only contain digits after the leading 'z' is removed");
-            largest_input_index = max(largest_input_index, input_index);
-        }
-    }
+// Draws the circuit with a node for each wire, and an edge from each input wire of a gate to its
+// output wire labelled with the gate's operation. The output wires that part 2 finds were swapped
+// are highlighted, and labelled with an explanation of the swap. A circuit that can't be repaired is
+// still drawn, just without any highlighted wires, as that's when it's most useful to look at.
+pub fn export_graph(input: &str) -> DotGraph {
+    let (_, circuit) = get_initial_signals_and_circuit(input);
 
-    // Track all preceding wires so they can be set for the carry-in digit.
-    let mut preceding_x_input_wires: Vec<String> = Vec::new();
-    let mut preceding_y_input_wires: Vec<String> = Vec::new();
-
-    // For every gate, we want to check that it gives the correct output digit according to
-    // full-adder logic. This means that the output digit for a given position is equal to the XOR
-    // of the two input digits for that position and the carry-in digit. The carry-in digit can't be
-    // set directly, but it can be guaranteed to be false if every input digit before the current
-    // input digits is false (as no carry would ever occur), or guaranteed to be true if every digit
-    // before the current input digits is true (as a carry would happen on every digit). In order to
-    // confirm whether a given digit is working correctly, all eight combinations of the three input
-    // digits (first input wire, second input wire, and carry in) are tested and checked to see if
-    // they provide the correct output digit.
-    //
-    // If the digit is correct, all gates used to calculate that digit must have the correct output,
-    // so add them to verified gates. If the digit is not correct, iterate over every possible pair
-    // of unverified gates while ensuring at least one of those gates were involved in calculating
-    // the digit, and for each of these pairs, check if swapping them gives the correct digit. If it
-    // does, replicate the swap in the actual gate data and record the swapped wires.
-    for input_index in 0..=largest_input_index {
-        // "{input_index:0>2}" in a format string prints the input_index's value but padded with
-        // leading zeroes to be at least two characters wide.
-        let x_input_wire = format!("x{input_index:0>2}");
-        let y_input_wire = format!("y{input_index:0>2}");
-        let output_wire = format!("z{input_index:0>2}");
-
-        // Get every gate that is involved in calculating the next output digit.
-        let mut involved_gates = FxHashSet::default();
-        let mut involved_outputs = vec![output_wire.as_str()];
-        while let Some(output_wire) = involved_outputs.pop() {
-            let gate = gates.iter().find(|gate| gate.output_wire == output_wire).expect("Every wire that doesn't begin with x or y should have a corresponding gate that outputs it.");
-            involved_gates.insert(gate.clone());
-
-            if !(gate.first_wire.starts_with('x') || gate.first_wire.starts_with('y')) {
-                involved_outputs.push(gate.first_wire);
-            }
-            if !(gate.second_wire.starts_with('x') || gate.second_wire.starts_with('y')) {
-                involved_outputs.push(gate.second_wire);
+    let mut graph = DotGraph::new(true);
+    for (_, name) in circuit.wires() {
+        graph.add_node(name, None);
+    }
+    for gate in circuit.gates() {
+        for input in &gate.inputs {
+            if let Input::Wire(wire) = input {
+                graph.add_edge(
+                    circuit.wire_name(*wire),
+                    circuit.wire_name(gate.output),
+                    Some(gate.operation.to_string()),
+                );
             }
         }
+    }
+    for swap in find_swaps(&circuit).unwrap_or_default() {
+        for violation in swap.violations {
+            graph.add_node(violation.wire, Some(format!("{}\n{swap}", violation.wire)));
+            graph.highlight(violation.wire);
+        }
+    }
 
-        let mut is_digit_valid = true;
-        // repeat_n(iter, 3).multi_cartesian_product gives every 3-length permutation of iter with
-        // replacement, which is desired here to generate all 8 possibilities for the two input
-        // digits and carry-in digit.
-        for values in repeat_n([false, true], 3).multi_cartesian_product() {
-            let expected_output_value = if input_index > 0 {
-                values[0] ^ values[1] ^ values[2]
-            } else {
-                // If the input index is zero, disregard the carry bit, as the very first addition
-                // uses a half-adder.
-                values[0] ^ values[1]
-            };
+    graph
+}
 
-            let mut wire_values = FxHashMap::default();
-            wire_values.insert(x_input_wire.as_str(), values[0]);
-            wire_values.insert(y_input_wire.as_str(), values[1]);
-            // To set the carry-in digit, set every preceding input to the desired carry-in digit to
-            // ensure a carry either never happens or always happens.
-            for preceding_x_input_wire in &preceding_x_input_wires {
-                wire_values.insert(preceding_x_input_wire.as_str(), values[2]);
-            }
-            for preceding_y_input_wire in &preceding_y_input_wires {
-                wire_values.insert(preceding_y_input_wire.as_str(), values[2]);
-            }
+// The ways a gate can be out of place in a ripple-carry adder. For each bit after the first, the
+// adder computes:
+// - a partial sum, which is the XOR of the two input bits,
+// - the output bit, which is the XOR of the partial sum and the carry into the bit,
+// - two partial carries, which are the AND of the two input bits and the AND of the partial sum and
+//   the carry into the bit,
+// - the carry into the next bit, which is the OR of the two partial carries.
+// The first bit is a half adder, so its output bit is the XOR of its input bits and the carry into
+// the next bit is the AND of its input bits. The final output bit is the carry out of the last bit.
+#[derive(Clone, Copy)]
+enum Rule {
+    OutputNotSum,
+    FinalCarryNotCarry,
+    SumNotOutput,
+    PartialSumNotSummed,
+    PartialCarryNotCombined,
+    CarryNotPropagated,
+    // The gate fits the structure of an adder, but the given output bit is wrong until it's
+    // swapped, so it must be in the place of a gate with the same role in another bit.
+    WrongBit(usize),
+}
 
-            // Calculate the output digit.
-            let mut pending_gates = involved_gates.clone();
-            while !wire_values.contains_key(&output_wire.as_str()) {
-                pending_gates.retain(|gate| !gate.execute(&mut wire_values));
-            }
+// A gate whose output wire breaks one of the rules of a ripple-carry adder, along with the bit of the
+// adder that the gate belongs to.
+#[derive(Clone, Copy)]
+struct Violation<'a> {
+    gate: GateId,
+    wire: &'a str,
+    operation: GateOperation,
+    rule: Rule,
+    bit: usize,
+}
 
-            // Test if the output digit is valid. If it is ever wrong for any combination of input
-            // digits, then the involved gates must contain incorrect gates.
-            if *wire_values
-                .get(&output_wire.as_str())
-                .expect("This key should have just been inserted in the preceding while loop")
-                != expected_output_value
-            {
-                is_digit_valid = false;
-                break;
-            }
+impl Display for Violation<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let Violation {
+            wire, operation, ..
+        } = self;
+        match self.rule {
+            Rule::OutputNotSum => write!(
+                f,
+                "{wire} is an output bit, but is driven by an {operation} gate instead of XOR"
+            ),
+            Rule::FinalCarryNotCarry => write!(
+                f,
+                "{wire} is the final carry, but is driven by an {operation} gate"
+            ),
+            Rule::SumNotOutput => write!(
+                f,
+                "{wire} is the XOR of a partial sum and a carry, but isn't an output bit"
+            ),
+            Rule::PartialSumNotSummed => write!(
+                f,
+                "{wire} is a partial sum, but isn't read by an XOR gate for an output bit"
+            ),
+            Rule::PartialCarryNotCombined => write!(
+                f,
+                "{wire} is a partial carry, but isn't read by an OR gate for a carry"
+            ),
+            Rule::CarryNotPropagated => write!(
+                f,
+                "{wire} is a carry, but isn't read by an XOR gate for the next output bit"
+            ),
+            Rule::WrongBit(bit) => write!(
+                f,
+                "{wire} is driven by an {operation} gate in the right place, but z{bit:02} is \
+                wrong until it's swapped"
+            ),
         }
+    }
+}
 
-        if is_digit_valid {
-            verified_gates.extend(involved_gates);
-        } else {
-            // This for loop takes the set of every gate involved in this loop and the set of all
-            // gates, gets the gates in those sets that are not also in verified gates, and then
-            // iterates over every possible pair of these sets by iterating over their cartesian
-            // product.
-            'check_new_gate_pair: for (involved_gate, unverified_gate) in involved_gates
-                .difference(&verified_gates)
-                .cartesian_product(gates.difference(&verified_gates))
-            {
-                // Make sure the gates aren't equal.
-                if *involved_gate != *unverified_gate {
-                    // Make a temporary copy of the gates set, remove the current pair of gates form
-                    // them, and add these gates again with their output wires swapped.
-                    let mut new_gates = gates.clone();
-                    new_gates.retain(|gate| {
-                        gate.output_wire != involved_gate.output_wire
-                            && gate.output_wire != unverified_gate.output_wire
-                    });
-                    new_gates.insert(Gate {
-                        operation: involved_gate.operation.clone(),
-                        first_wire: involved_gate.first_wire,
-                        second_wire: involved_gate.second_wire,
-                        output_wire: unverified_gate.output_wire,
-                    });
-                    new_gates.insert(Gate {
-                        operation: unverified_gate.operation.clone(),
-                        first_wire: unverified_gate.first_wire,
-                        second_wire: unverified_gate.second_wire,
-                        output_wire: involved_gate.output_wire,
-                    });
-
-                    // Most of the following logic replicates the logic for checking for digit
-                    // correctness, but some additional checks need to be put in place as swapping
-                    // gate outputs creates the possibility of loops or removing the path to the
-                    // output digit.
-
-                    let mut involved_gates = FxHashSet::default();
-                    let mut involved_outputs = vec![output_wire.as_str()];
-                    // Track outputs that have been found before. If the same output is found again,
-                    // a loop has been created.
-                    let mut found_outputs = FxHashSet::default();
-                    while let Some(output_wire) = involved_outputs.pop() {
-                        if found_outputs.contains(output_wire) {
-                            continue 'check_new_gate_pair;
-                        }
-                        found_outputs.insert(output_wire);
-
-                        let gate = new_gates.iter().find(|gate| gate.output_wire == output_wire).expect("Every wire that doesn't begin with x or y should have a corresponding gate that outputs it.");
-                        involved_gates.insert(gate);
-
-                        if !(gate.first_wire.starts_with('x') || gate.first_wire.starts_with('y')) {
-                            involved_outputs.push(gate.first_wire);
-                        }
-                        if !(gate.second_wire.starts_with('x') || gate.second_wire.starts_with('y'))
-                        {
-                            involved_outputs.push(gate.second_wire);
-                        }
-                    }
-
-                    for values in repeat_n([false, true], 3).multi_cartesian_product() {
-                        let expected_output_value = if input_index > 0 {
-                            values[0] ^ values[1] ^ values[2]
-                        } else {
-                            values[0] ^ values[1]
-                        };
-
-                        let mut wire_values = FxHashMap::default();
-                        wire_values.insert(x_input_wire.as_str(), values[0]);
-                        wire_values.insert(y_input_wire.as_str(), values[1]);
-                        for preceding_x_input_wire in &preceding_x_input_wires {
-                            wire_values.insert(preceding_x_input_wire.as_str(), values[2]);
-                        }
-                        for preceding_y_input_wire in &preceding_y_input_wires {
-                            wire_values.insert(preceding_y_input_wire.as_str(), values[2]);
-                        }
-
-                        let mut pending_gates = involved_gates.clone();
-                        while !wire_values.contains_key(&output_wire.as_str()) {
-                            // Track the length of the pending gates on each iteration. If it
-                            // doesn't reduce on any step, the pending gates don't have a path to
-                            // the output digit.
-                            let starting_len = pending_gates.len();
-                            pending_gates.retain(|gate| !gate.execute(&mut wire_values));
-                            if pending_gates.len() == starting_len {
-                                continue 'check_new_gate_pair;
-                            }
-                        }
-
-                        if *wire_values.get(&output_wire.as_str()).expect(
-                            "This key should have just been inserted in the preceding while loop",
-                        ) != expected_output_value
-                        {
-                            continue 'check_new_gate_pair;
-                        }
-                    }
-
-                    // If none of the "continue 'check_new_gate_pair;" statements were reached and
-                    // code execution survived to this point, then it has been verified that the
-                    // current pair of gates are the ones that need to be swapped. Make the swap in
-                    // the real gates set, and remember the swapped wires.
-                    swapped_output_wires.insert(involved_gate.output_wire);
-                    swapped_output_wires.insert(unverified_gate.output_wire);
-
-                    let first_new_gate = Gate {
-                        operation: involved_gate.operation.clone(),
-                        first_wire: involved_gate.first_wire,
-                        second_wire: involved_gate.second_wire,
-                        output_wire: unverified_gate.output_wire,
-                    };
-                    let second_new_gate = Gate {
-                        operation: unverified_gate.operation.clone(),
-                        first_wire: unverified_gate.first_wire,
-                        second_wire: unverified_gate.second_wire,
-                        output_wire: involved_gate.output_wire,
-                    };
-
-                    gates.retain(|gate| {
-                        gate.output_wire != first_new_gate.output_wire
-                            && gate.output_wire != second_new_gate.output_wire
-                    });
-                    gates.insert(first_new_gate);
-                    gates.insert(second_new_gate);
-
-                    break;
-                }
-            }
-        }
+// A pair of gates whose output wires were swapped, along with the rules each of them broke.
+struct Swap<'a> {
+    violations: [Violation<'a>; 2],
+}
 
-        // Do not push the preceding values for the last iteration, as these values need to be
-        // checked again for the final carry-out digit.
-        if input_index != largest_input_index {
-            preceding_x_input_wires.push(x_input_wire);
-            preceding_y_input_wires.push(y_input_wire);
-        }
+impl<'a> Swap<'a> {
+    fn new(first: Violation<'a>, second: Violation<'a>) -> Swap<'a> {
+        let mut violations = [first, second];
+        violations.sort_unstable_by_key(|violation| violation.wire);
+        Swap { violations }
     }
+}
 
-    // Calculate the final carry-out digit to ensure it's correct. The inputs are the same as for
-    // the final input digit, but the output digit is one index greater.
-    let x_input_wire = format!("x{largest_input_index:0>2}");
-    let y_input_wire = format!("y{largest_input_index:0>2}");
-    let output_wire = format!("z{:0>2}", largest_input_index + 1);
-
-    // Get every gate that is involved in calculating the final carry output digit.
-    let mut involved_gates = FxHashSet::default();
-    let mut involved_outputs = vec![output_wire.as_str()];
-    while let Some(output_wire) = involved_outputs.pop() {
-        let gate = gates.iter().find(|gate| gate.output_wire == output_wire).expect("Every wire that doesn't begin with x or y should have a corresponding gate that outputs it.");
-        involved_gates.insert(gate.clone());
-
-        if !(gate.first_wire.starts_with('x')
-            || gate.first_wire.starts_with('y')
-            || gate.first_wire.starts_with('z'))
-        {
-            involved_outputs.push(gate.first_wire);
-        }
-        if !(gate.second_wire.starts_with('x')
-            || gate.second_wire.starts_with('y')
-            || gate.first_wire.starts_with('z'))
-        {
-            involved_outputs.push(gate.second_wire);
-        }
+impl Display for Swap<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let [first, second] = &self.violations;
+        write!(
+            f,
+            "Swapped {} and {}: {first}, and {second}",
+            first.wire, second.wire
+        )
     }
+}
 
-    let mut is_final_carry_valid = true;
-
-    for values in repeat_n([false, true], 3).multi_cartesian_product() {
-        // The final carry is expected to be true if at least two or more of the input digits are
-        // true.
-        let expected_final_carry_value =
-            (values[0] && values[1]) || (values[2] && (values[0] ^ values[1]));
+// Finds the gates whose output wires were swapped by checking the circuit against the structure of
+// a ripple-carry adder. Swapping two gates from the same bit with different roles moves both of
+// them out of place, so when exactly two gates in a bit break a rule, they're swapped back. Gates
+// with the same role in different bits (such as two output bits) can be swapped without breaking
+// any rule, so every output bit is then checked, and any that are still wrong are fixed by
+// searching for a swap. Returns None if the circuit can't be made into an adder this way.
+fn find_swaps<'a>(circuit: &Circuit<'a, GateOperation>) -> Option<Vec<Swap<'a>>> {
+    let mut violations_by_bit = BTreeMap::<usize, Vec<Violation>>::new();
+    for violation in find_violations(circuit) {
+        violations_by_bit
+            .entry(violation.bit)
+            .or_default()
+            .push(violation);
+    }
 
-        let mut wire_values = FxHashMap::default();
-        wire_values.insert(x_input_wire.as_str(), values[0]);
-        wire_values.insert(y_input_wire.as_str(), values[1]);
-        for preceding_x_input_wire in &preceding_x_input_wires {
-            wire_values.insert(preceding_x_input_wire.as_str(), values[2]);
-        }
-        for preceding_y_input_wire in &preceding_y_input_wires {
-            wire_values.insert(preceding_y_input_wire.as_str(), values[2]);
-        }
-        for preceding_output_wire in &preceding_y_input_wires {
-            wire_values.insert(preceding_output_wire.as_str(), values[2]);
+    let mut circuit = circuit.clone();
+    let mut swaps = Vec::new();
+    let mut unpaired_violations = Vec::new();
+    for violations in violations_by_bit.into_values() {
+        if let [first, second] = violations[..] {
+            circuit.swap_outputs(first.gate, second.gate);
+            swaps.push(Swap::new(first, second));
+        } else {
+            unpaired_violations.extend(violations);
         }
+    }
 
-        let mut pending_gates = involved_gates.clone();
+    fix_wrong_bits(&mut circuit, &unpaired_violations, &mut swaps).then_some(swaps)
+}
 
-        while !wire_values.contains_key(&output_wire.as_str()) {
-            pending_gates.retain(|gate| !gate.execute(&mut wire_values));
-        }
+// Checks every output bit in order. If a bit is correct, all the gates it depends on must be too.
+// If it's wrong, every swap between a gate it depends on and another gate that isn't known to be
+// correct is tried until the bit is correct. The swaps are added to swaps, explained by the
+// violations of their gates if they broke a rule. Returns false if a bit can't be fixed.
+fn fix_wrong_bits<'a>(
+    circuit: &mut Circuit<'a, GateOperation>,
+    unpaired_violations: &[Violation<'a>],
+    swaps: &mut Vec<Swap<'a>>,
+) -> bool {
+    let input_size = get_input_size(circuit);
+    let mut verified_gates = FxHashSet::default();
 
-        if *wire_values
-            .get(&output_wire.as_str())
-            .expect("This key should have just been inserted in the preceding while loop")
-            != expected_final_carry_value
-        {
-            is_final_carry_valid = false;
-            break;
+    // The output digit after the last input digits is the final carry-out digit.
+    for output_index in 0..=input_size {
+        let output_wire = get_wire(circuit, 'z', output_index);
+        if !is_output_digit_valid(circuit, output_index, input_size) {
+            let involved_gates = circuit
+                .fan_in(output_wire)
+                .into_iter()
+                .filter(|gate| !verified_gates.contains(gate))
+                .collect::<Vec<_>>();
+            let unverified_gates = (0..circuit.gates().len())
+                .filter(|gate| !verified_gates.contains(gate))
+                .collect::<Vec<_>>();
+            let violation = |circuit: &Circuit<'a, GateOperation>, gate: GateId| {
+                unpaired_violations
+                    .iter()
+                    .find(|violation| violation.gate == gate)
+                    .copied()
+                    .unwrap_or(Violation {
+                        gate,
+                        wire: circuit.wire_name(circuit.gates()[gate].output),
+                        operation: circuit.gates()[gate].operation,
+                        rule: Rule::WrongBit(output_index),
+                        bit: output_index,
+                    })
+            };
+
+            // Swapping gate outputs can create loops or remove the path to the output digit, both
+            // of which make the digit invalid.
+            let mut is_fixed = false;
+            for (involved_gate, unverified_gate) in involved_gates
+                .into_iter()
+                .cartesian_product(unverified_gates)
+            {
+                if involved_gate == unverified_gate {
+                    continue;
+                }
+                let swap = Swap::new(
+                    violation(circuit, involved_gate),
+                    violation(circuit, unverified_gate),
+                );
+                circuit.swap_outputs(involved_gate, unverified_gate);
+                if is_output_digit_valid(circuit, output_index, input_size) {
+                    swaps.push(swap);
+                    is_fixed = true;
+                    break;
+                }
+                circuit.swap_outputs(involved_gate, unverified_gate);
+            }
+            if !is_fixed {
+                return false;
+            }
         }
+        verified_gates.extend(circuit.fan_in(output_wire));
     }
+    true
+}
 
-    if !is_final_carry_valid {
-        // Do the same check for gates that need to be swapped for the final carry. This is the same
-        // logic for any other digit except for the different calculation for the expected value of
-        // the final carry digit.
-        'check_new_gate_pair: for (involved_gate, unverified_gate) in involved_gates
-            .difference(&verified_gates)
-            .cartesian_product(gates.difference(&verified_gates))
-        {
-            if *involved_gate != *unverified_gate {
-                let mut new_gates = gates.clone();
-                new_gates.retain(|gate| {
-                    gate.output_wire != involved_gate.output_wire
-                        && gate.output_wire != unverified_gate.output_wire
-                });
-                new_gates.insert(Gate {
-                    operation: involved_gate.operation.clone(),
-                    first_wire: involved_gate.first_wire,
-                    second_wire: involved_gate.second_wire,
-                    output_wire: unverified_gate.output_wire,
-                });
-                new_gates.insert(Gate {
-                    operation: unverified_gate.operation.clone(),
-                    first_wire: unverified_gate.first_wire,
-                    second_wire: unverified_gate.second_wire,
-                    output_wire: involved_gate.output_wire,
-                });
-
-                let mut involved_gates = FxHashSet::default();
-                let mut involved_outputs = vec![output_wire.as_str()];
-                let mut found_outputs = FxHashSet::default();
-                while let Some(output_wire) = involved_outputs.pop() {
-                    if found_outputs.contains(output_wire) {
-                        continue 'check_new_gate_pair;
-                    }
-                    found_outputs.insert(output_wire);
-
-                    let gate = new_gates.iter().find(|gate| gate.output_wire == output_wire).expect("Every wire that doesn't begin with x or y should have a corresponding gate that outputs it.");
-                    involved_gates.insert(gate);
-
-                    if !(gate.first_wire.starts_with('x') || gate.first_wire.starts_with('y')) {
-                        involved_outputs.push(gate.first_wire);
-                    }
-                    if !(gate.second_wire.starts_with('x') || gate.second_wire.starts_with('y')) {
-                        involved_outputs.push(gate.second_wire);
-                    }
+// Checks every gate against the role it plays in a ripple-carry adder, and returns the gates that
+// are out of place.
+fn find_violations<'a>(circuit: &Circuit<'a, GateOperation>) -> Vec<Violation<'a>> {
+    let input_size = get_input_size(circuit);
+    let is_input_bit = |input: &Input<bool>| match input {
+        Input::Wire(wire) => circuit.wire_name(*wire).starts_with(['x', 'y']),
+        Input::Value(_) => false,
+    };
+    let is_first_input_bit = |input: &Input<bool>| match input {
+        Input::Wire(wire) => matches!(circuit.wire_name(*wire), "x00" | "y00"),
+        Input::Value(_) => false,
+    };
+
+    let mut violations = Vec::new();
+    for (gate_id, gate) in circuit.gates().iter().enumerate() {
+        let wire = circuit.wire_name(gate.output);
+        let is_read_by = |operation| {
+            circuit
+                .readers(gate.output)
+                .iter()
+                .any(|reader| circuit.gates()[*reader].operation == operation)
+        };
+        let reads_input_bits = gate.inputs.iter().all(is_input_bit);
+        let is_first_bit = gate.inputs.iter().all(is_first_input_bit);
+
+        let output_index = wire
+            .strip_prefix('z')
+            .and_then(|output_index| output_index.parse::<usize>().ok());
+        let rule = if let Some(output_index) = output_index {
+            // The carry out of the last bit is an OR gate, except for a single-bit adder where
+            // it's the AND gate of the half adder.
+            let final_carry_operation = if input_size == 1 {
+                GateOperation::And
+            } else {
+                GateOperation::Or
+            };
+            if output_index == input_size {
+                (gate.operation != final_carry_operation).then_some(Rule::FinalCarryNotCarry)
+            } else {
+                (gate.operation != GateOperation::Xor).then_some(Rule::OutputNotSum)
+            }
+        } else {
+            match gate.operation {
+                // The XOR of the first input bits is the first output bit, and any other XOR that
+                // doesn't read input bits makes an output bit.
+                GateOperation::Xor if is_first_bit || !reads_input_bits => Some(Rule::SumNotOutput),
+                GateOperation::Xor => {
+                    (!is_read_by(GateOperation::Xor)).then_some(Rule::PartialSumNotSummed)
                 }
-
-                for values in repeat_n([false, true], 3).multi_cartesian_product() {
-                    let expected_final_carry_value =
-                        (values[0] && values[1]) || (values[2] && (values[0] ^ values[1]));
-
-                    let mut wire_values = FxHashMap::default();
-                    wire_values.insert(x_input_wire.as_str(), values[0]);
-                    wire_values.insert(y_input_wire.as_str(), values[1]);
-                    for preceding_x_input_wire in &preceding_x_input_wires {
-                        wire_values.insert(preceding_x_input_wire.as_str(), values[2]);
-                    }
-                    for preceding_y_input_wire in &preceding_y_input_wires {
-                        wire_values.insert(preceding_y_input_wire.as_str(), values[2]);
-                    }
-
-                    let mut pending_gates = involved_gates.clone();
-                    while !wire_values.contains_key(&output_wire.as_str()) {
-                        let starting_len = pending_gates.len();
-                        pending_gates.retain(|gate| !gate.execute(&mut wire_values));
-                        if pending_gates.len() == starting_len {
-                            continue 'check_new_gate_pair;
-                        }
-                    }
-
-                    if *wire_values.get(&output_wire.as_str()).expect(
-                        "This key should have just been inserted in the preceding while loop",
-                    ) != expected_final_carry_value
-                    {
-                        continue 'check_new_gate_pair;
-                    }
+                // The AND of the first input bits is the carry into the second bit.
+                GateOperation::And if is_first_bit => {
+                    (!is_read_by(GateOperation::Xor)).then_some(Rule::CarryNotPropagated)
+                }
+                GateOperation::And => {
+                    (!is_read_by(GateOperation::Or)).then_some(Rule::PartialCarryNotCombined)
+                }
+                GateOperation::Or => {
+                    (!is_read_by(GateOperation::Xor)).then_some(Rule::CarryNotPropagated)
                 }
-
-                swapped_output_wires.insert(involved_gate.output_wire);
-                swapped_output_wires.insert(unverified_gate.output_wire);
-
-                let first_new_gate = Gate {
-                    operation: involved_gate.operation.clone(),
-                    first_wire: involved_gate.first_wire,
-                    second_wire: involved_gate.second_wire,
-                    output_wire: unverified_gate.output_wire,
-                };
-                let second_new_gate = Gate {
-                    operation: unverified_gate.operation.clone(),
-                    first_wire: unverified_gate.first_wire,
-                    second_wire: unverified_gate.second_wire,
-                    output_wire: involved_gate.output_wire,
-                };
-
-                gates.retain(|gate| {
-                    gate.output_wire != first_new_gate.output_wire
-                        && gate.output_wire != second_new_gate.output_wire
-                });
-                gates.insert(first_new_gate);
-                gates.insert(second_new_gate);
-
-                break;
             }
+        };
+
+        if let Some(rule) = rule {
+            // An output bit's gate belongs to that bit, and the final carry belongs to the last
+            // bit. Any other gate belongs to the highest bit of the inputs it depends on, as the
+            // carry chain only brings in lower bits.
+            let bit = match rule {
+                Rule::OutputNotSum => output_index.unwrap_or_default(),
+                Rule::FinalCarryNotCarry => input_size - 1,
+                _ => get_highest_input_bit(circuit, gate.output),
+            };
+            violations.push(Violation {
+                gate: gate_id,
+                wire,
+                operation: gate.operation,
+                rule,
+                bit,
+            });
         }
     }
+    violations
+}
 
-    // Get the names of output wires that have been swapped (which are automatically sorted
-    // alphabetically due to being stored in a BTreeSet) and join them to create the final output.
-    let joined_swapped_output_wires = swapped_output_wires.iter().join(",");
-    Solution::String(joined_swapped_output_wires)
+// Returns the highest index of the input digits that a wire's signal depends on.
+fn get_highest_input_bit(circuit: &Circuit<GateOperation>, wire: WireId) -> usize {
+    circuit
+        .fan_in(wire)
+        .into_iter()
+        .flat_map(|gate| &circuit.gates()[gate].inputs)
+        .filter_map(|input| match input {
+            Input::Wire(wire) => circuit
+                .wire_name(*wire)
+                .strip_prefix(['x', 'y'])?
+                .parse()
+                .ok(),
+            Input::Value(_) => None,
+        })
+        .max()
+        .unwrap_or_default()
 }
 
-#[derive(Clone, PartialEq, Eq, Hash)]
-struct Gate<'a, 'b, 'c> {
-    operation: GateOperation,
-    first_wire: &'a str,
-    second_wire: &'b str,
-    output_wire: &'c str,
+// Returns how many binary digits are in the input numbers.
+fn get_input_size(circuit: &Circuit<GateOperation>) -> usize {
+    circuit
+        .wires()
+        .filter(|(_, name)| name.starts_with('x'))
+        .count()
 }
-impl<'c> Gate<'_, '_, 'c> {
-    // Attempts to execute the gate's operation on its input wires. If either of the input wires
-    // don't have a value yet, this execution fails. If both input wires have a value, this
-    // execution succeeds and the result is written to the output wire. This method returns a bool
-    // indicating whether execution succeeded so the caller knows whether it needs to check this
-    // gate again in the future.
-    fn execute(&self, wire_values: &mut FxHashMap<&'c str, bool>) -> bool {
-        if let Some((first_value, second_value)) = wire_values
-            .get(&self.first_wire)
-            .zip(wire_values.get(&self.second_wire))
-        {
-            let output_value = match self.operation {
-                GateOperation::And => *first_value && *second_value,
-                GateOperation::Or => *first_value || *second_value,
-                GateOperation::Xor => *first_value ^ *second_value,
-            };
-            wire_values.insert(self.output_wire, output_value);
-            return true;
+
+// Checks that an output digit is correct for every combination of the two input digits for that
+// position and the carry-in digit. The output digit should be the XOR of these three digits, except
+// for the first digit which uses a half-adder so it disregards the carry-in digit, and the final
+// carry-out digit which should be true if at least two of the last input digits and their carry-in
+// digit are true.
+//
+// The carry-in digit can't be set directly, so it's set through the preceding input digits. It's
+// false if every preceding digit is false, as no carry would ever occur. It's true if every
+// preceding digit is true, as the previous digits generate a carry, and also if the previous digits
+// are different and every digit before them is true, as they then propagate the carry from the
+// digits before. Checking both kinds of carry makes sure every gate of the previous digits is used.
+// Later input digits are left without a signal, as the output digit shouldn't depend on them.
+fn is_output_digit_valid(
+    circuit: &Circuit<GateOperation>,
+    output_index: usize,
+    input_size: usize,
+) -> bool {
+    let output_wire = get_wire(circuit, 'z', output_index);
+    let input_index = output_index.min(input_size - 1);
+    let digit_pairs = [(false, false), (false, true), (true, false), (true, true)];
+
+    // Pairs each way of setting the preceding digits with the carry-in digit it gives. The first
+    // input digits use a half-adder, so there's no carry-in digit, and a carry can only be
+    // propagated through digits after the first.
+    let mut preceding_digits = vec![(false, vec![(false, false); input_index])];
+    if input_index > 0 {
+        preceding_digits.push((true, vec![(true, true); input_index]));
+    }
+    if input_index > 1 {
+        for propagating_digits in [(false, true), (true, false)] {
+            let mut digits = vec![(true, true); input_index];
+            digits[input_index - 1] = propagating_digits;
+            preceding_digits.push((true, digits));
         }
-        false
     }
+
+    preceding_digits.iter().cartesian_product(digit_pairs).all(
+        |((carry_in, preceding_digits), (x_value, y_value))| {
+            let expected_output_value = if output_index == input_size {
+                (x_value && y_value) || (*carry_in && (x_value ^ y_value))
+            } else {
+                x_value ^ y_value ^ carry_in
+            };
+
+            let mut signals = vec![
+                (get_wire(circuit, 'x', input_index), x_value),
+                (get_wire(circuit, 'y', input_index), y_value),
+            ];
+            for (preceding_index, (x_value, y_value)) in preceding_digits.iter().enumerate() {
+                signals.push((get_wire(circuit, 'x', preceding_index), *x_value));
+                signals.push((get_wire(circuit, 'y', preceding_index), *y_value));
+            }
+
+            circuit
+                .evaluate(signals)
+                .is_ok_and(|output| output[output_wire] == Some(expected_output_value))
+        },
+    )
 }
 
-#[derive(Clone, PartialEq, Eq, Hash)]
+// Returns the wire named by a letter followed by a two-digit index, such as "x05".
+fn get_wire(circuit: &Circuit<GateOperation>, letter: char, index: usize) -> WireId {
+    circuit
+        .wire_id(&format!("{letter}{index:0>2}"))
+        .expect("Circuit should have a wire for every digit")
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
 enum GateOperation {
     And,
     Or,
     Xor,
 }
 
-fn get_wire_values_and_gates(input: &str) -> (FxHashMap<&str, bool>, FxHashSet<Gate<'_, '_, '_>>) {
-    let mut wire_values = FxHashMap::default();
-    let mut gates = FxHashSet::default();
-    let mut line_iter = input.lines();
-
-    // Get the wires with an initial value and insert them into the wire_values map. Stop iterating
-    // when a blank line is reached, as this indicates that the input is swapping to describing
-    // gates. Use by_ref() to borrow line_iter instead of consuming it, so it can be reused in a
-    // later for loop.
-    for line in line_iter.by_ref() {
-        if line.is_empty() {
-            break;
-        }
+impl Display for GateOperation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            GateOperation::And => "AND",
+            GateOperation::Or => "OR",
+            GateOperation::Xor => "XOR",
+        })
+    }
+}
 
-        let mut str_iter = line.split(": ");
-        let wire = str_iter.next().expect("Line should have first value");
-        // Assuming that the second string can only be "0" or "1", set value to whether this second
-        // string equals "1".
-        let value = str_iter.next().expect("Line should have second value") == "1";
-        wire_values.insert(wire, value);
+impl Operation for GateOperation {
+    type Value = bool;
+
+    fn from_keyword(keyword: Option<&str>) -> Option<Self> {
+        match keyword {
+            Some("AND") => Some(GateOperation::And),
+            Some("OR") => Some(GateOperation::Or),
+            Some("XOR") => Some(GateOperation::Xor),
+            _ => None,
+        }
     }
 
-    // Get each gate's operation, input wire names, and output wire name. Insert everything into the
-    // pending_gates vector (as no gate has been executed yet so every gate is pending).
-    for line in line_iter {
-        let mut outer_str_iter = line.split(" -> ");
-        let mut inner_str_iter = outer_str_iter
-            .next()
-            .expect("Line should have values before the \" -> \"")
-            .split(' ');
-        let first_wire = inner_str_iter
-            .next()
-            .expect("Line should have first value before the \" -> \"");
-        let operation = match inner_str_iter
-            .next()
-            .expect("Line should have second value before the \" -> \"")
-        {
-            "AND" => GateOperation::And,
-            "OR" => GateOperation::Or,
-            "XOR" => GateOperation::Xor,
-            _ => panic!("Unsupported gate operation encountered"),
-        };
-        let second_wire = inner_str_iter
-            .next()
-            .expect("Line should have third value before the \" -> \"");
-        let output_wire = outer_str_iter
-            .next()
-            .expect("Line should have value after the \" -> \"");
-        gates.insert(Gate {
-            operation,
-            first_wire,
-            second_wire,
-            output_wire,
-        });
+    fn evaluate(&self, inputs: &[bool]) -> bool {
+        match self {
+            GateOperation::And => inputs[0] && inputs[1],
+            GateOperation::Or => inputs[0] || inputs[1],
+            GateOperation::Xor => inputs[0] ^ inputs[1],
+        }
     }
+}
 
-    (wire_values, gates)
+// The input lists the wires with an initial signal, then a blank line, then the gates.
+fn get_initial_signals_and_circuit(
+    input: &str,
+) -> (Vec<(WireId, bool)>, Circuit<'_, GateOperation>) {
+    let (initial_signals, gates) = input
+        .split_once("\n\n")
+        .expect("Input should have a blank line between the initial signals and the gates");
+    let mut circuit = Circuit::parse(gates);
+
+    let initial_signals = initial_signals
+        .lines()
+        .map(|line| {
+            let (wire, value) = line
+                .split_once(": ")
+                .expect("Line should have a wire and a value");
+            // Assuming that the value can only be "0" or "1", set the signal to whether the value
+            // equals "1".
+            (circuit.wire(wire), value == "1")
+        })
+        .collect();
+
+    (initial_signals, circuit)
 }
 
 #[cfg(test)]
@@ -635,5 +596,110 @@ tnw OR pbm -> gnj"
     }
 
     // The example for part 2 is omitted as it uses bitwise AND instead of summation as the
-    // operation the gates are trying to implement.
+    // operation the gates are trying to implement. Instead, this is a 4-bit adder with the outputs
+    // of x01 AND y01 and z01 swapped, and the outputs of x02 XOR y02 and x02 AND y02 swapped.
+    const SWAPPED_ADDER: &str = "\
+x00: 1
+x01: 0
+x02: 0
+x03: 1
+y00: 0
+y01: 1
+y02: 0
+y03: 1
+
+x03 XOR y03 -> snq
+x02 AND y02 -> bft
+fek OR cjf -> mcv
+x00 AND y00 -> csa
+x03 AND y03 -> kec
+bft AND spt -> cjf
+x00 XOR y00 -> z00
+x01 XOR y01 -> nqc
+bft XOR spt -> z02
+nqc XOR csa -> uhc
+x02 XOR y02 -> fek
+snq AND mcv -> kqj
+x01 AND y01 -> z01
+nqc AND csa -> tjp
+uhc OR tjp -> spt
+kec OR kqj -> z04
+snq XOR mcv -> z03";
+
+    #[test]
+    fn swapped_adder() {
+        assert_eq!(
+            solve_2(SWAPPED_ADDER),
+            Solution::String("bft,fek,uhc,z01".to_string())
+        );
+
+        let (_, circuit) = get_initial_signals_and_circuit(SWAPPED_ADDER);
+        let swaps = find_swaps(&circuit)
+            .expect("Circuit should be repairable")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert!(
+            swaps.contains(
+                &"Swapped uhc and z01: uhc is the XOR of a partial sum and a carry, but isn't an \
+              output bit, and z01 is an output bit, but is driven by an AND gate instead of XOR"
+                    .to_string()
+            )
+        );
+    }
+
+    // Swapping two output bits doesn't move either gate out of its role, so this can only be found
+    // by checking the output bits.
+    #[test]
+    fn swapped_output_bits() {
+        let input = "\
+x00: 1
+x01: 0
+x02: 0
+x03: 1
+y00: 0
+y01: 1
+y02: 0
+y03: 1
+
+x03 XOR y03 -> snq
+x02 AND y02 -> fek
+fek OR cjf -> mcv
+x00 AND y00 -> csa
+x03 AND y03 -> kec
+bft AND spt -> cjf
+x00 XOR y00 -> z00
+x01 XOR y01 -> nqc
+bft XOR spt -> z01
+nqc XOR csa -> z02
+x02 XOR y02 -> bft
+snq AND mcv -> kqj
+x01 AND y01 -> uhc
+nqc AND csa -> tjp
+uhc OR tjp -> spt
+kec OR kqj -> z04
+snq XOR mcv -> z03";
+        assert_eq!(solve_2(input), Solution::String("z01,z02".to_string()));
+    }
+
+    #[test]
+    fn unrepairable_circuit() {
+        let input = "\
+x00: 1
+y00: 0
+
+x00 OR y00 -> z00
+x00 OR y00 -> z01";
+        let (_, circuit) = get_initial_signals_and_circuit(input);
+        assert!(find_swaps(&circuit).is_none());
+
+        // The graph is still drawn, just without any highlighted wires.
+        let mut output = Vec::new();
+        export_graph(input)
+            .write("circuit", &mut output)
+            .expect("Writing to a Vec can't fail");
+        let output = String::from_utf8(output).expect("DOT output should be valid UTF-8");
+        assert!(output.contains("\"x00\" -> \"z01\" [label=\"OR\"];"));
+        assert!(!output.contains("fillcolor"));
+    }
 }
@@ -1,3 +1,4 @@
+use crate::solver::common::range_set::RangeSet;
 use crate::solver::{AdventOfCode, Solution};
 use std::cmp::{Ordering, max};
 
@@ -37,21 +38,40 @@ fn solve_2(input: &str) -> Solution {
     Solution::U64(invalid_id_sum)
 }
 
+// Gets the ranges of IDs as inclusive lower and upper bounds. Overlapping ranges are merged so no
+// ID is counted twice, and ranges are split wherever the number of digits changes, so that every
+// ID within a range has the same number of digits.
 fn get_ranges(input: &str) -> Vec<(u64, u64)> {
-    let mut ranges = Vec::new();
+    let ids = input
+        .split(',')
+        .map(|range_str| {
+            let (lower_bound_str, upper_bound_str) = range_str
+                .split_once('-')
+                .expect("Range string should contain '-'");
+            let lower_bound: u64 = lower_bound_str
+                .parse()
+                .expect("Lower bound should be a number");
+            let upper_bound: u64 = upper_bound_str
+                .parse()
+                .expect("Upper bound should be a number");
+            // The set holds ranges that end one past the upper bound, which can't be represented
+            // for the largest u64.
+            let end = upper_bound
+                .checked_add(1)
+                .expect("Upper bound should be less than the largest u64");
+            lower_bound..end
+        })
+        .collect::<RangeSet<_>>();
 
-    for range_str in input.split(',') {
-        let (lower_bound_str, upper_bound_str) = range_str
-            .split_once('-')
-            .expect("Range string should contain '-'");
-        let lower_bound = lower_bound_str
-            .parse()
-            .expect("Lower bound should be a number");
-        let upper_bound = upper_bound_str
-            .parse()
-            .expect("Upper bound should be a number");
-
-        ranges.push((lower_bound, upper_bound));
+    // The largest u64 has 20 digits, and 10^20 doesn't fit in a u64, so the band of 20-digit IDs
+    // ends at the largest u64 instead.
+    let mut ranges = Vec::new();
+    for digits in 1..=u64::MAX.ilog10() + 1 {
+        let end = 10_u64.checked_pow(digits).unwrap_or(u64::MAX);
+        let same_digit_ids = RangeSet::from(10_u64.pow(digits - 1)..end);
+        for range in ids.intersection(&same_digit_ids).iter() {
+            ranges.push((range.start, range.end - 1));
+        }
     }
 
     ranges
@@ -96,10 +116,7 @@ fn solve_with_repetitions(
         }
 
         // At this point, the lower and upper bounds are guaranteed to have the same number of
-        // digits (this isn't strictly guaranteed in general, but it is assumed no range spans more
-        // than one order of magnitude, e.g 97-6403, as this doesn't appear in the puzzle input and
-        // this would take much more complicated logic to properly handle. If this assumption holds,
-        // then there is only a maximum one order of magnitude that can work for any range). Split
+        // digits, as get_ranges() splits the ranges wherever the number of digits changes. Split
         // these bounds into a number of chunks equal to the specified number of repetitions in the
         // invalid ID.
         let sequence_len = target_lower_bound_len / repetitions;
@@ -247,4 +264,18 @@ mod test {
             Solution::U32(4_174_379_265)
         );
     }
+
+    #[test]
+    fn twenty_digit_ids() {
+        assert_eq!(
+            solve_1("10000000000999999999-10000000001000000000"),
+            Solution::U64(10_000_000_001_000_000_000)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Upper bound should be less than the largest u64")]
+    fn largest_upper_bound() {
+        solve_1("1-18446744073709551615");
+    }
 }
@@ -1,3 +1,5 @@
+use crate::solver::common::geometry::Point3;
+use crate::solver::common::union_find::UnionFind;
 use crate::solver::{AdventOfCode, Solution};
 use std::collections::BTreeMap;
 
@@ -8,15 +10,11 @@ pub const SOLVER: AdventOfCode = AdventOfCode {
     part_solvers: &[solve_1, solve_2],
 };
 
-struct Position {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-impl Position {
-    fn distance_square(&self, other: &Position) -> i64 {
-        (self.x - other.x).pow(2) + (self.y - other.y).pow(2) + (self.z - other.z).pow(2)
-    }
+type Position = Point3<i64>;
+
+fn get_distance_square(position: Position, other: Position) -> i64 {
+    let difference = position - other;
+    difference.x.pow(2) + difference.y.pow(2) + difference.z.pow(2)
 }
 
 fn get_positions(input: &str) -> Vec<Position> {
@@ -70,7 +68,7 @@ fn solve_1_with_pairs(input: &str, pairs: usize) -> Solution {
         let other_positions_iter = positions_iter.clone();
 
         for (other_index, other_position) in other_positions_iter {
-            let distance_square = position.distance_square(other_position);
+            let distance_square = get_distance_square(*position, *other_position);
 
             if smallest_distance_squares.len() < pairs {
                 smallest_distance_squares.insert(distance_square, (index, other_index));
@@ -82,111 +80,19 @@ fn solve_1_with_pairs(input: &str, pairs: usize) -> Solution {
     }
 
     // At this point, we essentially have a graph with nodes given by ids 0 to positions.len() - 1,
-    // and edges given by the values in smallest_distance_squares. Use breadth-first-search on each
-    // unvisited node to find all connected nodes from it, mark them as visited, and record the size
-    // of the group.
-    let mut circuit_sizes = Vec::with_capacity(positions.len());
-    let mut is_visited = vec![false; positions.len()];
-    let mut root_node = 0;
-
-    loop {
-        let mut circuit_size = 1;
-        is_visited[root_node] = true;
-        let mut queue = Vec::with_capacity(positions.len());
-        // This isn't a queue as it's first-in-last-out, so this isn't technically breadth-first
-        // search, but this doesn't matter as we simply want to visit all connected nodes once, no
-        // matter the order.
-        queue.push(root_node);
-
-        while let Some(node) = queue.pop() {
-            for edge in smallest_distance_squares.values() {
-                if edge.0 == node && !is_visited[edge.1] {
-                    is_visited[edge.1] = true;
-                    queue.push(edge.1);
-                    circuit_size += 1;
-                } else if edge.1 == node && !is_visited[edge.0] {
-                    is_visited[edge.0] = true;
-                    queue.push(edge.0);
-                    circuit_size += 1;
-                }
-            }
-        }
-
-        circuit_sizes.push(circuit_size);
-        let next_root_node_offset = is_visited.iter().skip(root_node).position(|node| !node);
-        if let Some(next_root_node_offset) = next_root_node_offset {
-            root_node += next_root_node_offset;
-        } else {
-            break;
-        }
+    // and edges given by the values in smallest_distance_squares. Use the Union Find data structure
+    // to merge the circuits joined by each edge, then get the size of every circuit.
+    let mut union_find = UnionFind::new(positions.len());
+    for edge in smallest_distance_squares.values() {
+        union_find.union(edge.0, edge.1);
     }
+    let mut circuit_sizes = union_find.component_sizes();
 
     // Sort by b.cmp(a) to get results in reversed order, so the three biggest circuits are at the
     // front.
     circuit_sizes.sort_unstable_by(|a, b| b.cmp(a));
 
-    Solution::U32(circuit_sizes[0] * circuit_sizes[1] * circuit_sizes[2])
-}
-
-struct Node {
-    parent: usize,
-    // Size counts how many child nodes are under this node.
-    size: u32,
-}
-
-// A Union Find data structure, also known as a Disjoint Set Union, is an efficient way to represent
-// sets of elements which can be merged. This is used to keep track of all distinct circuits that
-// junction boxes are connected in, and merge circuits when junction boxes from two different
-// circuits are connected. Rust has no built-in Union Find data structure, so I manually implement
-// one here.
-struct UnionFind {
-    nodes: Vec<Node>,
-}
-impl UnionFind {
-    fn new(size: usize) -> UnionFind {
-        let nodes = (0..size)
-            .map(|index| Node {
-                parent: index,
-                size: 1,
-            })
-            .collect();
-        UnionFind { nodes }
-    }
-
-    fn find_root(&self, node: usize) -> usize {
-        let parent = self.nodes[node].parent;
-        if node == parent {
-            node
-        } else {
-            self.find_root(parent)
-        }
-    }
-
-    // Merges the sets containing the specified nodes, then returns the size of the combined set.
-    fn merge(&mut self, node_a: usize, node_b: usize) -> u32 {
-        let root_a = self.find_root(node_a);
-        let root_b = self.find_root(node_b);
-
-        let size_a = self.nodes[root_a].size;
-        let size_b = self.nodes[root_b].size;
-
-        if root_a == root_b {
-            return size_a;
-        }
-
-        // Making the root node with fewer descendants become a child of the root node with more
-        // descendants helps maintain a flatter tree structure, reducing how many levels of
-        // recursion are needed to find the root node.
-        if size_a > size_b {
-            self.nodes[root_b].parent = node_a;
-            self.nodes[root_a].size = size_a + size_b;
-        } else {
-            self.nodes[root_a].parent = node_b;
-            self.nodes[root_b].size = size_a + size_b;
-        }
-
-        size_a + size_b
-    }
+    Solution::USize(circuit_sizes[0] * circuit_sizes[1] * circuit_sizes[2])
 }
 
 fn solve_2(input: &str) -> Solution {
@@ -202,22 +108,21 @@ fn solve_2(input: &str) -> Solution {
         let other_positions_iter = positions_iter.clone();
 
         for (other_index, other_position) in other_positions_iter {
-            let distance_square = position.distance_square(other_position);
+            let distance_square = get_distance_square(*position, *other_position);
 
             distance_squares.insert(distance_square, (index, other_index));
         }
     }
 
     // Use the Union Find data structure to keep track of each individual circuit, merging them each
-    // time the next closest pair of junction boxes get connected. Check the size of the new circuit
-    // after each merge. If it is the same value as the total number of junction boxes, then this
-    // last connection formed a single circuit containing every junction box.
-    let full_size = positions.len();
-    let mut union_find = UnionFind::new(full_size);
+    // time the next closest pair of junction boxes get connected. Check the number of circuits
+    // after each merge. If there's only one left, then this last connection formed a single
+    // circuit containing every junction box.
+    let mut union_find = UnionFind::new(positions.len());
 
     for edge in distance_squares.values() {
-        let size = union_find.merge(edge.0, edge.1);
-        if size as usize == full_size {
+        union_find.union(edge.0, edge.1);
+        if union_find.component_count() == 1 {
             return Solution::I64(positions[edge.0].x * positions[edge.1].x);
         }
     }
@@ -0,0 +1,58 @@
+use advent_of_code_rust::common::vm::trace::{TraceOptions, Tracer};
+use advent_of_code_rust::{TRACEABLE_SOLVERS, TraceableSolver};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufWriter, Write};
+
+// Runs the program in the puzzle input for the given day with a tracer that reports to stdout.
+pub fn trace(
+    year: u16,
+    day: u8,
+    part: u8,
+    options: TraceOptions,
+    trace_path: Option<String>,
+    interactive: bool,
+) {
+    let traceable_solver = find_traceable_solver(year, day);
+    assert!(
+        (1..=traceable_solver.solver.part_solvers.len()).contains(&usize::from(part)),
+        "{year} day {day} doesn't have a part {part}"
+    );
+    let input =
+        fs::read_to_string(traceable_solver.solver.input_path()).expect("Error reading file");
+
+    let mut stdout = io::stdout().lock();
+    let mut trace_file = trace_path
+        .map(|path| BufWriter::new(File::create(path).expect("Error creating trace file")));
+    let mut stdin = interactive.then(|| io::stdin().lock());
+    let mut tracer = Tracer::new(
+        options,
+        &mut stdout,
+        trace_file.as_mut().map(|file| file as &mut dyn Write),
+        stdin.as_mut().map(|stdin| stdin as &mut dyn BufRead),
+    );
+
+    (traceable_solver.trace)(&input, part, &mut tracer).expect("Error tracing program");
+    if let Some(trace_file) = &mut trace_file {
+        trace_file.flush().expect("Error writing trace file");
+    }
+}
+
+// Writes the program in the puzzle input for the given day to stdout as pseudo-code.
+pub fn print_disassembly(year: u16, day: u8) {
+    let traceable_solver = find_traceable_solver(year, day);
+    let disassemble = traceable_solver
+        .disassemble
+        .unwrap_or_else(|| panic!("{year} day {day} doesn't have a disassembler"));
+    let input =
+        fs::read_to_string(traceable_solver.solver.input_path()).expect("Error reading file");
+    disassemble(&input, &mut io::stdout().lock()).expect("Error disassembling program");
+}
+
+fn find_traceable_solver(year: u16, day: u8) -> &'static TraceableSolver {
+    TRACEABLE_SOLVERS
+        .iter()
+        .find(|traceable_solver| {
+            traceable_solver.solver.year == year && traceable_solver.solver.day == day
+        })
+        .unwrap_or_else(|| panic!("{year} day {day} doesn't have a program that can be traced"))
+}